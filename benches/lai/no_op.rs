@@ -1,3 +1,5 @@
+#![allow(clippy::field_reassign_with_default)]
+
 use iai::black_box;
 use tokio::task::JoinSet;
 
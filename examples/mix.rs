@@ -34,12 +34,7 @@ fn main() {
 
                 loop {
                     // Read a chunk
-<<<<<<< HEAD
-                    let (res, b) = file.read_at(buf, pos).submit().await;
-                    let n = res.unwrap();
-=======
-                    let (n, b) = file.read_at(buf, pos).await.unwrap();
->>>>>>> 6b5865f (Refresh: Update Result<T, B> to be Result<(T,B), Error<B>)
+                    let (n, b) = file.read_at(buf, pos).submit().await.unwrap();
 
                     if n == 0 {
                         break;
@@ -1,7 +1,7 @@
 // An example of an echo server using fixed buffers for reading and writing TCP streams.
 // A buffer registry size of two is created, to allow a maximum of two simultaneous connections.
 
-use std::{env, iter, net::SocketAddr};
+use std::{env, net::SocketAddr};
 
 use tokio_uring::{
     buf::{fixed::FixedBufRegistry, BoundedBuf, IoBufMut},
@@ -34,7 +34,7 @@ async fn accept_loop(listen_addr: SocketAddr) {
     );
 
     // Other iterators may be passed to FixedBufRegistry::new also.
-    let registry = FixedBufRegistry::new(iter::repeat(vec![0; 4096]).take(POOL_SIZE));
+    let registry = FixedBufRegistry::new(std::iter::repeat_n(vec![0; 4096], POOL_SIZE));
 
     // Register the buffers with the kernel, asserting the syscall passed.
 
@@ -3,10 +3,10 @@ use std::rc::Rc;
 use tokio::task::JoinHandle;
 use tokio_uring::Submit;
 
-pub const RESPONSE: &'static [u8] =
+pub const RESPONSE: &[u8] =
     b"HTTP/1.1 200 OK\nContent-Type: text/plain\nContent-Length: 12\n\nHello world!";
 
-pub const ADDRESS: &'static str = "127.0.0.1:8080";
+pub const ADDRESS: &str = "127.0.0.1:8080";
 
 fn main() -> io::Result<()> {
     tokio_uring::start(async {
@@ -0,0 +1,197 @@
+use crate::buf::{IoBuf, IoBufMut};
+
+use std::alloc::{self, Layout};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::ptr::{self, NonNull};
+
+/// A buffer whose memory is aligned to a given alignment, as direct I/O
+/// (`O_DIRECT`) requires.
+///
+/// Like a `Vec<u8>`, the buffer has a fixed capacity, of which the first
+/// [`len`](AlignedBuf::len) bytes are initialized. Reads fill it from the
+/// start, and writes write its initialized bytes. It dereferences to its
+/// initialized bytes.
+///
+/// See [`DirectFile::alloc_buf`](crate::fs::DirectFile::alloc_buf) to get a
+/// buffer suited to a file.
+///
+/// # Examples
+///
+/// ```
+/// use tokio_uring::buf::AlignedBuf;
+///
+/// let mut buf = AlignedBuf::with_capacity(4096, 512);
+/// assert_eq!(buf.as_ptr() as usize % 512, 0);
+///
+/// buf.extend_from_slice(b"hello");
+/// assert_eq!(&buf[..], b"hello");
+/// ```
+pub struct AlignedBuf {
+    ptr: NonNull<u8>,
+    len: usize,
+    layout: Layout,
+}
+
+impl AlignedBuf {
+    /// Allocates an empty buffer of `capacity` bytes, aligned to `align`
+    /// bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero, or if `align` is not a power of two.
+    pub fn with_capacity(capacity: usize, align: usize) -> AlignedBuf {
+        AlignedBuf::alloc(capacity, align, false)
+    }
+
+    /// Allocates a buffer of `len` zeroed bytes, aligned to `align` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is zero, or if `align` is not a power of two.
+    pub fn zeroed(len: usize, align: usize) -> AlignedBuf {
+        let mut buf = AlignedBuf::alloc(len, align, true);
+        buf.len = len;
+        buf
+    }
+
+    fn alloc(capacity: usize, align: usize, zeroed: bool) -> AlignedBuf {
+        assert!(capacity > 0, "capacity must be greater than zero");
+        let layout = Layout::from_size_align(capacity, align)
+            .expect("align must be a power of two, and the capacity not overflow");
+
+        // Safety: the size of the layout is not zero.
+        let ptr = unsafe {
+            if zeroed {
+                alloc::alloc_zeroed(layout)
+            } else {
+                alloc::alloc(layout)
+            }
+        };
+        let ptr = NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+
+        AlignedBuf {
+            ptr,
+            len: 0,
+            layout,
+        }
+    }
+
+    /// Returns the number of initialized bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no byte is initialized.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the capacity of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.layout.size()
+    }
+
+    /// Returns the alignment of the buffer.
+    pub fn alignment(&self) -> usize {
+        self.layout.align()
+    }
+
+    /// Marks all bytes as uninitialized, keeping the memory.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Appends `data` to the initialized bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` doesn't fit in the remaining capacity.
+    pub fn extend_from_slice(&mut self, data: &[u8]) {
+        assert!(
+            data.len() <= self.capacity() - self.len,
+            "data exceeds the capacity of the buffer"
+        );
+        // Safety: the destination is within the allocation, which `data`
+        // can't overlap since it is borrowed immutably.
+        unsafe {
+            ptr::copy_nonoverlapping(data.as_ptr(), self.ptr.as_ptr().add(self.len), data.len());
+        }
+        self.len += data.len();
+    }
+
+    /// Fills the rest of the capacity with zeroes, making all bytes
+    /// initialized, e.g. to pad data to the length direct I/O requires.
+    pub fn zero_pad(&mut self) {
+        // Safety: the range is within the allocation.
+        unsafe {
+            ptr::write_bytes(
+                self.ptr.as_ptr().add(self.len),
+                0,
+                self.capacity() - self.len,
+            );
+        }
+        self.len = self.capacity();
+    }
+}
+
+// Safety: the buffer owns its memory, like a `Vec<u8>`.
+unsafe impl Send for AlignedBuf {}
+unsafe impl Sync for AlignedBuf {}
+
+impl Drop for AlignedBuf {
+    fn drop(&mut self) {
+        // Safety: the memory was allocated with this layout.
+        unsafe { alloc::dealloc(self.ptr.as_ptr(), self.layout) }
+    }
+}
+
+unsafe impl IoBuf for AlignedBuf {
+    fn stable_ptr(&self) -> *const u8 {
+        self.ptr.as_ptr()
+    }
+
+    fn bytes_init(&self) -> usize {
+        self.len
+    }
+
+    fn bytes_total(&self) -> usize {
+        self.capacity()
+    }
+}
+
+unsafe impl IoBufMut for AlignedBuf {
+    fn stable_mut_ptr(&mut self) -> *mut u8 {
+        self.ptr.as_ptr()
+    }
+
+    unsafe fn set_init(&mut self, pos: usize) {
+        if self.len < pos {
+            self.len = pos;
+        }
+    }
+}
+
+impl Deref for AlignedBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        super::deref(self)
+    }
+}
+
+impl DerefMut for AlignedBuf {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        super::deref_mut(self)
+    }
+}
+
+impl fmt::Debug for AlignedBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlignedBuf")
+            .field("len", &self.len)
+            .field("capacity", &self.capacity())
+            .field("alignment", &self.alignment())
+            .finish()
+    }
+}
@@ -7,6 +7,8 @@
 //!
 //! Two kinds of buffer collections are provided: [`FixedBufRegistry`] and
 //! [`FixedBufPool`], realizing two different patterns of buffer management.
+//! In addition, [`SharedFixedBufs`] provides a region of buffers which can be
+//! registered with the runtimes of several threads at once.
 //! The `register` method on either of these types is used to register a
 //! collection of buffers with the kernel. It must be called before any of
 //! the [`FixedBuf`] handles to the collection's buffers can be used with
@@ -26,5 +28,12 @@ mod plumbing;
 pub mod pool;
 pub use pool::FixedBufPool;
 
+mod region;
+pub(crate) use region::memlock_error;
+pub use region::{memlock_usage, MemlockUsage, NumaPolicy, RegionOptions};
+
 mod registry;
 pub use registry::FixedBufRegistry;
+
+mod shared;
+pub use shared::SharedFixedBufs;
@@ -0,0 +1,256 @@
+use std::fs;
+use std::io;
+use std::ptr;
+
+/// NUMA placement of a fixed-buffer region.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NumaPolicy {
+    /// Use the process' default memory policy.
+    #[default]
+    Default,
+
+    /// Interleave the pages of the region across all online nodes.
+    Interleave,
+
+    /// Place the pages on the node of the CPU which first touches them.
+    Local,
+}
+
+/// Options controlling how the memory of a [`SharedFixedBufs`] region is
+/// allocated.
+///
+/// Registering buffers pins their pages and charges them against
+/// `RLIMIT_MEMLOCK`, so large registrations on constrained hosts tend to fail
+/// with bare `ENOMEM` or `EPERM` errors. These options make the placement of
+/// the memory explicit, and [`memlock_usage`] reports how much of the limit
+/// is already in use.
+///
+/// [`SharedFixedBufs`]: super::SharedFixedBufs
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::buf::fixed::{NumaPolicy, RegionOptions};
+///
+/// let bufs = RegionOptions::new()
+///     .mlock(true)
+///     .numa(NumaPolicy::Interleave)
+///     .allocate(1024, 64 * 1024)
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RegionOptions {
+    pub(super) mlock: bool,
+    pub(super) numa: NumaPolicy,
+}
+
+/// Locked memory accounting of the current process, as reported by
+/// [`memlock_usage`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemlockUsage {
+    /// The soft `RLIMIT_MEMLOCK` limit in bytes, `None` if unlimited.
+    pub limit: Option<u64>,
+
+    /// Bytes locked with `mlock`.
+    pub locked: u64,
+
+    /// Bytes pinned by the kernel, e.g. for registered buffers.
+    pub pinned: u64,
+}
+
+impl RegionOptions {
+    /// Creates options with the default settings: memory is neither locked
+    /// nor placed on particular NUMA nodes.
+    pub fn new() -> RegionOptions {
+        RegionOptions::default()
+    }
+
+    /// Sets whether the region is locked in memory with `mlock` when it is
+    /// allocated.
+    ///
+    /// Registration pins the pages anyway; locking them up front moves the
+    /// `RLIMIT_MEMLOCK` failure to allocation time, and faults the pages in
+    /// before the first I/O.
+    pub fn mlock(&mut self, mlock: bool) -> &mut Self {
+        self.mlock = mlock;
+        self
+    }
+
+    /// Sets the NUMA placement of the region.
+    ///
+    /// The policy is ignored on kernels built without NUMA support.
+    pub fn numa(&mut self, numa: NumaPolicy) -> &mut Self {
+        self.numa = numa;
+        self
+    }
+
+    /// Allocates a region of `count` buffers of `size` bytes each with these
+    /// options.
+    ///
+    /// See [`SharedFixedBufs::new`](super::SharedFixedBufs::new).
+    ///
+    /// # Errors
+    ///
+    /// Fails if the memory cannot be mapped, placed, or locked. Locking
+    /// failures report the current `RLIMIT_MEMLOCK` usage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` or `size` is zero, or if the region size overflows.
+    pub fn allocate(&self, count: usize, size: usize) -> io::Result<super::SharedFixedBufs> {
+        super::SharedFixedBufs::allocate(count, size, self)
+    }
+}
+
+/// Returns the locked memory accounting of the current process.
+///
+/// Compare the sizes of the buffers about to be registered with
+/// `limit - locked - pinned` to anticipate registration failures.
+pub fn memlock_usage() -> io::Result<MemlockUsage> {
+    let mut rlim = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    syscall!(getrlimit(libc::RLIMIT_MEMLOCK, &mut rlim))?;
+
+    let status = fs::read_to_string("/proc/self/status")?;
+    let field = |name: &str| {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .and_then(|v| v.trim().trim_end_matches("kB").trim().parse::<u64>().ok())
+            .map_or(0, |kb| kb * 1024)
+    };
+
+    Ok(MemlockUsage {
+        limit: (rlim.rlim_cur != libc::RLIM_INFINITY).then_some(rlim.rlim_cur),
+        locked: field("VmLck:"),
+        pinned: field("VmPin:"),
+    })
+}
+
+impl MemlockUsage {
+    /// Returns the number of bytes which can still be locked or pinned,
+    /// `None` if unlimited.
+    pub fn available(&self) -> Option<u64> {
+        self.limit
+            .map(|limit| limit.saturating_sub(self.locked + self.pinned))
+    }
+}
+
+// Map `len` bytes of zeroed memory placed and locked as requested.
+pub(super) fn map(len: usize, options: &RegionOptions) -> io::Result<*mut u8> {
+    // Safety: an anonymous private mapping has no preconditions.
+    let ptr = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    if ptr == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+
+    // Placement must be applied before the pages are faulted in.
+    let res = bind(ptr, len, options.numa).and_then(|()| {
+        if options.mlock {
+            syscall!(mlock(ptr, len)).map_err(|e| memlock_error(e, len))?;
+        }
+        Ok(())
+    });
+
+    if let Err(e) = res {
+        // Safety: the mapping was created above.
+        unsafe { libc::munmap(ptr, len) };
+        return Err(e);
+    }
+
+    Ok(ptr as *mut u8)
+}
+
+// Safety: `ptr` and `len` must describe a mapping returned by `map`.
+pub(super) unsafe fn unmap(ptr: *mut u8, len: usize) {
+    libc::munmap(ptr as *mut _, len);
+}
+
+fn bind(ptr: *mut libc::c_void, len: usize, numa: NumaPolicy) -> io::Result<()> {
+    let (mode, mask) = match numa {
+        NumaPolicy::Default => return Ok(()),
+        NumaPolicy::Interleave => (libc::MPOL_INTERLEAVE, online_nodes()),
+        NumaPolicy::Local => (libc::MPOL_LOCAL, Vec::new()),
+    };
+
+    let maxnode = mask.len() * 64 + 1;
+    let mask = if mask.is_empty() {
+        ptr::null()
+    } else {
+        mask.as_ptr()
+    };
+
+    match syscall!(syscall(libc::SYS_mbind, ptr, len, mode, mask, maxnode, 0)) {
+        Ok(_) => Ok(()),
+        // No NUMA support, there is nothing to place.
+        Err(e) if e.raw_os_error() == Some(libc::ENOSYS) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+// Returns the mask of online NUMA nodes, as expected by mbind.
+fn online_nodes() -> Vec<u64> {
+    let mut mask = vec![0u64; 1];
+    let online = fs::read_to_string("/sys/devices/system/node/online").unwrap_or_default();
+
+    for range in online.trim().split(',').filter(|r| !r.is_empty()) {
+        let (start, end) = match range.split_once('-') {
+            Some((start, end)) => (start.parse(), end.parse()),
+            None => (range.parse(), range.parse()),
+        };
+        if let (Ok(start), Ok(end)) = (start, end) {
+            for node in start..=end {
+                let node: usize = node;
+                if mask.len() <= node / 64 {
+                    mask.resize(node / 64 + 1, 0);
+                }
+                mask[node / 64] |= 1 << (node % 64);
+            }
+        }
+    }
+
+    // Without topology information, assume a single node.
+    if mask.iter().all(|&word| word == 0) {
+        mask[0] = 1;
+    }
+    mask
+}
+
+// Add the memlock accounting to an error caused by pinning `len` bytes.
+pub(crate) fn memlock_error(e: io::Error, len: usize) -> io::Error {
+    if !matches!(
+        e.raw_os_error(),
+        Some(libc::ENOMEM) | Some(libc::EPERM) | Some(libc::EAGAIN)
+    ) {
+        return e;
+    }
+
+    let usage = match memlock_usage() {
+        Ok(usage) => usage,
+        Err(_) => return e,
+    };
+    let limit = match usage.limit {
+        Some(limit) => format!("{} bytes", limit),
+        None => "unlimited".to_owned(),
+    };
+
+    io::Error::new(
+        e.kind(),
+        format!(
+            "{} while locking {} bytes (RLIMIT_MEMLOCK: {}, locked: {} bytes, pinned: {} bytes)",
+            e, len, limit, usage.locked, usage.pinned
+        ),
+    )
+}
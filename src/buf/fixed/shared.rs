@@ -0,0 +1,288 @@
+use super::handle::CheckedOutBuf;
+use super::region::{self, RegionOptions};
+use super::{FixedBuf, FixedBuffers};
+
+use crate::runtime::CONTEXT;
+use libc::{iovec, UIO_MAXIOV};
+use std::cell::RefCell;
+use std::cmp;
+use std::io;
+use std::ptr;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// State value of a checked out buffer. Free buffers store the length of
+// their initialized part instead.
+const CHECKED_OUT: usize = usize::MAX;
+
+/// A collection of I/O buffers carved out of a single memory region, which
+/// can be registered with several `tokio-uring` runtimes at once.
+///
+/// Thread-per-core servers typically run one runtime per thread. Registering
+/// a separate [`FixedBufRegistry`] with each of them multiplies the memory
+/// held by fixed buffers by the number of threads. A `SharedFixedBufs`
+/// instead allocates one page-aligned region, split into buffers of equal
+/// size, and registers the same region, with the same buffer indices, with
+/// every runtime calling [`register`].
+///
+/// A buffer can be checked out by only one thread at a time. A [`FixedBuf`]
+/// checked out on one thread can only be used with the runtime of that
+/// thread, but returns to the shared collection once dropped, after which it
+/// can be checked out by any thread.
+///
+/// `SharedFixedBufs` is `Send` and `Sync`; cloning it creates a new reference
+/// to the same region. The region is deallocated once all references, all
+/// checked out buffers, and all runtimes it is registered with are gone.
+///
+/// [`FixedBufRegistry`]: super::FixedBufRegistry
+/// [`register`]: Self::register
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::buf::fixed::SharedFixedBufs;
+///
+/// let bufs = SharedFixedBufs::new(64, 4096);
+///
+/// let workers: Vec<_> = (0..4)
+///     .map(|worker| {
+///         let bufs = bufs.clone();
+///         std::thread::spawn(move || {
+///             tokio_uring::start(async move {
+///                 bufs.register().unwrap();
+///
+///                 // Each worker uses its own range of buffer indices.
+///                 let buf = bufs.check_out(worker * 16).unwrap();
+///                 // ...
+///             })
+///         })
+///     })
+///     .collect();
+///
+/// for worker in workers {
+///     worker.join().unwrap();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SharedFixedBufs {
+    region: Arc<Region>,
+}
+
+struct Region {
+    ptr: ptr::NonNull<u8>,
+    len: usize,
+    iovecs: Vec<iovec>,
+    states: Vec<AtomicUsize>,
+}
+
+// Safety: the region owns its allocation, the iovecs merely point into it.
+// Access to the buffer memory is arbitrated by the atomic check out states.
+unsafe impl Send for Region {}
+unsafe impl Sync for Region {}
+
+// The per-thread registration of a region, as seen by the driver.
+struct Registration {
+    region: Arc<Region>,
+}
+
+impl SharedFixedBufs {
+    /// Allocates a zeroed, page-aligned region of `count` buffers of `size`
+    /// bytes each.
+    ///
+    /// The number of buffers is capped at [`UIO_MAXIOV`]. Use
+    /// [`RegionOptions`] to lock the region in memory or control its NUMA
+    /// placement.
+    ///
+    /// [`UIO_MAXIOV`]: libc::UIO_MAXIOV
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` or `size` is zero, if the region size overflows, or
+    /// if the memory cannot be mapped.
+    pub fn new(count: usize, size: usize) -> SharedFixedBufs {
+        SharedFixedBufs::allocate(count, size, &RegionOptions::new())
+            .expect("failed to map fixed buffer region")
+    }
+
+    pub(super) fn allocate(
+        count: usize,
+        size: usize,
+        options: &RegionOptions,
+    ) -> io::Result<SharedFixedBufs> {
+        assert!(count > 0, "count must be greater than zero");
+        assert!(size > 0, "size must be greater than zero");
+
+        let count = cmp::min(count, cmp::min(UIO_MAXIOV as usize, u16::MAX as usize));
+        let len = count.checked_mul(size).expect("region size overflows");
+
+        let ptr = region::map(len, options)?;
+        // Safety: mmap never returns a null mapping on success.
+        let ptr = unsafe { ptr::NonNull::new_unchecked(ptr) };
+
+        let iovecs = (0..count)
+            .map(|i| iovec {
+                // Safety: the offset is within the mapping.
+                iov_base: unsafe { ptr.as_ptr().add(i * size) } as *mut _,
+                iov_len: size,
+            })
+            .collect();
+        let states = (0..count).map(|_| AtomicUsize::new(0)).collect();
+
+        Ok(SharedFixedBufs {
+            region: Arc::new(Region {
+                ptr,
+                len,
+                iovecs,
+                states,
+            }),
+        })
+    }
+
+    /// Returns the number of buffers in the region.
+    pub fn len(&self) -> usize {
+        self.region.states.len()
+    }
+
+    /// Returns `true` if the region holds no buffers.
+    pub fn is_empty(&self) -> bool {
+        self.region.states.is_empty()
+    }
+
+    /// Returns the size of each buffer.
+    pub fn buf_size(&self) -> usize {
+        self.region.iovecs[0].iov_len
+    }
+
+    /// Registers the region with the runtime of the current thread.
+    ///
+    /// This method must be called in the context of a `tokio-uring` runtime.
+    /// As with [`FixedBufRegistry::register`], the registration persists
+    /// for the lifetime of the runtime, unless revoked by [`unregister`].
+    ///
+    /// [`FixedBufRegistry::register`]: super::FixedBufRegistry::register
+    /// [`unregister`]: Self::unregister
+    ///
+    /// # Errors
+    ///
+    /// If a collection of buffers is currently registered in the context
+    /// of the `tokio-uring` runtime this call is made in, the function returns
+    /// an error.
+    pub fn register(&self) -> io::Result<()> {
+        let registration = Rc::new(RefCell::new(Registration {
+            region: Arc::clone(&self.region),
+        }));
+
+        CONTEXT.with(|x| {
+            x.handle()
+                .as_ref()
+                .expect("Not in a runtime context")
+                .register_buffers(registration as _)
+        })
+    }
+
+    /// Unregisters the region from the runtime of the current thread.
+    ///
+    /// This operation invalidates any `FixedBuf` handles checked out on this
+    /// thread. Buffers checked out on other threads are not affected.
+    ///
+    /// # Errors
+    ///
+    /// If the region is not currently registered with the runtime of the
+    /// current thread, an error is returned.
+    pub fn unregister(&self) -> io::Result<()> {
+        let registration = self
+            .registration()
+            .ok_or_else(|| io::Error::other("fixed buffers are not currently registered"))?;
+
+        CONTEXT.with(|x| {
+            x.handle()
+                .as_ref()
+                .expect("Not in a runtime context")
+                .unregister_buffers(registration)
+        })
+    }
+
+    /// Returns the buffer at the given index, if it is not checked out by
+    /// this or any other thread.
+    ///
+    /// The buffer can only be used with the runtime of the current thread,
+    /// which must have registered the region with [`register`].
+    ///
+    /// [`register`]: Self::register
+    ///
+    /// # Panics
+    ///
+    /// Panics if the region is not registered with the runtime of the
+    /// current thread, or if called outside of a runtime context.
+    pub fn check_out(&self, index: usize) -> Option<FixedBuf> {
+        let registration = self
+            .registration()
+            .expect("fixed buffers are not registered with this thread's runtime");
+
+        let state = self.region.states.get(index)?;
+        let init_len = state.load(Ordering::Acquire);
+        if init_len == CHECKED_OUT
+            || state
+                .compare_exchange(init_len, CHECKED_OUT, Ordering::AcqRel, Ordering::Acquire)
+                .is_err()
+        {
+            return None;
+        }
+
+        let buf = CheckedOutBuf {
+            iovec: self.region.iovecs[index],
+            init_len,
+            index: index as u16,
+        };
+
+        // Safety: the buffer lives as long as the region, which is kept alive
+        // by the registration, and is initialized up to init_len.
+        Some(unsafe { FixedBuf::new(registration, buf) })
+    }
+
+    // Returns this region's registration with the runtime of the current
+    // thread, if any.
+    fn registration(&self) -> Option<Rc<RefCell<dyn FixedBuffers>>> {
+        let registered = CONTEXT.with(|x| x.handle()?.registered_buffers())?;
+
+        // Registrations of the same region share its iovecs.
+        let ours = ptr::eq(
+            registered.borrow().iovecs().as_ptr(),
+            self.region.iovecs.as_ptr(),
+        );
+        ours.then_some(registered)
+    }
+}
+
+impl std::fmt::Debug for SharedFixedBufs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedFixedBufs")
+            .field("len", &self.len())
+            .field("buf_size", &self.buf_size())
+            .finish()
+    }
+}
+
+impl FixedBuffers for Registration {
+    fn iovecs(&self) -> &[iovec] {
+        &self.region.iovecs
+    }
+
+    unsafe fn check_in(&mut self, index: u16, init_len: usize) {
+        let state = &self.region.states[index as usize];
+        debug_assert_eq!(
+            state.load(Ordering::Relaxed),
+            CHECKED_OUT,
+            "the buffer must be checked out"
+        );
+        state.store(init_len, Ordering::Release);
+    }
+}
+
+impl Drop for Region {
+    fn drop(&mut self) {
+        // Safety: mapped in SharedFixedBufs::allocate with this length.
+        unsafe { region::unmap(self.ptr.as_ptr(), self.len) }
+    }
+}
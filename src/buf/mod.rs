@@ -6,6 +6,9 @@
 
 pub mod fixed;
 
+mod aligned;
+pub use aligned::AlignedBuf;
+
 mod io_buf;
 pub use io_buf::IoBuf;
 
@@ -0,0 +1,226 @@
+use crate::fs::File;
+use crate::Submit;
+use std::cmp;
+use std::fmt;
+use std::io;
+
+// The default capacity of the buffer.
+pub(super) const DEFAULT_CAPACITY: usize = 64 * 1024;
+
+/// Reads a [`File`] sequentially through an internal buffer.
+///
+/// Each read of the file fills the buffer, which is owned by the reader and
+/// handed to the ring while the read is in flight. Small reads, like those
+/// of a parser or of [`read_line`](BufReader::read_line), are then served
+/// from the buffer without submitting an operation each.
+///
+/// The reader keeps its own cursor, starting at the beginning of the file.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::{BufReader, File};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         let mut reader = BufReader::new(File::open("app.log").await?);
+///
+///         let mut line = String::new();
+///         while reader.read_line(&mut line).await? != 0 {
+///             print!("{}", line);
+///             line.clear();
+///         }
+///
+///         reader.into_inner().close().await?;
+///         Ok(())
+///     })
+/// }
+/// ```
+pub struct BufReader {
+    file: File,
+
+    /// Data read from the file, of which `buf[consumed..]` wasn't returned.
+    buf: Vec<u8>,
+
+    consumed: usize,
+
+    /// Offset in the file of the end of `buf`.
+    pos: u64,
+
+    capacity: usize,
+}
+
+impl BufReader {
+    /// Wraps `file` with a buffer of 64 KiB.
+    pub fn new(file: File) -> BufReader {
+        BufReader::with_capacity(DEFAULT_CAPACITY, file)
+    }
+
+    /// Wraps `file` with a buffer of `capacity` bytes, the size of the reads
+    /// submitted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn with_capacity(capacity: usize, file: File) -> BufReader {
+        assert!(capacity > 0, "capacity must be greater than zero");
+
+        BufReader {
+            file,
+            buf: Vec::with_capacity(capacity),
+            consumed: 0,
+            pos: 0,
+            capacity,
+        }
+    }
+
+    /// Returns a reference to the underlying file.
+    pub fn get_ref(&self) -> &File {
+        &self.file
+    }
+
+    /// Consumes the `BufReader`, returning the underlying file.
+    ///
+    /// Buffered data which wasn't read is dropped.
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+
+    /// Returns the capacity of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the buffered data which wasn't read yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.consumed..]
+    }
+
+    /// Returns the offset in the file of the next byte to read.
+    pub fn position(&self) -> u64 {
+        self.pos - self.buffer().len() as u64
+    }
+
+    /// Returns the buffered data, reading more from the file first if none
+    /// is left.
+    ///
+    /// An empty slice is returned at the end of the file. Mark the bytes used
+    /// as read with [`consume`](BufReader::consume).
+    pub async fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.consumed == self.buf.len() {
+            // The buffer is lost if a previous read was cancelled.
+            let mut buf = std::mem::take(&mut self.buf);
+            buf.clear();
+            buf.reserve_exact(self.capacity);
+            self.consumed = 0;
+            match self.file.read_at(buf, self.pos).submit().await {
+                Ok((n, buf)) => {
+                    self.buf = buf;
+                    self.pos += n as u64;
+                }
+                Err(crate::Error(e, buf)) => {
+                    self.buf = buf;
+                    return Err(e);
+                }
+            }
+        }
+        Ok(self.buffer())
+    }
+
+    /// Marks `amt` bytes of the buffer as read.
+    ///
+    /// `amt` is capped to the length of the buffer.
+    pub fn consume(&mut self, amt: usize) {
+        self.consumed = cmp::min(self.consumed + amt, self.buf.len());
+    }
+
+    /// Reads some bytes into `out`, returning how many were read.
+    ///
+    /// Returns `0` at the end of the file, or if `out` is empty.
+    pub async fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        if out.is_empty() {
+            return Ok(0);
+        }
+        let available = self.fill_buf().await?;
+        let n = cmp::min(available.len(), out.len());
+        out[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+
+    /// Reads the exact number of bytes required to fill `out`.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) if the end
+    /// of the file is reached first. The bytes read until then are consumed.
+    pub async fn read_exact(&mut self, mut out: &mut [u8]) -> io::Result<()> {
+        while !out.is_empty() {
+            let n = self.read(out).await?;
+            if n == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "failed to fill whole buffer",
+                ));
+            }
+            out = &mut out[n..];
+        }
+        Ok(())
+    }
+
+    /// Reads bytes into `out` until the delimiter `byte` or the end of the
+    /// file, returning how many were read.
+    ///
+    /// The delimiter is included in `out` if found.
+    pub async fn read_until(&mut self, byte: u8, out: &mut Vec<u8>) -> io::Result<usize> {
+        let mut read = 0;
+        loop {
+            let available = self.fill_buf().await?;
+            let (done, n) = match available.iter().position(|&b| b == byte) {
+                Some(i) => (true, i + 1),
+                None => (available.is_empty(), available.len()),
+            };
+            out.extend_from_slice(&available[..n]);
+            self.consume(n);
+            read += n;
+            if done {
+                return Ok(read);
+            }
+        }
+    }
+
+    /// Reads a line into `out`, including its newline if any, returning how
+    /// many bytes were read.
+    ///
+    /// Returns `0` at the end of the file.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`InvalidData`](io::ErrorKind::InvalidData) if the line
+    /// isn't valid UTF-8, leaving `out` unchanged. The line is consumed.
+    pub async fn read_line(&mut self, out: &mut String) -> io::Result<usize> {
+        let mut line = Vec::new();
+        let n = self.read_until(b'\n', &mut line).await?;
+        let line = String::from_utf8(line).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            )
+        })?;
+        out.push_str(&line);
+        Ok(n)
+    }
+}
+
+impl fmt::Debug for BufReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufReader")
+            .field("file", &self.file)
+            .field(
+                "buffer",
+                &format_args!("{}/{}", self.buffer().len(), self.capacity()),
+            )
+            .field("position", &self.position())
+            .finish()
+    }
+}
@@ -0,0 +1,159 @@
+use super::buf_reader::DEFAULT_CAPACITY;
+use crate::fs::File;
+use std::cmp;
+use std::fmt;
+use std::io;
+
+/// Writes a [`File`] sequentially through an internal buffer.
+///
+/// Writes are copied into the buffer, which is owned by the writer and
+/// written to the file once full, handing it to the ring while the write is
+/// in flight. Many small writes thus make few large writes to the file.
+///
+/// The writer keeps its own cursor, starting at the beginning of the file.
+/// Data still buffered when the writer is dropped, or when a flush is
+/// cancelled, is lost: call [`flush`](BufWriter::flush), or
+/// [`into_inner`](BufWriter::into_inner) which flushes, once done.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::{BufWriter, File};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         let mut writer = BufWriter::new(File::create("squares.txt").await?);
+///
+///         for i in 0..1000 {
+///             writer.write_all(format!("{}\n", i * i).as_bytes()).await?;
+///         }
+///
+///         let file = writer.into_inner().await?;
+///         file.sync_all().await?;
+///         file.close().await?;
+///         Ok(())
+///     })
+/// }
+/// ```
+pub struct BufWriter {
+    file: File,
+
+    /// Data not written to the file yet.
+    buf: Vec<u8>,
+
+    /// Offset in the file of the start of `buf`.
+    pos: u64,
+
+    capacity: usize,
+}
+
+impl BufWriter {
+    /// Wraps `file` with a buffer of 64 KiB.
+    pub fn new(file: File) -> BufWriter {
+        BufWriter::with_capacity(DEFAULT_CAPACITY, file)
+    }
+
+    /// Wraps `file` with a buffer of `capacity` bytes, the size of the writes
+    /// submitted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn with_capacity(capacity: usize, file: File) -> BufWriter {
+        assert!(capacity > 0, "capacity must be greater than zero");
+
+        BufWriter {
+            file,
+            buf: Vec::with_capacity(capacity),
+            pos: 0,
+            capacity,
+        }
+    }
+
+    /// Returns a reference to the underlying file.
+    pub fn get_ref(&self) -> &File {
+        &self.file
+    }
+
+    /// Flushes the buffer, then returns the underlying file.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the buffer can't be written, dropping its data and the file.
+    pub async fn into_inner(mut self) -> io::Result<File> {
+        self.flush().await?;
+        Ok(self.file)
+    }
+
+    /// Returns the capacity of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the buffered data which wasn't written to the file yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Returns the offset in the file the next byte is written at.
+    pub fn position(&self) -> u64 {
+        self.pos + self.buf.len() as u64
+    }
+
+    /// Writes all of `data`, flushing the buffer each time it fills up.
+    pub async fn write_all(&mut self, mut data: &[u8]) -> io::Result<()> {
+        while !data.is_empty() {
+            if self.buf.len() == self.capacity {
+                self.flush().await?;
+            }
+            // The buffer is lost if a previous flush was cancelled.
+            self.buf.reserve_exact(self.capacity - self.buf.len());
+            let n = cmp::min(self.capacity - self.buf.len(), data.len());
+            self.buf.extend_from_slice(&data[..n]);
+            data = &data[n..];
+        }
+        Ok(())
+    }
+
+    /// Writes the buffered data to the file.
+    ///
+    /// This doesn't sync the file, see [`File::sync_all`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the data can't be written. It is kept buffered then, and
+    /// written again at the same offset by the next flush.
+    pub async fn flush(&mut self) -> io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+
+        let buf = std::mem::take(&mut self.buf);
+        let len = buf.len();
+        match self.file.write_all_at(buf, self.pos).await {
+            Ok(((), mut buf)) => {
+                buf.clear();
+                self.buf = buf;
+                self.pos += len as u64;
+                Ok(())
+            }
+            Err(crate::Error(e, buf)) => {
+                self.buf = buf;
+                Err(e)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BufWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufWriter")
+            .field("file", &self.file)
+            .field(
+                "buffer",
+                &format_args!("{}/{}", self.buf.len(), self.capacity()),
+            )
+            .field("position", &self.position())
+            .finish()
+    }
+}
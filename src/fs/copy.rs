@@ -0,0 +1,155 @@
+use super::{File, OpenOptions};
+use crate::io::SharedFd;
+use crate::runtime::driver::op::{Op, Submit};
+use std::convert::TryInto;
+use std::fs::Permissions;
+use std::io;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+// The size of the pipe data is spliced through, if the kernel allows it.
+const PIPE_SIZE: libc::c_int = 1024 * 1024;
+
+// The size of the buffer data is copied through without splicing.
+const BUF_SIZE: usize = 64 * 1024;
+
+/// Copies the contents of the file at `from` to the file at `to`, returning
+/// the number of bytes copied.
+///
+/// `to` is created if it doesn't exist, and truncated otherwise. Its
+/// permissions are set to those of `from`, like [`std::fs::copy`].
+///
+/// The data is moved within the kernel with `IORING_OP_SPLICE`, through a
+/// pipe created for the copy, and never copied to userspace. Kernels or
+/// filesystems without splice support are copied with reads and writes
+/// through the ring instead.
+///
+/// # Errors
+///
+/// Fails if `from` doesn't exist or isn't a regular file, or if the user
+/// lacks permissions to read `from` or to write `to`.
+///
+/// # Examples
+///
+/// ```no_run
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         let n = tokio_uring::fs::copy("foo.txt", "bar.txt").await?;
+///         println!("copied {} bytes", n);
+///         Ok::<(), std::io::Error>(())
+///     })?;
+///     Ok(())
+/// }
+/// ```
+pub async fn copy(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<u64> {
+    let src = File::open(from).await?;
+    let res = copy_from(&src, to.as_ref()).await;
+    let closed = src.close().await;
+    let n = res?;
+    closed?;
+    Ok(n)
+}
+
+async fn copy_from(src: &File, to: &Path) -> io::Result<u64> {
+    let statx = src.statx().await?;
+    if u32::from(statx.stx_mode) & libc::S_IFMT != libc::S_IFREG {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the source path is not a regular file",
+        ));
+    }
+    let mode = u32::from(statx.stx_mode) & 0o7777;
+
+    let dst = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(mode)
+        .open(to)
+        .await?;
+    let res = fill(src, &dst, mode).await;
+    let closed = dst.close().await;
+    let n = res?;
+    closed?;
+    Ok(n)
+}
+
+async fn fill(src: &File, dst: &File, mode: u32) -> io::Result<u64> {
+    let n = copy_contents(src, dst).await?;
+    // The mode given at creation is masked by the umask, and doesn't apply
+    // to an existing file.
+    dst.set_permissions(Permissions::from_mode(mode)).await?;
+    Ok(n)
+}
+
+async fn copy_contents(src: &File, dst: &File) -> io::Result<u64> {
+    if crate::runtime::supports(io_uring::opcode::Splice::CODE) {
+        match splice_contents(src, dst).await {
+            // Start over with reads and writes if a filesystem can't splice.
+            Err(e) if e.raw_os_error() == Some(libc::EINVAL) => {}
+            res => return res,
+        }
+    }
+    read_write_contents(src, dst).await
+}
+
+async fn splice_contents(src: &File, dst: &File) -> io::Result<u64> {
+    let (pipe_r, pipe_w) = pipe()?;
+    // A larger pipe moves more data per splice. The default size of 64 KiB
+    // is kept if the limit of the user is lower.
+    let len = match syscall!(fcntl(pipe_w.raw_fd(), libc::F_SETPIPE_SZ, PIPE_SIZE)) {
+        Ok(size) => size,
+        Err(_) => syscall!(fcntl(pipe_w.raw_fd(), libc::F_GETPIPE_SZ))?,
+    } as u32;
+
+    let mut pos: u64 = 0;
+    loop {
+        let off = offset(pos)?;
+        let n = Op::splice(&src.fd, off, &pipe_w, -1, len)?.await?;
+        if n == 0 {
+            return Ok(pos);
+        }
+
+        // Drain the pipe into the destination, at the same offset.
+        let mut left = n;
+        while left > 0 {
+            let off = offset(pos)?;
+            let m = Op::splice(&pipe_r, -1, &dst.fd, off, left as u32)?.await?;
+            if m == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                ));
+            }
+            left -= m;
+            pos += m as u64;
+        }
+    }
+}
+
+async fn read_write_contents(src: &File, dst: &File) -> io::Result<u64> {
+    let mut buf = Vec::with_capacity(BUF_SIZE);
+    let mut pos: u64 = 0;
+    loop {
+        buf.clear();
+        let (n, read) = src.read_at(buf, pos).submit().await.map_err(|e| e.0)?;
+        if n == 0 {
+            return Ok(pos);
+        }
+        let (_, written) = dst.write_all_at(read, pos).await.map_err(|e| e.0)?;
+        buf = written;
+        pos += n as u64;
+    }
+}
+
+// Creates a pipe, returning its read and write ends.
+fn pipe() -> io::Result<(SharedFd, SharedFd)> {
+    let mut fds = [0; 2];
+    syscall!(pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC))?;
+    Ok((SharedFd::new(fds[0]), SharedFd::new(fds[1])))
+}
+
+fn offset(pos: u64) -> io::Result<i64> {
+    pos.try_into()
+        .map_err(|_| io::Error::from_raw_os_error(libc::EFBIG))
+}
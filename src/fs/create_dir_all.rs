@@ -139,10 +139,7 @@ impl DirBuilder {
             match path.parent() {
                 Some(p) => self.recurse_create_dir_all(p).await?,
                 None => {
-                    return Err(std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        "failed to create whole tree",
-                    ));
+                    return Err(std::io::Error::other("failed to create whole tree"));
                     /* TODO build own allocation free error some day like the std library does.
                     return Err(io::const_io_error!(
                         io::ErrorKind::Uncategorized,
@@ -179,7 +176,7 @@ mod fs_imp {
         }
 
         pub async fn mkdir(&self, p: &Path) -> std::io::Result<()> {
-            Op::make_dir(p, self.mode)?.await
+            Op::make_dir(None, p, self.mode)?.await
         }
 
         pub fn set_mode(&mut self, mode: u32) {
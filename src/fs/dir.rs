@@ -0,0 +1,142 @@
+use super::{File, OpenOptions};
+use crate::runtime::driver::op::Op;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// An open directory, which paths are resolved relative to.
+///
+/// The operations of a `Dir` resolve relative paths from the directory it
+/// was opened on, through its descriptor, rather than from a path. They keep
+/// acting on the same directory even if it is renamed or moved meanwhile, and
+/// aren't affected by changes of the current working directory. Absolute
+/// paths ignore the directory.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::{Dir, OpenOptions};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         let dir = Dir::open("/srv/data").await?;
+///
+///         let file = dir
+///             .open_at("new.txt", OpenOptions::new().write(true).create_new(true))
+///             .await?;
+///         file.close().await?;
+///         dir.rename_at("new.txt", &dir, "current.txt").await?;
+///
+///         dir.close().await?;
+///         Ok(())
+///     })
+/// }
+/// ```
+pub struct Dir {
+    file: File,
+}
+
+impl Dir {
+    /// Opens the directory at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `path` doesn't exist or isn't a directory, with `ENOTDIR` in
+    /// the latter case.
+    pub async fn open(path: impl AsRef<Path>) -> io::Result<Dir> {
+        let file = OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_DIRECTORY)
+            .open(path)
+            .await?;
+        Ok(Dir { file })
+    }
+
+    // Opens the directory at `path`, relative to `dir` if given, failing
+    // rather than following a symbolic link at `path`.
+    pub(crate) async fn open_nofollow(dir: Option<&Dir>, path: &Path) -> io::Result<Dir> {
+        let mut options = OpenOptions::new();
+        options
+            .read(true)
+            .custom_flags(libc::O_DIRECTORY | libc::O_NOFOLLOW);
+        let file = Op::open(dir.map(|dir| &dir.file.fd), path, &options)?.await?;
+        Ok(Dir { file })
+    }
+
+    /// Opens a file at `path` relative to the directory, with `options`.
+    ///
+    /// See [`OpenOptions::open`] for the errors, and
+    /// [`OpenOptions::open_at2`] to restrict how `path` is resolved, e.g. to
+    /// stay below the directory, with [`as_file`](Dir::as_file).
+    pub async fn open_at(&self, path: impl AsRef<Path>, options: &OpenOptions) -> io::Result<File> {
+        Op::open(Some(&self.file.fd), path.as_ref(), options)?.await
+    }
+
+    /// Creates a directory at `path` relative to the directory.
+    ///
+    /// See [`create_dir`](super::create_dir) for the errors.
+    pub async fn mkdir_at(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        Op::make_dir(Some(&self.file.fd), path.as_ref(), 0o777)?.await
+    }
+
+    /// Removes the file at `path` relative to the directory.
+    ///
+    /// See [`remove_file`](super::remove_file) for the errors.
+    pub async fn unlink_at(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        Op::unlink(Some(&self.file.fd), path.as_ref(), 0)?.await
+    }
+
+    /// Removes the empty directory at `path` relative to the directory.
+    ///
+    /// See [`remove_dir`](super::remove_dir) for the errors.
+    pub async fn remove_dir_at(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        Op::unlink(Some(&self.file.fd), path.as_ref(), libc::AT_REMOVEDIR)?.await
+    }
+
+    /// Renames `from` relative to the directory to `to` relative to `to_dir`,
+    /// replacing the original file if `to` already exists.
+    ///
+    /// `to_dir` may be the directory itself. See [`rename`](super::rename)
+    /// for the errors.
+    pub async fn rename_at(
+        &self,
+        from: impl AsRef<Path>,
+        to_dir: &Dir,
+        to: impl AsRef<Path>,
+    ) -> io::Result<()> {
+        Op::rename_at(
+            Some(&self.file.fd),
+            from.as_ref(),
+            Some(&to_dir.file.fd),
+            to.as_ref(),
+            0,
+        )?
+        .await
+    }
+
+    /// Returns statx(2) metadata for `path` relative to the directory.
+    ///
+    /// See [`statx_at`](super::statx_at).
+    pub async fn statx_at(&self, path: impl AsRef<Path>) -> io::Result<libc::statx> {
+        super::statx_at(&self.file, path).await
+    }
+
+    /// Returns the directory as a [`File`], e.g. to pass it to
+    /// [`OpenOptions::open_at2`] or to [`File::statx`].
+    pub fn as_file(&self) -> &File {
+        &self.file
+    }
+
+    /// Closes the directory, see [`File::close`].
+    pub async fn close(self) -> io::Result<()> {
+        self.file.close().await
+    }
+}
+
+impl fmt::Debug for Dir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dir")
+            .field("fd", &self.file.fd.raw_fd())
+            .finish()
+    }
+}
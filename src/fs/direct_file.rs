@@ -0,0 +1,197 @@
+use crate::buf::{AlignedBuf, BoundedBuf, BoundedBufMut};
+use crate::fs::{File, OpenOptions};
+use crate::Submit;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+// The alignment assumed if the file doesn't report its own, which suits the
+// logical block size of nearly all devices.
+const DEFAULT_ALIGN: u32 = 4096;
+
+/// A file opened for direct I/O (`O_DIRECT`), which checks that reads and
+/// writes are aligned as the file requires.
+///
+/// Direct I/O bypasses the page cache, but requires the memory of buffers,
+/// the offsets and the lengths of reads and writes to be aligned, depending
+/// on the file. The kernel fails misaligned operations with an `EINVAL`
+/// that doesn't tell which is misaligned. `DirectFile` queries the
+/// alignments when opened (`STATX_DIOALIGN`, Linux 6.1 or later, and 4096
+/// bytes otherwise), and fails misaligned operations itself, with an
+/// [`InvalidInput`](io::ErrorKind::InvalidInput) error telling which.
+///
+/// [`alloc_buf`](DirectFile::alloc_buf) allocates buffers aligned as
+/// required.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::{DirectFile, OpenOptions};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         let file = DirectFile::open("data.bin", OpenOptions::new().read(true).write(true)).await?;
+///
+///         let mut buf = file.alloc_buf(4096);
+///         buf.extend_from_slice(b"hello");
+///         buf.zero_pad();
+///         let (_, buf) = file.write_at(buf, 0).await?;
+///
+///         let (n, buf) = file.read_at(buf, 0).await?;
+///         assert_eq!(&buf[..5], b"hello");
+///
+///         file.close().await?;
+///         Ok(())
+///     })
+/// }
+/// ```
+pub struct DirectFile {
+    file: File,
+
+    /// Alignment of the memory of buffers.
+    mem_align: u32,
+
+    /// Alignment of offsets and lengths.
+    offset_align: u32,
+}
+
+impl DirectFile {
+    /// Opens the file at `path` with `options`, adding `O_DIRECT`.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `EINVAL` if the filesystem doesn't support direct I/O, such
+    /// as tmpfs. See [`OpenOptions::open`] for the other errors.
+    pub async fn open(path: impl AsRef<Path>, options: &OpenOptions) -> io::Result<DirectFile> {
+        let mut options = options.clone();
+        let flags = options.custom_flags | libc::O_DIRECT;
+        let file = options.custom_flags(flags).open(path).await?;
+
+        let (mem_align, offset_align) = match file.dio_alignment().await {
+            Ok(Some(align)) => align,
+            Ok(None) => (DEFAULT_ALIGN, DEFAULT_ALIGN),
+            Err(e) => {
+                let _ = file.close().await;
+                return Err(e);
+            }
+        };
+        Ok(DirectFile {
+            file,
+            mem_align,
+            offset_align,
+        })
+    }
+
+    /// Returns the alignment the memory of buffers requires.
+    pub fn memory_alignment(&self) -> usize {
+        self.mem_align as usize
+    }
+
+    /// Returns the alignment offsets and lengths require.
+    pub fn offset_alignment(&self) -> usize {
+        self.offset_align as usize
+    }
+
+    /// Allocates an empty buffer aligned for the file, of at least `capacity`
+    /// bytes, rounded up to the offset alignment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn alloc_buf(&self, capacity: usize) -> AlignedBuf {
+        let align = self.offset_alignment();
+        let capacity = capacity.div_ceil(align) * align;
+        AlignedBuf::with_capacity(capacity, self.memory_alignment())
+    }
+
+    /// Reads into `buf` from the specified offset, returning how many bytes
+    /// were read, like [`File::read_at`].
+    ///
+    /// The whole capacity of `buf` is read, which, like `pos`, must be a
+    /// multiple of the offset alignment. A read past the end of the file is
+    /// short, as with [`File::read_at`], and may end at an unaligned offset.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`InvalidInput`](io::ErrorKind::InvalidInput) if `buf` or
+    /// `pos` isn't aligned, without submitting the read.
+    pub async fn read_at<T: BoundedBufMut>(&self, buf: T, pos: u64) -> crate::Result<usize, T> {
+        if let Err(e) = self.check(buf.stable_ptr(), buf.bytes_total(), pos) {
+            return Err(crate::Error(e, buf));
+        }
+        self.file.read_at(buf, pos).submit().await
+    }
+
+    /// Writes the initialized bytes of `buf` at the specified offset,
+    /// returning how many bytes were written, like [`File::write_at`].
+    ///
+    /// The number of initialized bytes, like `pos`, must be a multiple of the
+    /// offset alignment: see [`AlignedBuf::zero_pad`], and
+    /// [`File::truncate`] through [`as_file`](DirectFile::as_file) to trim
+    /// the padding.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`InvalidInput`](io::ErrorKind::InvalidInput) if `buf` or
+    /// `pos` isn't aligned, without submitting the write.
+    pub async fn write_at<T: BoundedBuf>(&self, buf: T, pos: u64) -> crate::Result<usize, T> {
+        if let Err(e) = self.check(buf.stable_ptr(), buf.bytes_init(), pos) {
+            return Err(crate::Error(e, buf));
+        }
+        self.file.write_at(buf, pos).submit().await
+    }
+
+    /// Returns the underlying file, e.g. to sync it.
+    ///
+    /// Its operations don't check the alignment.
+    pub fn as_file(&self) -> &File {
+        &self.file
+    }
+
+    /// Consumes the `DirectFile`, returning the underlying file, which is
+    /// still open with `O_DIRECT`.
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+
+    /// Closes the file, see [`File::close`].
+    pub async fn close(self) -> io::Result<()> {
+        self.file.close().await
+    }
+
+    fn check(&self, ptr: *const u8, len: usize, pos: u64) -> io::Result<()> {
+        let misaligned = |what: String, align: u32| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} is not aligned to {} bytes, as direct I/O on the file requires",
+                    what, align
+                ),
+            )
+        };
+
+        if !(ptr as usize).is_multiple_of(self.mem_align as usize) {
+            return Err(misaligned(
+                format!("the buffer address {:p}", ptr),
+                self.mem_align,
+            ));
+        }
+        if !len.is_multiple_of(self.offset_align as usize) {
+            return Err(misaligned(format!("the length {}", len), self.offset_align));
+        }
+        if !pos.is_multiple_of(self.offset_align as u64) {
+            return Err(misaligned(format!("the offset {}", pos), self.offset_align));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DirectFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DirectFile")
+            .field("file", &self.file)
+            .field("memory_alignment", &self.mem_align)
+            .field("offset_alignment", &self.offset_align)
+            .finish()
+    }
+}
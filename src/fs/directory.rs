@@ -31,16 +31,13 @@ use std::path::Path;
 /// }
 /// ```
 pub async fn create_dir<P: AsRef<Path>>(path: P) -> io::Result<()> {
-    Op::make_dir(path.as_ref(), 0o777)?.await
+    Op::make_dir(None, path.as_ref(), 0o777)?.await
 }
 
 /// Removes a directory on the local filesystem.
 ///
 /// This will only remove empty directories with no children. If you want to destroy the entire
-/// contents of a directory, you may try [`remove_dir_all`] which uses the standard Tokio executor.
-/// There currently is no implementation of `remove_dir_all` in tokio-uring.
-///
-/// [`remove_dir_all`]: https://docs.rs/tokio/latest/tokio/fs/fn.remove_dir_all.html
+/// contents of a directory, use [`remove_dir_all`](super::remove_dir_all).
 ///
 /// # Errors
 ///
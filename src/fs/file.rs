@@ -8,6 +8,7 @@ use crate::MapResult;
 use crate::{
     UnsubmittedOneshot, UnsubmittedRead, UnsubmittedReadv, UnsubmittedWrite, UnsubmittedWritev,
 };
+use std::convert::TryInto;
 use std::fmt;
 use std::io;
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
@@ -122,6 +123,64 @@ impl File {
             .await
     }
 
+    /// Creates an anonymous file in the filesystem of the directory `dir`,
+    /// opened for reading and writing, using `O_TMPFILE`.
+    ///
+    /// The file has no name until it is given one with
+    /// [`persist`](File::persist), and is removed once closed otherwise. This
+    /// suits writing a file in full before publishing it: readers never see
+    /// it partially written, and a crash leaves no temporary file behind.
+    ///
+    /// Requires a filesystem supporting `O_TMPFILE`, such as ext4, XFS, Btrfs
+    /// or tmpfs. It fails with `EOPNOTSUPP` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let f = File::create_anonymous("/srv/data").await?;
+    ///         f.write_all_at(&b"hello"[..], 0).await?;
+    ///         f.sync_all().await?;
+    ///
+    ///         f.persist("/srv/data/hello.txt").await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub async fn create_anonymous(dir: impl AsRef<Path>) -> io::Result<File> {
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(libc::O_TMPFILE)
+            .open(dir)
+            .await
+    }
+
+    /// Links the file into the filesystem at `path`, using `linkat(2)`.
+    ///
+    /// This publishes a file created with
+    /// [`create_anonymous`](File::create_anonymous) atomically: `path`
+    /// appears with the full contents written so far, or not at all. Unlike
+    /// renaming a temporary file into place, the file never has another
+    /// visible name. `path` must be on the same filesystem as the file.
+    ///
+    /// The link is made through `/proc/self/fd`, which needs `/proc` to be
+    /// mounted. Sync the file first with [`sync_all`](File::sync_all) for the
+    /// contents to survive a crash along with the name.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`AlreadyExists`](io::ErrorKind::AlreadyExists) if `path`
+    /// exists, since a link never replaces a file, and with `EXDEV` if `path`
+    /// is on another filesystem.
+    pub async fn persist(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let proc_path = format!("/proc/self/fd/{}", self.fd.raw_fd());
+        Op::link_at(proc_path.as_ref(), path.as_ref(), libc::AT_SYMLINK_FOLLOW)?.await
+    }
+
     pub(crate) fn from_shared_fd(fd: SharedFd) -> File {
         File { fd }
     }
@@ -134,6 +193,36 @@ impl File {
         File::from_shared_fd(SharedFd::new(file.into_raw_fd()))
     }
 
+    /// Creates a new `File` for the same open file, with a duplicate of the
+    /// underlying descriptor.
+    ///
+    /// Both files share the open file description: its access mode, status
+    /// flags such as `O_APPEND`, and [locks](File::lock_range). Each must be
+    /// closed separately, and closing one leaves the other usable, e.g. to
+    /// read on one task while another appends.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let f = File::open("foo.txt").await?;
+    ///         let clone = f.try_clone()?;
+    ///
+    ///         f.close().await?;
+    ///         // The clone still reads from the file.
+    ///         clone.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub fn try_clone(&self) -> io::Result<File> {
+        let fd = syscall!(fcntl(self.fd.raw_fd(), libc::F_DUPFD_CLOEXEC, 0))?;
+        Ok(File::from_shared_fd(SharedFd::new(fd)))
+    }
+
     /// Read some bytes at the specified offset from the file into the specified
     /// buffer, returning how many bytes were read.
     ///
@@ -210,6 +299,7 @@ impl File {
     ///
     /// ```no_run
     /// use tokio_uring::fs::File;
+    /// use tokio_uring::Submit;
     ///
     /// fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     tokio_uring::start(async {
@@ -258,6 +348,7 @@ impl File {
     ///
     /// ```no_run
     /// use tokio_uring::fs::File;
+    /// use tokio_uring::Submit;
     ///
     /// fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     tokio_uring::start(async {
@@ -425,6 +516,69 @@ impl File {
         Ok(((), buf.into_inner()))
     }
 
+    /// Reads all bytes from the specified offset until the end of the file.
+    ///
+    /// The buffer is sized from the file size reported by `statx(2)`, so a
+    /// file which doesn't change meanwhile is read with a single read, plus a
+    /// small one to make sure it ended. Files which grow, or don't report a
+    /// size, like those in `/proc`, are read until a read returns no bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let f = File::open("foo.txt").await?;
+    ///         let contents = f.read_to_end_at(0).await?;
+    ///         println!("{} bytes", contents.len());
+    ///         f.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub async fn read_to_end_at(&self, mut pos: u64) -> io::Result<Vec<u8>> {
+        // The size of the reads probing for the end of the file.
+        const PROBE_SIZE: usize = 32;
+
+        let size = self.statx().await?.stx_size;
+        let hint = size.saturating_sub(pos).try_into().unwrap_or(usize::MAX);
+        let mut buf = Vec::new();
+        buf.try_reserve_exact(hint)
+            .map_err(|_| io::Error::new(io::ErrorKind::OutOfMemory, "file too large"))?;
+
+        loop {
+            if buf.len() == buf.capacity() {
+                // Check for the end of the file with a small read first,
+                // rather than doubling the buffer of a file read in full.
+                let (n, probe) = self
+                    .read_at(Vec::with_capacity(PROBE_SIZE), pos)
+                    .submit()
+                    .await
+                    .map_err(|e| e.0)?;
+                if n == 0 {
+                    return Ok(buf);
+                }
+                buf.extend_from_slice(&probe);
+                pos += n as u64;
+                buf.reserve(buf.len().max(PROBE_SIZE));
+            }
+
+            let len = buf.len();
+            let (n, slice) = self
+                .read_at(buf.slice(len..), pos)
+                .submit()
+                .await
+                .map_err(|e| e.0)?;
+            buf = slice.into_inner();
+            if n == 0 {
+                return Ok(buf);
+            }
+            pos += n as u64;
+        }
+    }
+
     /// Like [`read_at`], but using a pre-mapped buffer
     /// registered with [`FixedBufRegistry`].
     ///
@@ -473,6 +627,100 @@ impl File {
         op.await
     }
 
+    /// Like [`readv_at`], but reading into pre-mapped buffers registered
+    /// with [`FixedBufRegistry`] or [`FixedBufPool`].
+    ///
+    /// The buffers are filled in order, as if by a single vectored read. Each
+    /// buffer is read by its own fixed-buffer read, and the reads are linked
+    /// so that a short read stops the chain. This lets records spanning
+    /// several pooled fixed buffers be read without an intermediate copy.
+    ///
+    /// [`readv_at`]: Self::readv_at
+    /// [`FixedBufRegistry`]: crate::buf::fixed::FixedBufRegistry
+    /// [`FixedBufPool`]: crate::buf::fixed::FixedBufPool
+    ///
+    /// # Return
+    ///
+    /// The method returns the total number of bytes read, and the same array
+    /// of buffers. The first `n` bytes across the buffers hold the data read.
+    ///
+    /// # Errors
+    ///
+    /// In addition to errors that can be reported by `readv_at`, this
+    /// operation fails if a buffer is not registered in the current
+    /// `tokio-uring` runtime. An error is only reported if it occurred before
+    /// any data was read; otherwise the bytes read so far are returned.
+    ///
+    /// The linked reads are submitted all at once, or not at all if the
+    /// submission fails, e.g. when the runtime's cap on operations in flight
+    /// leaves no room for all of them.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    ///# fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use tokio_uring::fs::File;
+    /// use tokio_uring::buf::fixed::FixedBufRegistry;
+    /// use std::iter;
+    ///
+    /// tokio_uring::start(async {
+    ///     let registry = FixedBufRegistry::new(iter::repeat(vec![0; 4096]).take(4));
+    ///     registry.register()?;
+    ///
+    ///     let f = File::open("foo.txt").await?;
+    ///     let buffers = vec![registry.check_out(0).unwrap(), registry.check_out(1).unwrap()];
+    ///
+    ///     // Read up to 8192 bytes
+    ///     let (n, _buffers) = f.readv_fixed_at(buffers, 0).await?;
+    ///
+    ///     println!("Read {} bytes", n);
+    ///
+    ///     // Close the file
+    ///     f.close().await?;
+    ///     Ok(())
+    /// })
+    ///# }
+    /// ```
+    pub async fn readv_fixed_at<T>(&self, bufs: Vec<T>, pos: u64) -> crate::Result<usize, Vec<T>>
+    where
+        T: BoundedBufMut<BufMut = FixedBuf>,
+    {
+        // Submit the linked reads, keeping their lengths to spot a short read
+        let lens: Vec<usize> = bufs.iter().map(|buf| buf.bytes_total()).collect();
+        let ops = Op::read_fixed_at_linked(&self.fd, bufs, pos)?;
+
+        let mut bufs = Vec::with_capacity(ops.len());
+        let mut total = 0;
+        let mut error = None;
+        let mut short = false;
+
+        // All reads must complete to get the buffers back. Only count bytes
+        // up to the first short read, anything after it was cancelled.
+        for (len, op) in lens.into_iter().zip(ops) {
+            match op.await {
+                Ok((n, buf)) => {
+                    if !short {
+                        total += n;
+                        short = n < len;
+                    }
+                    bufs.push(buf);
+                }
+                Err(crate::Error(e, buf)) => {
+                    if !short {
+                        error = Some(e);
+                        short = true;
+                    }
+                    bufs.push(buf);
+                }
+            }
+        }
+
+        match error {
+            Some(e) if total == 0 => Err(crate::Error(e, bufs)),
+            _ => Ok((total, bufs)),
+        }
+    }
+
     #[allow(missing_docs)]
     pub fn unsubmitted_read_fixed_at<T>(&self, buf: T, pos: u64) -> UnsubmittedRead<T>
     where
@@ -630,6 +878,88 @@ impl File {
         Ok(((), buf.into_inner()))
     }
 
+    /// Write a buffer into this file at the current file position, returning
+    /// how many bytes were written.
+    ///
+    /// This is [`write_at`] with an offset of `-1`: the write starts at the
+    /// file position, which it advances, as `write(2)` does. If the file was
+    /// opened with [`append`], the write starts at the end of the file
+    /// instead.
+    ///
+    /// The file position is shared by all the operations using it, so
+    /// writes in flight at the same time may be applied in any order.
+    ///
+    /// [`write_at`]: File::write_at
+    /// [`append`]: crate::fs::OpenOptions::append
+    pub fn write_at_current<T: BoundedBuf>(&self, buf: T) -> UnsubmittedWrite<T> {
+        UnsubmittedOneshot::write_at(&self.fd, buf, u64::MAX)
+    }
+
+    /// Appends an entire buffer to the file.
+    ///
+    /// The file must have been opened with [`append`], so that each write
+    /// goes to the end of the file as it is when the write is applied,
+    /// without tracking the length of the file. This calls
+    /// [`write_at_current`] until the whole buffer was written.
+    ///
+    /// As with `O_APPEND` in general, a buffer written in several writes may
+    /// be interleaved with the writes of others appending at the same time.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`WriteZero`](io::ErrorKind::WriteZero) if a write returns
+    /// `0`. The buffer is returned in all cases.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::OpenOptions;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let log = OpenOptions::new()
+    ///             .append(true)
+    ///             .create(true)
+    ///             .open("app.log")
+    ///             .await?;
+    ///
+    ///         log.append(&b"started\n"[..]).await?;
+    ///
+    ///         log.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    ///
+    /// [`append`]: crate::fs::OpenOptions::append
+    /// [`write_at_current`]: File::write_at_current
+    pub async fn append<T>(&self, buf: T) -> crate::Result<(), T>
+    where
+        T: BoundedBuf,
+    {
+        let orig_bounds = buf.bounds();
+        self.append_slice(buf.slice_full())
+            .await
+            .map_buf(|buf| T::from_buf_bounds(buf, orig_bounds))
+    }
+
+    async fn append_slice<T: IoBuf>(&self, mut buf: Slice<T>) -> crate::Result<(), T> {
+        while buf.bytes_init() != 0 {
+            match self.write_at_current(buf).submit().await {
+                Ok((0, slice)) => {
+                    return Err(crate::Error(
+                        io::Error::new(io::ErrorKind::WriteZero, "failed to write whole buffer"),
+                        slice.into_inner(),
+                    ))
+                }
+                Ok((n, slice)) => buf = slice.slice(n..),
+                Err(e) => return Err(e.map(|slice| slice.into_inner())),
+            };
+        }
+
+        Ok(((), buf.into_inner()))
+    }
+
     /// Like [`write_at`], but using a pre-mapped buffer
     /// registered with [`FixedBufRegistry`].
     ///
@@ -833,6 +1163,50 @@ impl File {
         Op::datasync(&self.fd)?.await
     }
 
+    /// Starts or waits for the writeback of the `len` bytes of the file
+    /// starting at `offset`, using `sync_file_range(2)`.
+    ///
+    /// A `len` of zero extends the range to the end of the file. `flags` is a
+    /// combination of:
+    ///
+    /// * `libc::SYNC_FILE_RANGE_WAIT_BEFORE`: wait for the writeback of the
+    ///   range already started.
+    /// * `libc::SYNC_FILE_RANGE_WRITE`: start the writeback of the dirty pages
+    ///   of the range, without waiting for it.
+    /// * `libc::SYNC_FILE_RANGE_WAIT_AFTER`: wait for the writeback of the
+    ///   range to complete.
+    ///
+    /// This lets a writer flush regions as it goes, rather than leave all of
+    /// its writes to a final [`sync_data`]. Unlike [`sync_data`], it syncs
+    /// neither the metadata of the file nor the disk cache, and makes no
+    /// guarantee the data survives a crash.
+    ///
+    /// [`sync_data`]: File::sync_data
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    /// use tokio_uring::Submit;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let f = File::create("foo.txt").await?;
+    ///         f.write_at(vec![0; 1 << 20], 0).submit().await?;
+    ///
+    ///         // Start writing the first MiB back, then carry on
+    ///         f.sync_range(0, 1 << 20, libc::SYNC_FILE_RANGE_WRITE).await?;
+    ///
+    ///         // Close the file
+    ///         f.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub async fn sync_range(&self, offset: u64, len: u32, flags: u32) -> io::Result<()> {
+        Op::sync_file_range(&self.fd, offset, len, flags)?.await
+    }
+
     /// Manipulate the allocated disk space of the file.
     ///
     /// The manipulated range starts at the `offset` and continues for `len` bytes.
@@ -862,9 +1236,162 @@ impl File {
         Op::fallocate(&self.fd, offset, len, flags)?.await
     }
 
+    /// Deallocates the `len` bytes of the file starting at `offset`, which
+    /// read as zeroes afterwards, leaving a hole in the file.
+    ///
+    /// The size of the file is unchanged. Partial blocks at the edges of the
+    /// range are zeroed rather than deallocated. This is
+    /// [`fallocate`](File::fallocate) with
+    /// `FALLOC_FL_PUNCH_HOLE | FALLOC_FL_KEEP_SIZE`, and fails with
+    /// `EOPNOTSUPP` on filesystems without sparse files.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::OpenOptions;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let f = OpenOptions::new().write(true).open("disk.img").await?;
+    ///
+    ///         // Give the space of a discarded MiB back to the filesystem
+    ///         f.punch_hole(1 << 20, 1 << 20).await?;
+    ///
+    ///         f.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub async fn punch_hole(&self, offset: u64, len: u64) -> io::Result<()> {
+        let flags = libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE;
+        self.fallocate(offset, len, flags).await
+    }
+
+    /// Zeroes the `len` bytes of the file starting at `offset`, extending
+    /// the file if the range ends past it.
+    ///
+    /// Unlike writing zeroes, the filesystem may mark the range as zeroed
+    /// without writing it, while keeping it allocated. This is
+    /// [`fallocate`](File::fallocate) with `FALLOC_FL_ZERO_RANGE`, and fails
+    /// with `EOPNOTSUPP` on filesystems which don't support it.
+    pub async fn zero_range(&self, offset: u64, len: u64) -> io::Result<()> {
+        self.fallocate(offset, len, libc::FALLOC_FL_ZERO_RANGE)
+            .await
+    }
+
+    /// Advises the kernel of how the `len` bytes of the file starting at
+    /// `offset` are going to be accessed, using `posix_fadvise(2)`.
+    ///
+    /// A `len` of zero extends the range to the end of the file. `advice` is
+    /// one of the `POSIX_FADV_*` values, e.g.:
+    ///
+    /// * `libc::POSIX_FADV_SEQUENTIAL`: the range is read sequentially, so the
+    ///   kernel may read ahead more.
+    /// * `libc::POSIX_FADV_WILLNEED`: the range is read soon, so the kernel may
+    ///   start reading it into the page cache.
+    /// * `libc::POSIX_FADV_DONTNEED`: the range won't be read again, so the
+    ///   kernel may drop it from the page cache once written back.
+    ///
+    /// The advice is only a hint, and does not change the behavior of the
+    /// operations on the file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let f = File::open("video.mp4").await?;
+    ///
+    ///         // The whole file is streamed once
+    ///         f.fadvise(0, 0, libc::POSIX_FADV_SEQUENTIAL).await?;
+    ///
+    ///         // Close the file
+    ///         f.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub async fn fadvise(&self, offset: u64, len: u32, advice: i32) -> io::Result<()> {
+        Op::fadvise(&self.fd, offset, len, advice)?.await
+    }
+
+    /// Truncates or extends the file to `len` bytes, as
+    /// [`std::fs::File::set_len`] does.
+    ///
+    /// Extending the file fills it with zeroes, without allocating space for
+    /// them. The file must be open for writing.
+    ///
+    /// Uses `IORING_OP_FTRUNCATE` on Linux 6.9 or later, and `ftruncate(2)`
+    /// on a blocking thread otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::OpenOptions;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let f = OpenOptions::new().write(true).open("app.log").await?;
+    ///
+    ///         // Drop everything but the first 4 KiB
+    ///         f.truncate(4096).await?;
+    ///
+    ///         f.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub async fn truncate(&self, len: u64) -> io::Result<()> {
+        if crate::runtime::supports(crate::io::IORING_OP_FTRUNCATE) {
+            return Op::ftruncate(&self.fd, len)?.await;
+        }
+
+        let len: libc::off_t = len
+            .try_into()
+            .map_err(|_| io::Error::from_raw_os_error(libc::EINVAL))?;
+        // The blocking thread holds its own descriptor for the same open
+        // file description, so the file may be closed in the meantime.
+        let fd = syscall!(fcntl(self.fd.raw_fd(), libc::F_DUPFD_CLOEXEC, 0))?;
+        // Safety: the descriptor was just duplicated, and is owned here.
+        let fd = unsafe { std::os::unix::io::OwnedFd::from_raw_fd(fd) };
+        tokio::task::spawn_blocking(move || syscall!(ftruncate(fd.as_raw_fd(), len)).map(|_| ()))
+            .await
+            .map_err(io::Error::other)?
+    }
+
+    /// Cancels all operations in flight on the file, returning how many were
+    /// cancelled.
+    ///
+    /// The cancelled operations fail with `ECANCELED`. Requires Linux 5.19 or
+    /// later.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let f = File::open("/dev/tty").await?;
+    ///         let cancelled = f.cancel_ops().await?;
+    ///         println!("cancelled {} operations", cancelled);
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub async fn cancel_ops(&self) -> io::Result<usize> {
+        self.fd.cancel_ops().await
+    }
+
     /// Closes the file using the uring asynchronous close operation and returns the possible error
     /// as described in the close(2) man page.
     ///
+    /// Operations still in flight on the file are cancelled, see
+    /// [`cancel_ops`](File::cancel_ops), and the close waits for them to
+    /// complete.
+    ///
     /// The programmer has the choice of calling this asynchronous close and waiting for the result
     /// or letting the library close the file automatically and simply letting the file go out of
     /// scope and having the library close the file descriptor automatically and synchronously.
@@ -968,5 +1495,205 @@ pub async fn remove_file<P: AsRef<Path>>(path: P) -> io::Result<()> {
 /// }
 /// ```
 pub async fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> {
-    Op::rename_at(from.as_ref(), to.as_ref(), 0)?.await
+    Op::rename_at(None, from.as_ref(), None, to.as_ref(), 0)?.await
+}
+
+/// Renames a file or directory to a new name, as [`rename`] does, with the
+/// `renameat2(2)` flags `flags`.
+///
+/// `flags` is a combination of:
+///
+/// * `libc::RENAME_NOREPLACE`: fail rather than replace `to` if it exists,
+///   atomically, unlike checking for `to` beforehand.
+/// * `libc::RENAME_EXCHANGE`: swap `from` and `to` atomically, both of which
+///   must exist, e.g. to switch to a new configuration directory at once.
+/// * `libc::RENAME_WHITEOUT`: leave a whiteout in place of `from`, for
+///   overlay filesystems.
+///
+/// A `flags` of `0` renames as [`rename`] does.
+///
+/// # Errors
+///
+/// Besides the errors of [`rename`]:
+///
+/// * `to` exists against `RENAME_NOREPLACE`.
+///      * [`io::ErrorKind`] would be set to `AlreadyExists`
+/// * `to` doesn't exist against `RENAME_EXCHANGE`.
+///      * [`io::ErrorKind`] would be set to `NotFound`
+/// * The filesystem doesn't support `flags`.
+///      * [`io::ErrorKind`] would be set to `InvalidInput`
+///
+/// # Example
+///
+/// ```no_run
+/// use tokio_uring::fs::rename_with;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         // Swap the current configuration with the new one
+///         rename_with("config.new", "config", libc::RENAME_EXCHANGE).await?;
+///         Ok::<(), std::io::Error>(())
+///     })?;
+///     Ok(())
+/// }
+/// ```
+pub async fn rename_with(
+    from: impl AsRef<Path>,
+    to: impl AsRef<Path>,
+    flags: u32,
+) -> io::Result<()> {
+    Op::rename_at(None, from.as_ref(), None, to.as_ref(), flags)?.await
+}
+
+/// Creates a new symbolic link at `link` pointing to `target`.
+///
+/// `target` is stored in the link as given: a relative `target` is resolved
+/// against the directory of `link` when the link is followed, and need not
+/// exist.
+///
+/// # Errors
+///
+/// * `link` already exists.
+///      * [`io::ErrorKind`] would be set to `AlreadyExists`
+/// * A parent of `link` doesn't exist.
+///      * [`io::ErrorKind`] would be set to `NotFound`
+/// * The user lacks permissions to create the link.
+///      * [`io::ErrorKind`] would be set to `PermissionDenied`
+///
+/// # Example
+///
+/// ```no_run
+/// use tokio_uring::fs::symlink;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         symlink("a.txt", "b.txt").await?; // b.txt now points to a.txt
+///         Ok::<(), std::io::Error>(())
+///     })?;
+///     Ok(())
+/// }
+/// ```
+pub async fn symlink(target: impl AsRef<Path>, link: impl AsRef<Path>) -> io::Result<()> {
+    Op::symlink(target.as_ref(), link.as_ref())?.await
+}
+
+/// Creates a new hard link at `link` to the file at `original`.
+///
+/// Both paths then name the same file, which is only removed once all of its
+/// names are. If `original` is a symbolic link, the link itself is linked
+/// rather than the file it points to, as with `std::fs::hard_link`.
+///
+/// # Errors
+///
+/// * `original` doesn't exist.
+///      * [`io::ErrorKind`] would be set to `NotFound`
+/// * `link` already exists.
+///      * [`io::ErrorKind`] would be set to `AlreadyExists`
+/// * `link` is on a different mount point.
+///      * [`io::ErrorKind`] would be set to `CrossesDevices`
+///
+/// # Example
+///
+/// ```no_run
+/// use tokio_uring::fs::hard_link;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         hard_link("a.txt", "b.txt").await?; // b.txt is now a.txt as well
+///         Ok::<(), std::io::Error>(())
+///     })?;
+///     Ok(())
+/// }
+/// ```
+pub async fn hard_link(original: impl AsRef<Path>, link: impl AsRef<Path>) -> io::Result<()> {
+    Op::link_at(original.as_ref(), link.as_ref(), 0)?.await
+}
+
+/// Reads the entire contents of the file at `path` into a vector.
+///
+/// This opens, reads and closes the file through the ring, reading it with
+/// [`File::read_to_end_at`].
+///
+/// # Errors
+///
+/// Fails if `path` doesn't exist, or if the user lacks permissions to read
+/// it.
+///
+/// # Examples
+///
+/// ```no_run
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         let manifest = tokio_uring::fs::read("Cargo.toml").await?;
+///         println!("{} bytes", manifest.len());
+///         Ok::<(), std::io::Error>(())
+///     })?;
+///     Ok(())
+/// }
+/// ```
+pub async fn read(path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+    let file = File::open(path).await?;
+    let res = file.read_to_end_at(0).await;
+    let closed = file.close().await;
+    let contents = res?;
+    closed?;
+    Ok(contents)
+}
+
+/// Reads the entire contents of the file at `path` into a string.
+///
+/// See [`read`] for the errors. Also fails with
+/// [`InvalidData`](io::ErrorKind::InvalidData) if the contents aren't valid
+/// UTF-8.
+///
+/// # Examples
+///
+/// ```no_run
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         let config = tokio_uring::fs::read_to_string("config.toml").await?;
+///         println!("{}", config);
+///         Ok::<(), std::io::Error>(())
+///     })?;
+///     Ok(())
+/// }
+/// ```
+pub async fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    String::from_utf8(read(path).await?).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "stream did not contain valid UTF-8",
+        )
+    })
+}
+
+/// Writes `contents` as the entire contents of the file at `path`.
+///
+/// The file is created if it doesn't exist, and truncated otherwise. It is
+/// opened, written with [`File::write_all_at`] and closed through the ring.
+/// `contents` is an owned buffer, which is written without copying it and
+/// dropped afterwards.
+///
+/// # Errors
+///
+/// Fails if the parent directory of `path` doesn't exist, or if the user
+/// lacks permissions to write the file.
+///
+/// # Examples
+///
+/// ```no_run
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         tokio_uring::fs::write("hello.txt", "hello world").await?;
+///         Ok::<(), std::io::Error>(())
+///     })?;
+///     Ok(())
+/// }
+/// ```
+pub async fn write<T: BoundedBuf>(path: impl AsRef<Path>, contents: T) -> io::Result<()> {
+    let file = File::create(path).await?;
+    let res = file.write_all_at(contents, 0).await;
+    let closed = file.close().await;
+    res.map_err(|e| e.0)?;
+    closed
 }
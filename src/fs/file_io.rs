@@ -0,0 +1,242 @@
+use crate::fs::File;
+use crate::io::read::{ReadData, ReadTransform};
+use crate::io::write::{WriteData, WriteTransform};
+use crate::{InFlightOneshot, Submit};
+use std::cmp;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+type InFlightRead = InFlightOneshot<ReadData<Vec<u8>>, ReadTransform<Vec<u8>>>;
+type InFlightWrite = InFlightOneshot<WriteData<Vec<u8>>, WriteTransform<Vec<u8>>>;
+
+// The size of the staging buffer, and so of the reads and writes submitted.
+const BUF_SIZE: usize = 64 * 1024;
+
+impl File {
+    /// Converts the file into a [`FileIo`], which reads and writes it from an
+    /// internal cursor through Tokio's [`AsyncRead`] and [`AsyncWrite`].
+    ///
+    /// The cursor starts at the beginning of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let mut reader = File::open("foo.txt").await?.into_io();
+    ///         let mut writer = File::create("bar.txt").await?.into_io();
+    ///
+    ///         // Requires the `io-util` feature of Tokio.
+    ///         tokio::io::copy(&mut reader, &mut writer).await?;
+    ///         tokio::io::AsyncWriteExt::flush(&mut writer).await?;
+    ///
+    ///         reader.into_inner().close().await?;
+    ///         writer.into_inner().close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub fn into_io(self) -> FileIo {
+        FileIo {
+            file: self,
+            pos: 0,
+            buf: Some(Vec::with_capacity(BUF_SIZE)),
+            consumed: 0,
+            state: State::Idle,
+        }
+    }
+}
+
+/// A [`File`] with a cursor, implementing Tokio's [`AsyncRead`] and
+/// [`AsyncWrite`], as returned by [`File::into_io`].
+///
+/// Reads and writes go through an owned staging buffer, since the ring needs
+/// to own the memory of an operation while it is in flight. Reads fill the
+/// buffer with a read of up to 64 KiB, which further reads are served from.
+///
+/// Writes are copied into the buffer and submitted right away: a write
+/// completes as soon as it is submitted, and its error, if any, is returned
+/// by the next write or [`poll_flush`](AsyncWrite::poll_flush). Flush
+/// before [`into_inner`](FileIo::into_inner) so no write is in flight
+/// anymore. Like [`File::write_at`], this doesn't sync the data.
+///
+/// Files opened with `O_APPEND` are written at their end regardless of the
+/// cursor, which then no longer matches the file.
+pub struct FileIo {
+    file: File,
+
+    /// Offset in the file of the end of the staged data.
+    pos: u64,
+
+    /// The staging buffer, `None` while an operation owns it.
+    buf: Option<Vec<u8>>,
+
+    /// Number of staged read bytes returned already.
+    consumed: usize,
+
+    state: State,
+}
+
+enum State {
+    Idle,
+    Reading(InFlightRead),
+    // The write, and the number of bytes it writes.
+    Writing(InFlightWrite, usize),
+}
+
+impl FileIo {
+    /// Returns the offset in the file of the cursor.
+    pub fn position(&self) -> u64 {
+        match (&self.state, &self.buf) {
+            (State::Idle, Some(buf)) => self.pos - (buf.len() - self.consumed) as u64,
+            (State::Writing(_, len), _) => self.pos + *len as u64,
+            _ => self.pos,
+        }
+    }
+
+    /// Returns a reference to the underlying file.
+    pub fn get_ref(&self) -> &File {
+        &self.file
+    }
+
+    /// Consumes the `FileIo`, returning the underlying file.
+    ///
+    /// Staged data which wasn't read is dropped. A write still in flight is
+    /// abandoned, and its error, if any, is lost.
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+
+    // Waits for the operation in flight to complete, if any.
+    fn poll_idle(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            match &mut self.state {
+                State::Idle => return Poll::Ready(Ok(())),
+                State::Reading(read) => {
+                    let res = ready!(Pin::new(read).poll(cx));
+                    self.state = State::Idle;
+                    match res {
+                        Ok((n, buf)) => {
+                            self.pos += n as u64;
+                            self.buf = Some(buf);
+                            self.consumed = 0;
+                        }
+                        Err(crate::Error(e, buf)) => {
+                            self.buf = Some(buf);
+                            return Poll::Ready(Err(e));
+                        }
+                    }
+                }
+                State::Writing(write, _) => {
+                    let res = ready!(Pin::new(write).poll(cx));
+                    self.state = State::Idle;
+                    match res {
+                        Ok((0, mut buf)) if !buf.is_empty() => {
+                            buf.clear();
+                            self.buf = Some(buf);
+                            return Poll::Ready(Err(io::Error::new(
+                                io::ErrorKind::WriteZero,
+                                "failed to write whole buffer",
+                            )));
+                        }
+                        Ok((n, mut buf)) => {
+                            self.pos += n as u64;
+                            buf.drain(..n);
+                            if buf.is_empty() {
+                                self.buf = Some(buf);
+                            } else {
+                                // Write the rest of a short write.
+                                let len = buf.len();
+                                let write = self.file.write_at(buf, self.pos).submit();
+                                self.state = State::Writing(write, len);
+                            }
+                        }
+                        Err(crate::Error(e, mut buf)) => {
+                            buf.clear();
+                            self.buf = Some(buf);
+                            return Poll::Ready(Err(e));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl AsyncRead for FileIo {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        out: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if let State::Writing(..) = this.state {
+            ready!(this.poll_idle(cx))?;
+        }
+
+        if let State::Idle = this.state {
+            let buf = this.buf.as_ref().expect("the staging buffer is owned");
+            if this.consumed == buf.len() && out.remaining() > 0 {
+                let mut buf = this.buf.take().expect("the staging buffer is owned");
+                buf.clear();
+                this.consumed = 0;
+                let read = this.file.read_at(buf, this.pos).submit();
+                this.state = State::Reading(read);
+            }
+        }
+        ready!(this.poll_idle(cx))?;
+
+        // Nothing is staged anymore at the end of the file.
+        let buf = this.buf.as_ref().expect("the staging buffer is owned");
+        let n = cmp::min(buf.len() - this.consumed, out.remaining());
+        out.put_slice(&buf[this.consumed..this.consumed + n]);
+        this.consumed += n;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for FileIo {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        data: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_idle(cx))?;
+
+        // Staged data which wasn't read is dropped, writing at the cursor.
+        let mut buf = this.buf.take().expect("the staging buffer is owned");
+        this.pos -= buf.len().saturating_sub(this.consumed) as u64;
+        this.consumed = 0;
+        buf.clear();
+
+        let n = cmp::min(data.len(), BUF_SIZE);
+        buf.extend_from_slice(&data[..n]);
+        let write = this.file.write_at(buf, this.pos).submit();
+        this.state = State::Writing(write, n);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_idle(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl fmt::Debug for FileIo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileIo")
+            .field("file", &self.file)
+            .field("position", &self.position())
+            .finish()
+    }
+}
@@ -0,0 +1,220 @@
+use super::File;
+use std::convert::TryInto;
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// The kind of a byte-range lock taken with [`File::lock_range`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockType {
+    /// A shared (read) lock. Any number of shared locks may overlap.
+    Shared,
+
+    /// An exclusive (write) lock, which excludes all other locks on the range.
+    Exclusive,
+}
+
+impl File {
+    /// Locks the byte range starting at `offset` and spanning `len` bytes,
+    /// waiting until conflicting locks are released.
+    ///
+    /// A `len` of zero extends the range to the end of the file, however
+    /// large it grows. Locking a range already locked through this file
+    /// converts the lock to `lock_type`. Shared locks require the file to be
+    /// open for reading, exclusive locks for writing.
+    ///
+    /// The locks are open file description locks (`F_OFD_SETLK`): they belong
+    /// to the file opened by `self` rather than to the process, so they
+    /// conflict with locks taken through other opens of the file, both in
+    /// this process and in others. They are released with
+    /// [`unlock_range`](File::unlock_range), or once the file is closed.
+    ///
+    /// `io_uring` has no locking operation. If the range cannot be locked
+    /// right away, the wait is done with a blocking `fcntl(2)` on Tokio's
+    /// blocking thread pool. If the returned future is dropped while waiting,
+    /// a lock acquired afterwards is released again.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::{LockType, OpenOptions};
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let f = OpenOptions::new().read(true).write(true).open("foo.db").await?;
+    ///
+    ///         // Lock the header of the database
+    ///         f.lock_range(0, 100, LockType::Exclusive).await?;
+    ///         // ...
+    ///         f.unlock_range(0, 100)?;
+    ///
+    ///         f.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub async fn lock_range(&self, offset: u64, len: u64, lock_type: LockType) -> io::Result<()> {
+        if self.try_lock_range(offset, len, lock_type)? {
+            return Ok(());
+        }
+
+        // The blocking thread holds its own descriptor for the same open
+        // file description, so the file may be closed in the meantime.
+        let fd = syscall!(fcntl(self.fd.raw_fd(), libc::F_DUPFD_CLOEXEC, 0))?;
+        // Safety: the descriptor was just duplicated, and is owned here.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        let guard = CancelGuard(Arc::new(AtomicBool::new(false)));
+        let cancelled = guard.0.clone();
+
+        tokio::task::spawn_blocking(move || {
+            loop {
+                match set_lock(fd.as_raw_fd(), libc::F_OFD_SETLKW, offset, len, lock_type) {
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    res => res?,
+                }
+                break;
+            }
+
+            if cancelled.load(Ordering::Acquire) {
+                // Nobody is waiting for the lock anymore.
+                let _ = unlock(fd.as_raw_fd(), offset, len);
+            }
+            Ok(())
+        })
+        .await
+        .map_err(io::Error::other)?
+    }
+
+    /// Attempts to lock the byte range starting at `offset` and spanning
+    /// `len` bytes, without waiting.
+    ///
+    /// Returns `false` if a conflicting lock is held through another open of
+    /// the file. See [`lock_range`](File::lock_range) for the semantics of
+    /// the locks.
+    pub fn try_lock_range(&self, offset: u64, len: u64, lock_type: LockType) -> io::Result<bool> {
+        match set_lock(self.fd.raw_fd(), libc::F_OFD_SETLK, offset, len, lock_type) {
+            Ok(()) => Ok(true),
+            Err(e) if matches!(e.raw_os_error(), Some(libc::EAGAIN) | Some(libc::EACCES)) => {
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Releases the locks held through this file on the byte range starting
+    /// at `offset` and spanning `len` bytes.
+    ///
+    /// A `len` of zero extends the range to the end of the file. Unlocking
+    /// part of a locked range splits the lock.
+    pub fn unlock_range(&self, offset: u64, len: u64) -> io::Result<()> {
+        unlock(self.fd.raw_fd(), offset, len)
+    }
+
+    /// Takes a shared lock on the whole file, waiting until conflicting
+    /// locks are released.
+    ///
+    /// This is [`lock_range`](File::lock_range) over the whole file, however
+    /// large it grows, and is released with [`unlock`](File::unlock).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let f = File::open("foo.db").await?;
+    ///
+    ///         f.lock_shared().await?;
+    ///         // Read the database, while no writer holds it...
+    ///         f.unlock()?;
+    ///
+    ///         f.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub async fn lock_shared(&self) -> io::Result<()> {
+        self.lock_range(0, 0, LockType::Shared).await
+    }
+
+    /// Takes an exclusive lock on the whole file, waiting until conflicting
+    /// locks are released.
+    ///
+    /// This is [`lock_range`](File::lock_range) over the whole file, however
+    /// large it grows, and is released with [`unlock`](File::unlock). The
+    /// file must be open for writing.
+    pub async fn lock_exclusive(&self) -> io::Result<()> {
+        self.lock_range(0, 0, LockType::Exclusive).await
+    }
+
+    /// Attempts to take an exclusive lock on the whole file, without waiting.
+    ///
+    /// Returns `false` if another open of the file holds a lock on any part
+    /// of it.
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.try_lock_range(0, 0, LockType::Exclusive)
+    }
+
+    /// Attempts to take a shared lock on the whole file, without waiting.
+    ///
+    /// Returns `false` if another open of the file holds an exclusive lock
+    /// on any part of it.
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.try_lock_range(0, 0, LockType::Shared)
+    }
+
+    /// Releases all locks held through this file.
+    pub fn unlock(&self) -> io::Result<()> {
+        self.unlock_range(0, 0)
+    }
+}
+
+// Flags a pending lock as abandoned when the waiting future is dropped.
+struct CancelGuard(Arc<AtomicBool>);
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+fn set_lock(
+    fd: RawFd,
+    cmd: libc::c_int,
+    offset: u64,
+    len: u64,
+    lock_type: LockType,
+) -> io::Result<()> {
+    let l_type = match lock_type {
+        LockType::Shared => libc::F_RDLCK,
+        LockType::Exclusive => libc::F_WRLCK,
+    };
+    fcntl_lock(fd, cmd, l_type, offset, len)
+}
+
+fn unlock(fd: RawFd, offset: u64, len: u64) -> io::Result<()> {
+    fcntl_lock(fd, libc::F_OFD_SETLK, libc::F_UNLCK, offset, len)
+}
+
+fn fcntl_lock(
+    fd: RawFd,
+    cmd: libc::c_int,
+    l_type: libc::c_int,
+    offset: u64,
+    len: u64,
+) -> io::Result<()> {
+    let out_of_range = || io::Error::new(io::ErrorKind::InvalidInput, "lock range out of range");
+
+    // Safety: flock is a plain C struct, l_pid must be zero for OFD locks.
+    let mut lock: libc::flock = unsafe { std::mem::zeroed() };
+    lock.l_type = l_type as _;
+    lock.l_whence = libc::SEEK_SET as _;
+    lock.l_start = offset.try_into().map_err(|_| out_of_range())?;
+    lock.l_len = len.try_into().map_err(|_| out_of_range())?;
+
+    syscall!(fcntl(fd, cmd, &lock))?;
+    Ok(())
+}
@@ -0,0 +1,293 @@
+use super::{File, StatxBuilder};
+use std::convert::TryInto;
+use std::fs::Permissions;
+use std::io;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Metadata information about a file, as returned by [`File::metadata`],
+/// [`metadata`] and [`symlink_metadata`].
+///
+/// This mirrors [`std::fs::Metadata`], backed by a `statx(2)` call. The raw
+/// structure is available through [`statx`](Metadata::statx).
+#[derive(Clone, Copy)]
+pub struct Metadata {
+    statx: libc::statx,
+}
+
+/// Timestamps of a file, to be applied with [`File::set_times`].
+///
+/// Timestamps which are not set are left unchanged.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FileTimes {
+    accessed: Option<SystemTime>,
+    modified: Option<SystemTime>,
+}
+
+impl Metadata {
+    /// Returns the size of the file, in bytes.
+    pub fn len(&self) -> u64 {
+        self.statx.stx_size
+    }
+
+    /// Returns `true` if the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if this metadata is for a regular file.
+    pub fn is_file(&self) -> bool {
+        self.file_type() == libc::S_IFREG
+    }
+
+    /// Returns `true` if this metadata is for a directory.
+    pub fn is_dir(&self) -> bool {
+        self.file_type() == libc::S_IFDIR
+    }
+
+    /// Returns `true` if this metadata is for a symbolic link.
+    ///
+    /// Only metadata obtained with [`symlink_metadata`] can describe a link.
+    pub fn is_symlink(&self) -> bool {
+        self.file_type() == libc::S_IFLNK
+    }
+
+    /// Returns the permissions of the file.
+    pub fn permissions(&self) -> Permissions {
+        Permissions::from_mode(u32::from(self.statx.stx_mode) & 0o7777)
+    }
+
+    /// Returns the mode of the file, its type and permission bits, as
+    /// [`MetadataExt::mode`](std::os::unix::fs::MetadataExt::mode) does.
+    pub fn mode(&self) -> u32 {
+        u32::from(self.statx.stx_mode)
+    }
+
+    /// Returns the user ID of the owner of the file.
+    pub fn uid(&self) -> u32 {
+        self.statx.stx_uid
+    }
+
+    /// Returns the group ID of the owner of the file.
+    pub fn gid(&self) -> u32 {
+        self.statx.stx_gid
+    }
+
+    /// Returns the last modification time of the file.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the filesystem does not report the modification time.
+    pub fn modified(&self) -> io::Result<SystemTime> {
+        self.time(libc::STATX_MTIME, self.statx.stx_mtime, "modification")
+    }
+
+    /// Returns the last access time of the file.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the filesystem does not report the access time.
+    pub fn accessed(&self) -> io::Result<SystemTime> {
+        self.time(libc::STATX_ATIME, self.statx.stx_atime, "access")
+    }
+
+    /// Returns the creation time of the file.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the filesystem does not record the creation time.
+    pub fn created(&self) -> io::Result<SystemTime> {
+        self.time(libc::STATX_BTIME, self.statx.stx_btime, "creation")
+    }
+
+    /// Returns the raw `statx(2)` structure.
+    pub fn statx(&self) -> &libc::statx {
+        &self.statx
+    }
+
+    fn file_type(&self) -> u32 {
+        u32::from(self.statx.stx_mode) & libc::S_IFMT
+    }
+
+    fn time(&self, mask: u32, ts: libc::statx_timestamp, what: &str) -> io::Result<SystemTime> {
+        if self.statx.stx_mask & mask == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("{} time is not available on this filesystem", what),
+            ));
+        }
+
+        let nanos = Duration::from_nanos(u64::from(ts.tv_nsec));
+        Ok(if ts.tv_sec >= 0 {
+            UNIX_EPOCH + Duration::from_secs(ts.tv_sec as u64) + nanos
+        } else {
+            UNIX_EPOCH - Duration::from_secs(ts.tv_sec.unsigned_abs()) + nanos
+        })
+    }
+}
+
+impl From<libc::statx> for Metadata {
+    fn from(statx: libc::statx) -> Metadata {
+        Metadata { statx }
+    }
+}
+
+impl std::fmt::Debug for Metadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Metadata")
+            .field("len", &self.len())
+            .field("file_type", &self.file_type())
+            .field("permissions", &self.permissions())
+            .field("modified", &self.modified())
+            .field("accessed", &self.accessed())
+            .field("created", &self.created())
+            .finish()
+    }
+}
+
+impl FileTimes {
+    /// Creates a set of timestamps which leaves every timestamp unchanged.
+    pub fn new() -> FileTimes {
+        FileTimes::default()
+    }
+
+    /// Sets the last access time.
+    pub fn set_accessed(mut self, t: SystemTime) -> FileTimes {
+        self.accessed = Some(t);
+        self
+    }
+
+    /// Sets the last modification time.
+    pub fn set_modified(mut self, t: SystemTime) -> FileTimes {
+        self.modified = Some(t);
+        self
+    }
+
+    pub(crate) fn to_timespecs(self) -> io::Result<[libc::timespec; 2]> {
+        Ok([timespec(self.accessed)?, timespec(self.modified)?])
+    }
+}
+
+fn timespec(t: Option<SystemTime>) -> io::Result<libc::timespec> {
+    let t = match t {
+        Some(t) => t,
+        None => {
+            return Ok(libc::timespec {
+                tv_sec: 0,
+                tv_nsec: libc::UTIME_OMIT,
+            })
+        }
+    };
+
+    let overflow = || io::Error::new(io::ErrorKind::InvalidInput, "timestamp is out of range");
+    match t.duration_since(UNIX_EPOCH) {
+        Ok(d) => Ok(libc::timespec {
+            tv_sec: d.as_secs().try_into().map_err(|_| overflow())?,
+            tv_nsec: d.subsec_nanos() as _,
+        }),
+        Err(e) => {
+            // Before the epoch: round the seconds down, keep nanoseconds positive.
+            let d = e.duration();
+            let mut secs: libc::time_t = d.as_secs().try_into().map_err(|_| overflow())?;
+            let mut nanos = d.subsec_nanos();
+            if nanos > 0 {
+                secs += 1;
+                nanos = 1_000_000_000 - nanos;
+            }
+            Ok(libc::timespec {
+                tv_sec: -secs,
+                tv_nsec: nanos as _,
+            })
+        }
+    }
+}
+
+impl File {
+    /// Queries metadata about the underlying file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    ///
+    /// tokio_uring::start(async {
+    ///     let f = File::open("foo.txt").await.unwrap();
+    ///     let metadata = f.metadata().await.unwrap();
+    ///     println!("{} bytes, modified {:?}", metadata.len(), metadata.modified());
+    ///     f.close().await.unwrap();
+    /// })
+    /// ```
+    pub async fn metadata(&self) -> io::Result<Metadata> {
+        self.statx().await.map(Metadata::from)
+    }
+
+    /// Changes the timestamps of the underlying file.
+    ///
+    /// Timestamps not set in `times` are left unchanged. `io_uring` has no
+    /// operation for this, so it is a blocking `futimens(2)` call; it does not
+    /// wait for any I/O.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::SystemTime;
+    /// use tokio_uring::fs::{File, FileTimes};
+    ///
+    /// tokio_uring::start(async {
+    ///     let f = File::open("foo.txt").await.unwrap();
+    ///     f.set_times(FileTimes::new().set_modified(SystemTime::now())).unwrap();
+    ///     f.close().await.unwrap();
+    /// })
+    /// ```
+    pub fn set_times(&self, times: FileTimes) -> io::Result<()> {
+        let times = times.to_timespecs()?;
+        syscall!(futimens(self.fd.raw_fd(), times.as_ptr()))?;
+        Ok(())
+    }
+
+    /// Changes the modification time of the underlying file.
+    ///
+    /// This is an alias for `set_times(FileTimes::new().set_modified(time))`.
+    pub fn set_modified(&self, time: SystemTime) -> io::Result<()> {
+        self.set_times(FileTimes::new().set_modified(time))
+    }
+}
+
+/// Queries the metadata of the file at `path`, following symbolic links.
+///
+/// # Examples
+///
+/// ```no_run
+/// tokio_uring::start(async {
+///     let metadata = tokio_uring::fs::metadata("foo.txt").await.unwrap();
+///     assert!(metadata.is_file());
+/// })
+/// ```
+pub async fn metadata<P: AsRef<Path>>(path: P) -> io::Result<Metadata> {
+    StatxBuilder::new()
+        .pathname(path)?
+        .statx()
+        .await
+        .map(Metadata::from)
+}
+
+/// Queries the metadata of the file at `path`, without following symbolic
+/// links.
+///
+/// # Examples
+///
+/// ```no_run
+/// tokio_uring::start(async {
+///     let metadata = tokio_uring::fs::symlink_metadata("link").await.unwrap();
+///     assert!(metadata.is_symlink());
+/// })
+/// ```
+pub async fn symlink_metadata<P: AsRef<Path>>(path: P) -> io::Result<Metadata> {
+    StatxBuilder::new()
+        .symlink_nofollow(true)
+        .pathname(path)?
+        .statx()
+        .await
+        .map(Metadata::from)
+}
@@ -1,22 +1,87 @@
 //! Filesystem manipulation operations.
 
+mod dir;
+pub use dir::Dir;
+
+mod direct_file;
+pub use direct_file::DirectFile;
+
 mod directory;
 pub use directory::create_dir;
 pub use directory::remove_dir;
 
+mod buf_reader;
+pub use buf_reader::BufReader;
+
+mod buf_writer;
+pub use buf_writer::BufWriter;
+
+mod copy;
+pub use copy::copy;
+
 mod create_dir_all;
 pub use create_dir_all::create_dir_all;
 pub use create_dir_all::DirBuilder;
 
 mod file;
+pub use file::hard_link;
+pub use file::read;
+pub use file::read_to_string;
 pub use file::remove_file;
 pub use file::rename;
+pub use file::rename_with;
+pub use file::symlink;
+pub use file::write;
 pub use file::File;
 
+mod file_io;
+pub use file_io::FileIo;
+
+mod lock;
+pub use lock::LockType;
+
+mod metadata;
+pub use metadata::metadata;
+pub use metadata::symlink_metadata;
+pub use metadata::FileTimes;
+pub use metadata::Metadata;
+
+mod nvme;
+pub use nvme::NvmeCommand;
+pub use nvme::NvmeDevice;
+pub use nvme::NvmeStatus;
+
 mod open_options;
 pub use open_options::OpenOptions;
 
+mod permissions;
+pub use permissions::set_permissions;
+
+mod read_ahead;
+pub use read_ahead::ReadAhead;
+
+mod read_dir;
+pub use read_dir::read_dir;
+pub use read_dir::DirEntry;
+pub use read_dir::FileType;
+pub use read_dir::ReadDir;
+
+mod read_stream;
+pub use read_stream::ReadStream;
+
+mod remove_dir_all;
+pub use remove_dir_all::remove_dir_all;
+
 mod statx;
 pub use statx::is_dir_regfile;
 pub use statx::statx;
+pub use statx::statx_at;
 pub use statx::StatxBuilder;
+
+mod sync_coalescer;
+pub use sync_coalescer::SyncCoalescer;
+
+mod xattr;
+pub use xattr::getxattr;
+pub use xattr::listxattr;
+pub use xattr::setxattr;
@@ -0,0 +1,428 @@
+use crate::buf::{BoundedBuf, BoundedBufMut};
+use crate::fs::{File, OpenOptions};
+use crate::{UnsubmittedNvme, UnsubmittedNvmeRead};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+// `_IO('N', 0x40)`, returning the namespace ID of the device.
+const NVME_IOCTL_ID: libc::c_ulong = 0x4e40;
+
+// Admin opcodes.
+const ADMIN_IDENTIFY: u8 = 0x06;
+
+// I/O opcodes of the NVM command set.
+const IO_FLUSH: u8 = 0x00;
+const IO_WRITE: u8 = 0x01;
+const IO_READ: u8 = 0x02;
+
+/// A namespace of an NVMe device, to which commands are passed through with
+/// `IORING_OP_URING_CMD`, bypassing the block layer.
+///
+/// The device is opened through the generic character device of the
+/// namespace, `/dev/ngXnY`, which accepts any command, unlike the block
+/// device. Commands are described by an [`NvmeCommand`], and submitted with
+/// [`execute`](NvmeDevice::execute), or with
+/// [`execute_read`](NvmeDevice::execute_read) and
+/// [`execute_write`](NvmeDevice::execute_write) for those transferring data.
+///
+/// Passthrough requires Linux 5.19 or later, and a ring set up with both
+/// [`Builder::sqe128`](crate::Builder::sqe128) and
+/// [`Builder::cqe32`](crate::Builder::cqe32): commands fail with
+/// `EOPNOTSUPP` otherwise. Admin commands also require `CAP_SYS_ADMIN`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::{NvmeCommand, NvmeDevice, OpenOptions};
+/// use tokio_uring::Submit;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::builder().sqe128().cqe32().start(async {
+///         let dev = NvmeDevice::open("/dev/ng0n1", OpenOptions::new().read(true)).await?;
+///
+///         // Read the first 8 blocks of 512 bytes.
+///         let cmd = NvmeCommand::read(dev.nsid(), 0, 8);
+///         let (_, buf) = dev.execute_read(&cmd, vec![0; 4096]).submit().await?;
+///         println!("{:?}", &buf[..16]);
+///
+///         dev.close().await?;
+///         Ok(())
+///     })
+/// }
+/// ```
+pub struct NvmeDevice {
+    file: File,
+    nsid: u32,
+}
+
+impl NvmeDevice {
+    /// Opens the generic character device of a namespace at `path` with
+    /// `options`.
+    ///
+    /// Reads of the namespace require `options` to open the device for
+    /// reading, and writes for writing.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ENOTTY` if the file isn't a namespace of an NVMe device.
+    /// See [`OpenOptions::open`] for the other errors.
+    pub async fn open(path: impl AsRef<Path>, options: &OpenOptions) -> io::Result<NvmeDevice> {
+        let file = options.open(path).await?;
+        match NvmeDevice::nsid_of(&file) {
+            Ok(nsid) => Ok(NvmeDevice { file, nsid }),
+            Err(e) => {
+                let _ = file.close().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Wraps an open namespace device.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ENOTTY` if the file isn't a namespace of an NVMe device,
+    /// returning it with the error.
+    pub fn from_file(file: File) -> Result<NvmeDevice, (io::Error, File)> {
+        match NvmeDevice::nsid_of(&file) {
+            Ok(nsid) => Ok(NvmeDevice { file, nsid }),
+            Err(e) => Err((e, file)),
+        }
+    }
+
+    fn nsid_of(file: &File) -> io::Result<u32> {
+        let nsid = syscall!(ioctl(file.fd.raw_fd(), NVME_IOCTL_ID))?;
+        Ok(nsid as u32)
+    }
+
+    /// Returns the namespace ID of the device.
+    pub fn nsid(&self) -> u32 {
+        self.nsid
+    }
+
+    /// Submits a command which doesn't transfer data, such as a flush.
+    ///
+    /// The operation completes with the command specific result, the first
+    /// two dwords of the completion entry.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the errno if the command couldn't be issued, and with an
+    /// [`NvmeStatus`] error if the device completed it unsuccessfully.
+    pub fn execute(&self, cmd: &NvmeCommand) -> UnsubmittedNvme<()> {
+        UnsubmittedNvme::nvme(&self.file.fd, cmd)
+    }
+
+    /// Submits a command which transfers data from the device into `buf`,
+    /// such as a read or an identify.
+    ///
+    /// The whole capacity of `buf` is given to the device, which must be the
+    /// length the command implies: the number of blocks times the block size
+    /// for a read. Bytes of `buf` not initialized yet are zeroed before the
+    /// command is submitted, so the whole buffer is initialized whatever the
+    /// device transferred.
+    ///
+    /// # Errors
+    ///
+    /// See [`execute`](NvmeDevice::execute).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity of `buf` is 4 GiB or more.
+    pub fn execute_read<T: BoundedBufMut>(
+        &self,
+        cmd: &NvmeCommand,
+        buf: T,
+    ) -> UnsubmittedNvmeRead<T> {
+        UnsubmittedNvmeRead::nvme_read(&self.file.fd, cmd, buf)
+    }
+
+    /// Submits a command which transfers the initialized bytes of `buf` to
+    /// the device, such as a write.
+    ///
+    /// The number of initialized bytes must be the length the command
+    /// implies.
+    ///
+    /// # Errors
+    ///
+    /// See [`execute`](NvmeDevice::execute).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf` holds 4 GiB or more.
+    pub fn execute_write<T: BoundedBuf>(&self, cmd: &NvmeCommand, buf: T) -> UnsubmittedNvme<T> {
+        UnsubmittedNvme::nvme_write(&self.file.fd, cmd, buf)
+    }
+
+    /// Returns the underlying file.
+    pub fn as_file(&self) -> &File {
+        &self.file
+    }
+
+    /// Consumes the `NvmeDevice`, returning the underlying file.
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+
+    /// Closes the device, see [`File::close`].
+    pub async fn close(self) -> io::Result<()> {
+        self.file.close().await
+    }
+}
+
+impl fmt::Debug for NvmeDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NvmeDevice")
+            .field("file", &self.file)
+            .field("nsid", &self.nsid)
+            .finish()
+    }
+}
+
+/// An NVMe command, submitted with an [`NvmeDevice`].
+///
+/// Common commands are built with the typed constructors, such as
+/// [`read`](NvmeCommand::read) and
+/// [`identify_controller`](NvmeCommand::identify_controller), and any other
+/// with [`admin`](NvmeCommand::admin) or [`io`](NvmeCommand::io) and the
+/// setters of its fields. The data buffer is given when the command is
+/// submitted.
+///
+/// # Examples
+///
+/// ```
+/// use tokio_uring::fs::NvmeCommand;
+///
+/// // Get Log Page, of the SMART / Health Information log.
+/// let cmd = NvmeCommand::admin(0x02)
+///     .nsid(0xffff_ffff)
+///     .cdw10((512 / 4 - 1) << 16 | 0x02);
+/// assert!(cmd.is_admin());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NvmeCommand {
+    admin: bool,
+    opcode: u8,
+    flags: u8,
+    nsid: u32,
+    cdw2: u32,
+    cdw3: u32,
+    cdw10_15: [u32; 6],
+    timeout_ms: u32,
+}
+
+macro_rules! cdw_setters {
+    ($($name:ident: $index:expr),*) => {
+        $(
+            #[doc = concat!("Sets command dword ", stringify!($index), ".")]
+            pub fn $name(mut self, value: u32) -> Self {
+                self.cdw10_15[$index - 10] = value;
+                self
+            }
+        )*
+    };
+}
+
+impl NvmeCommand {
+    /// Creates an admin command with `opcode`, whose fields are zero.
+    pub fn admin(opcode: u8) -> NvmeCommand {
+        NvmeCommand::new(true, opcode)
+    }
+
+    /// Creates an I/O command with `opcode`, whose fields are zero.
+    pub fn io(opcode: u8) -> NvmeCommand {
+        NvmeCommand::new(false, opcode)
+    }
+
+    fn new(admin: bool, opcode: u8) -> NvmeCommand {
+        NvmeCommand {
+            admin,
+            opcode,
+            flags: 0,
+            nsid: 0,
+            cdw2: 0,
+            cdw3: 0,
+            cdw10_15: [0; 6],
+            timeout_ms: 0,
+        }
+    }
+
+    /// Creates an Identify command for the controller data structure, which
+    /// transfers 4096 bytes from the device.
+    pub fn identify_controller() -> NvmeCommand {
+        NvmeCommand::admin(ADMIN_IDENTIFY).cdw10(0x01)
+    }
+
+    /// Creates an Identify command for the data structure of namespace
+    /// `nsid`, which transfers 4096 bytes from the device.
+    pub fn identify_namespace(nsid: u32) -> NvmeCommand {
+        NvmeCommand::admin(ADMIN_IDENTIFY).nsid(nsid).cdw10(0x00)
+    }
+
+    /// Creates a Read command of `blocks` logical blocks from namespace
+    /// `nsid`, starting at block `lba`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `blocks` is zero or greater than 65536.
+    pub fn read(nsid: u32, lba: u64, blocks: u32) -> NvmeCommand {
+        NvmeCommand::io(IO_READ).nsid(nsid).blocks(lba, blocks)
+    }
+
+    /// Creates a Write command of `blocks` logical blocks to namespace
+    /// `nsid`, starting at block `lba`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `blocks` is zero or greater than 65536.
+    pub fn write(nsid: u32, lba: u64, blocks: u32) -> NvmeCommand {
+        NvmeCommand::io(IO_WRITE).nsid(nsid).blocks(lba, blocks)
+    }
+
+    /// Creates a Flush command of namespace `nsid`, making the data written
+    /// to it durable.
+    pub fn flush(nsid: u32) -> NvmeCommand {
+        NvmeCommand::io(IO_FLUSH).nsid(nsid)
+    }
+
+    fn blocks(self, lba: u64, blocks: u32) -> NvmeCommand {
+        assert!(
+            (1..=65536).contains(&blocks),
+            "blocks must be between 1 and 65536"
+        );
+        // The number of blocks is zero based.
+        self.cdw10(lba as u32)
+            .cdw11((lba >> 32) as u32)
+            .cdw12(blocks - 1)
+    }
+
+    /// Returns `true` for an admin command, `false` for an I/O command.
+    pub fn is_admin(&self) -> bool {
+        self.admin
+    }
+
+    /// Sets the flags of the command.
+    pub fn flags(mut self, flags: u8) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the namespace ID.
+    pub fn nsid(mut self, nsid: u32) -> Self {
+        self.nsid = nsid;
+        self
+    }
+
+    /// Sets command dword 2.
+    pub fn cdw2(mut self, value: u32) -> Self {
+        self.cdw2 = value;
+        self
+    }
+
+    /// Sets command dword 3.
+    pub fn cdw3(mut self, value: u32) -> Self {
+        self.cdw3 = value;
+        self
+    }
+
+    cdw_setters!(cdw10: 10, cdw11: 11, cdw12: 12, cdw13: 13, cdw14: 14, cdw15: 15);
+
+    /// Sets the time the driver waits for the command, after which it aborts
+    /// it. The default timeout of the driver applies if it isn't set.
+    ///
+    /// The timeout is truncated to milliseconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout_ms = u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX);
+        self
+    }
+
+    // Encodes the command as a `struct nvme_uring_cmd`, with the data buffer
+    // at `addr`.
+    pub(crate) fn to_bytes(&self, addr: u64, data_len: u32) -> [u8; 80] {
+        let mut cmd = [0; 80];
+        cmd[0] = self.opcode;
+        cmd[1] = self.flags;
+        cmd[4..8].copy_from_slice(&self.nsid.to_ne_bytes());
+        cmd[8..12].copy_from_slice(&self.cdw2.to_ne_bytes());
+        cmd[12..16].copy_from_slice(&self.cdw3.to_ne_bytes());
+        cmd[24..32].copy_from_slice(&addr.to_ne_bytes());
+        cmd[36..40].copy_from_slice(&data_len.to_ne_bytes());
+        for (i, cdw) in self.cdw10_15.iter().enumerate() {
+            cmd[40 + i * 4..44 + i * 4].copy_from_slice(&cdw.to_ne_bytes());
+        }
+        cmd[64..68].copy_from_slice(&self.timeout_ms.to_ne_bytes());
+        cmd
+    }
+}
+
+/// The status of an NVMe command the device completed unsuccessfully.
+///
+/// Commands of an [`NvmeDevice`] fail with an [`io::Error`] of kind
+/// [`Other`](io::ErrorKind::Other) wrapping the status, which is retrieved
+/// with [`io::Error::get_ref`] and `<dyn std::error::Error>::downcast_ref`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::{NvmeCommand, NvmeDevice, NvmeStatus};
+/// use tokio_uring::Submit;
+///
+/// async fn flush(dev: &NvmeDevice) -> std::io::Result<()> {
+///     match dev.execute(&NvmeCommand::flush(dev.nsid())).submit().await {
+///         Ok(_) => Ok(()),
+///         Err(tokio_uring::Error(e, _)) => {
+///             if let Some(status) = e.get_ref().and_then(|e| e.downcast_ref::<NvmeStatus>()) {
+///                 eprintln!("status code {:#x}", status.code());
+///             }
+///             Err(e)
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NvmeStatus(u16);
+
+impl NvmeStatus {
+    pub(crate) fn new(status: u16) -> NvmeStatus {
+        NvmeStatus(status)
+    }
+
+    /// Returns the raw status field, as reported by the driver.
+    pub fn raw(&self) -> u16 {
+        self.0
+    }
+
+    /// Returns the status code.
+    pub fn code(&self) -> u8 {
+        self.0 as u8
+    }
+
+    /// Returns the status code type, such as 0 for generic command status
+    /// and 1 for command specific status.
+    pub fn code_type(&self) -> u8 {
+        ((self.0 >> 8) & 0x7) as u8
+    }
+
+    /// Returns `true` if the device reported that retrying the command is
+    /// expected to fail too.
+    pub fn do_not_retry(&self) -> bool {
+        self.0 & (1 << 14) != 0
+    }
+}
+
+impl fmt::Display for NvmeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "NVMe command failed with status code {:#x} of type {:#x}",
+            self.code(),
+            self.code_type()
+        )
+    }
+}
+
+impl Error for NvmeStatus {}
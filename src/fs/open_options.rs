@@ -1,4 +1,5 @@
 use crate::fs::File;
+use crate::runtime::Personality;
 
 use crate::runtime::driver::op::Op;
 use std::io;
@@ -64,6 +65,7 @@ pub struct OpenOptions {
     create_new: bool,
     pub(crate) mode: libc::mode_t,
     pub(crate) custom_flags: libc::c_int,
+    pub(crate) personality: Option<Personality>,
 }
 
 impl OpenOptions {
@@ -97,6 +99,7 @@ impl OpenOptions {
             create_new: false,
             mode: 0o666,
             custom_flags: 0,
+            personality: None,
         }
     }
 
@@ -282,6 +285,92 @@ impl OpenOptions {
         self
     }
 
+    /// Sets the [`Personality`] whose credentials are used to open the file.
+    ///
+    /// By default, the file is opened with the credentials of the process at
+    /// the time the open is submitted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::OpenOptions;
+    /// use tokio_uring::runtime::Personality;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let personality = Personality::register()?;
+    ///         let file = OpenOptions::new()
+    ///             .read(true)
+    ///             .personality(personality)
+    ///             .open("foo.txt")
+    ///             .await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub fn personality(&mut self, personality: Personality) -> &mut OpenOptions {
+        self.personality = Some(personality);
+        self
+    }
+
+    /// Sets the mode bits a new file is created with, before the process
+    /// umask applies.
+    ///
+    /// The default is `0o666`. This is the same as
+    /// [`OpenOptionsExt::mode`], without having to import the trait.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::OpenOptions;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let file = OpenOptions::new()
+    ///             .write(true)
+    ///             .create(true)
+    ///             .mode(0o600)
+    ///             .open("secret.txt")
+    ///             .await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub fn mode(&mut self, mode: u32) -> &mut OpenOptions {
+        self.mode = mode;
+        self
+    }
+
+    /// Passes custom flags to the `flags` argument of `open(2)`, such as
+    /// `O_DIRECT`, `O_NOATIME` or `O_SYNC`.
+    ///
+    /// The access mode bits (`O_ACCMODE`) are ignored, they are set from
+    /// [`read`](OpenOptions::read), [`write`](OpenOptions::write) and
+    /// [`append`](OpenOptions::append). `O_CLOEXEC` is always set. This is
+    /// the same as [`OpenOptionsExt::custom_flags`], without having to import
+    /// the trait.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::OpenOptions;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let file = OpenOptions::new()
+    ///             .read(true)
+    ///             .custom_flags(libc::O_DIRECT | libc::O_NOATIME)
+    ///             .open("data.db")
+    ///             .await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub fn custom_flags(&mut self, flags: i32) -> &mut OpenOptions {
+        self.custom_flags = flags;
+        self
+    }
+
     /// Opens a file at `path` with the options specified by `self`.
     ///
     /// # Errors
@@ -333,7 +422,58 @@ impl OpenOptions {
     /// [`Other`]: io::ErrorKind::Other
     /// [`PermissionDenied`]: io::ErrorKind::PermissionDenied
     pub async fn open(&self, path: impl AsRef<Path>) -> io::Result<File> {
-        Op::open(path.as_ref(), self)?.await
+        Op::open(None, path.as_ref(), self)?.await
+    }
+
+    /// Opens a file at `path` relative to the directory `dir`, with the
+    /// options specified by `self`, using `openat2(2)`.
+    ///
+    /// `resolve` restricts how the path is resolved, as a combination of the
+    /// `RESOLVE_*` flags, e.g. `libc::RESOLVE_BENEATH` to fail the open if
+    /// resolving `path` would leave the tree below `dir`, or
+    /// `libc::RESOLVE_NO_SYMLINKS` to fail it on any symbolic link. The
+    /// restrictions are enforced by the kernel as part of the open, so unlike
+    /// checking the path beforehand, they can't be raced by renames. An
+    /// absolute `path` ignores `dir`, unless restricted from it.
+    ///
+    /// Requires Linux 5.6 or later.
+    ///
+    /// # Errors
+    ///
+    /// Besides the errors of [`open`](OpenOptions::open), the open fails with
+    /// `EXDEV` if `path` escapes `dir` against `RESOLVE_BENEATH` or
+    /// `RESOLVE_IN_ROOT`, and with `ELOOP` if it has a symbolic link against
+    /// `RESOLVE_NO_SYMLINKS`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::OpenOptions;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let root = OpenOptions::new()
+    ///             .read(true)
+    ///             .custom_flags(libc::O_DIRECTORY)
+    ///             .open("/srv/www")
+    ///             .await?;
+    ///
+    ///         // Requests for "../../etc/passwd" fail with EXDEV.
+    ///         let file = OpenOptions::new()
+    ///             .read(true)
+    ///             .open_at2(&root, "index.html", libc::RESOLVE_BENEATH)
+    ///             .await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub async fn open_at2(
+        &self,
+        dir: &File,
+        path: impl AsRef<Path>,
+        resolve: u64,
+    ) -> io::Result<File> {
+        Op::open_at2(&dir.fd, path.as_ref(), self, resolve)?.await
     }
 
     pub(crate) fn access_mode(&self) -> io::Result<libc::c_int> {
@@ -380,12 +520,10 @@ impl Default for OpenOptions {
 
 impl OpenOptionsExt for OpenOptions {
     fn mode(&mut self, mode: u32) -> &mut OpenOptions {
-        self.mode = mode;
-        self
+        OpenOptions::mode(self, mode)
     }
 
     fn custom_flags(&mut self, flags: i32) -> &mut OpenOptions {
-        self.custom_flags = flags;
-        self
+        OpenOptions::custom_flags(self, flags)
     }
 }
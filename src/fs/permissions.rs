@@ -0,0 +1,113 @@
+use super::File;
+use crate::io::cstr;
+use std::fs::Permissions;
+use std::io;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+use std::path::Path;
+
+impl File {
+    /// Changes the permissions of the underlying file.
+    ///
+    /// `io_uring` has no operation for this, so `fchmod(2)` is called on
+    /// Tokio's blocking thread pool.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::Permissions;
+    /// use std::os::unix::fs::PermissionsExt;
+    /// use tokio_uring::fs::File;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let f = File::open("foo.sh").await?;
+    ///         f.set_permissions(Permissions::from_mode(0o755)).await?;
+    ///         f.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub async fn set_permissions(&self, perm: Permissions) -> io::Result<()> {
+        let mode = perm.mode();
+        self.blocking(move |fd| syscall!(fchmod(fd.as_raw_fd(), mode as libc::mode_t)))
+            .await
+    }
+
+    /// Changes the owner and group of the underlying file.
+    ///
+    /// An id of `None` is left unchanged. Changing the owner requires
+    /// `CAP_CHOWN`; an unprivileged owner may only change the group to one
+    /// they belong to. Like [`set_permissions`](File::set_permissions), this
+    /// runs `fchown(2)` on Tokio's blocking thread pool.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let f = File::open("foo.txt").await?;
+    ///         f.set_owner(Some(1000), Some(1000)).await?;
+    ///         f.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub async fn set_owner(&self, uid: Option<u32>, gid: Option<u32>) -> io::Result<()> {
+        // -1 leaves the id unchanged.
+        let uid = uid.unwrap_or(u32::MAX);
+        let gid = gid.unwrap_or(u32::MAX);
+        self.blocking(move |fd| syscall!(fchown(fd.as_raw_fd(), uid, gid)))
+            .await
+    }
+
+    // Runs `f` on the blocking thread pool, with a descriptor of its own for
+    // the file so that it may be closed in the meantime.
+    async fn blocking<F>(&self, f: F) -> io::Result<()>
+    where
+        F: FnOnce(OwnedFd) -> io::Result<libc::c_int> + Send + 'static,
+    {
+        let fd = syscall!(fcntl(self.fd.raw_fd(), libc::F_DUPFD_CLOEXEC, 0))?;
+        // Safety: the descriptor was just duplicated, and is owned here.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        tokio::task::spawn_blocking(move || f(fd).map(|_| ()))
+            .await
+            .map_err(io::Error::other)?
+    }
+}
+
+/// Changes the permissions of the file at `path`, following symbolic links.
+///
+/// `io_uring` has no operation for this, so `chmod(2)` is called on Tokio's
+/// blocking thread pool, where resolving `path` may block.
+///
+/// # Errors
+///
+/// Fails if `path` doesn't exist, or if the user doesn't own the file.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::fs::Permissions;
+/// use std::os::unix::fs::PermissionsExt;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         tokio_uring::fs::set_permissions("foo.sh", Permissions::from_mode(0o755)).await?;
+///         Ok(())
+///     })
+/// }
+/// ```
+pub async fn set_permissions(path: impl AsRef<Path>, perm: Permissions) -> io::Result<()> {
+    let path = cstr(path.as_ref())?;
+    let mode = perm.mode();
+    tokio::task::spawn_blocking(move || {
+        syscall!(chmod(path.as_ptr(), mode as libc::mode_t))?;
+        Ok(())
+    })
+    .await
+    .map_err(io::Error::other)?
+}
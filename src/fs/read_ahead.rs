@@ -0,0 +1,244 @@
+use crate::buf::BoundedBufMut;
+use crate::fs::File;
+use crate::io::read::{ReadData, ReadTransform};
+use crate::{InFlightOneshot, Submit};
+use std::collections::VecDeque;
+
+type InFlightRead = InFlightOneshot<ReadData<Vec<u8>>, ReadTransform<Vec<u8>>>;
+
+/// A sequential read-ahead wrapper around a [`File`].
+///
+/// `ReadAhead` watches the offsets passed to [`read_at`] and, once it detects
+/// that the caller is reading the file sequentially, keeps up to `depth`
+/// reads of `chunk_size` bytes in flight ahead of the caller. Subsequent reads
+/// are then served from the prefetched chunks, hiding the device latency from
+/// scan-heavy workloads.
+///
+/// A read at any other offset drops the prefetched chunks and is submitted
+/// directly to the file.
+///
+/// The chunks are read into `Vec<u8>` buffers from a pool owned by the
+/// wrapper, which are recycled between reads. Registered buffers, such as
+/// those of a [`FixedBufPool`], are not supported: the chunks are always read
+/// with regular reads.
+///
+/// [`read_at`]: ReadAhead::read_at
+/// [`FixedBufPool`]: crate::buf::fixed::FixedBufPool
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::{File, ReadAhead};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         let file = File::open("app.log").await?;
+///         let mut reader = ReadAhead::new(file, 128 * 1024, 4);
+///
+///         let mut pos = 0;
+///         loop {
+///             let (n, buf) = reader.read_at(vec![0; 4096], pos).await?;
+///             if n == 0 {
+///                 break;
+///             }
+///             println!("{:?}", &buf[..n]);
+///             pos += n as u64;
+///         }
+///
+///         reader.into_inner().close().await?;
+///         Ok(())
+///     })
+/// }
+/// ```
+pub struct ReadAhead {
+    file: File,
+
+    /// Size of each prefetched read.
+    chunk_size: usize,
+
+    /// Maximum number of prefetched reads in flight.
+    depth: usize,
+
+    /// Offset at which the next sequential read is expected.
+    expected_pos: u64,
+
+    /// Number of consecutive sequential reads observed.
+    sequential: usize,
+
+    /// Offset of the next chunk to submit.
+    submit_pos: u64,
+
+    /// Set once a chunk came back short, no chunks are submitted past it.
+    eof: bool,
+
+    /// Prefetched chunks, ordered by offset.
+    chunks: VecDeque<Chunk>,
+
+    /// Buffers of consumed chunks, ready for reuse.
+    free: Vec<Vec<u8>>,
+}
+
+struct Chunk {
+    pos: u64,
+    state: ChunkState,
+}
+
+enum ChunkState {
+    InFlight(InFlightRead),
+    Ready(Vec<u8>),
+}
+
+impl ReadAhead {
+    /// Wraps `file`, prefetching up to `depth` chunks of `chunk_size` bytes
+    /// once sequential access is detected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` or `depth` is zero.
+    pub fn new(file: File, chunk_size: usize, depth: usize) -> ReadAhead {
+        assert!(chunk_size > 0, "chunk_size must be greater than zero");
+        assert!(depth > 0, "depth must be greater than zero");
+
+        ReadAhead {
+            file,
+            chunk_size,
+            depth,
+            expected_pos: 0,
+            sequential: 0,
+            submit_pos: 0,
+            eof: false,
+            chunks: VecDeque::with_capacity(depth),
+            free: Vec::with_capacity(depth),
+        }
+    }
+
+    /// Returns a reference to the underlying file.
+    pub fn get_ref(&self) -> &File {
+        &self.file
+    }
+
+    /// Consumes the `ReadAhead`, returning the underlying file.
+    ///
+    /// Prefetched reads still in flight are abandoned.
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+
+    /// Returns the number of prefetched chunks currently held, whether in
+    /// flight or completed.
+    pub fn prefetched(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Read some bytes at the specified offset from the file into the specified
+    /// buffer, returning how many bytes were read.
+    ///
+    /// This behaves like [`File::read_at`], except that sequential reads are
+    /// served from the prefetched chunks. A single call never returns more
+    /// bytes than are left in the current chunk, so callers must be prepared
+    /// for short reads, as with [`File::read_at`].
+    pub async fn read_at<T: BoundedBufMut>(
+        &mut self,
+        mut buf: T,
+        pos: u64,
+    ) -> crate::Result<usize, T> {
+        if pos == self.expected_pos {
+            self.sequential += 1;
+        } else {
+            self.reset();
+            self.sequential = 1;
+        }
+
+        // Random access, or the start of a potential sequence: read directly
+        // into the caller's buffer. Past the end of the file, keep reading
+        // directly until the file grows.
+        if self.sequential < 2 || self.eof {
+            let (n, buf) = self.file.read_at(buf, pos).submit().await?;
+            self.expected_pos = pos + n as u64;
+            if n > 0 {
+                self.eof = false;
+            }
+            return Ok((n, buf));
+        }
+
+        if self.chunks.is_empty() {
+            self.submit_pos = pos;
+        }
+        self.fill();
+
+        let chunk = self
+            .chunks
+            .front_mut()
+            .expect("at least one chunk in flight");
+
+        if let ChunkState::InFlight(read) = &mut chunk.state {
+            match read.await {
+                Ok((_, data)) => chunk.state = ChunkState::Ready(data),
+                Err(crate::Error(e, _)) => {
+                    self.reset();
+                    return Err(crate::Error(e, buf));
+                }
+            }
+        }
+
+        let ChunkState::Ready(data) = &chunk.state else {
+            unreachable!()
+        };
+
+        let start = (pos - chunk.pos) as usize;
+        let n = data.len().saturating_sub(start).min(buf.bytes_total());
+        buf.put_slice(&data[start..start + n]);
+
+        if start + n >= data.len() {
+            // A short chunk marks the end of the file, chunks behind it are
+            // empty.
+            let eof = data.len() < self.chunk_size;
+
+            if let Some(Chunk {
+                state: ChunkState::Ready(data),
+                ..
+            }) = self.chunks.pop_front()
+            {
+                self.free.push(data);
+            }
+
+            if eof {
+                self.reset();
+                self.eof = true;
+            } else {
+                self.fill();
+            }
+        }
+
+        self.expected_pos = pos + n as u64;
+        Ok((n, buf))
+    }
+
+    // Submit reads until `depth` chunks are prefetched.
+    fn fill(&mut self) {
+        while !self.eof && self.chunks.len() < self.depth {
+            let mut buf = self
+                .free
+                .pop()
+                .unwrap_or_else(|| Vec::with_capacity(self.chunk_size));
+            buf.clear();
+
+            let read = self.file.read_at(buf, self.submit_pos).submit();
+            self.chunks.push_back(Chunk {
+                pos: self.submit_pos,
+                state: ChunkState::InFlight(read),
+            });
+            self.submit_pos += self.chunk_size as u64;
+        }
+    }
+
+    // Drop any prefetched chunks.
+    fn reset(&mut self) {
+        for chunk in self.chunks.drain(..) {
+            if let ChunkState::Ready(data) = chunk.state {
+                self.free.push(data);
+            }
+        }
+        self.eof = false;
+    }
+}
@@ -0,0 +1,261 @@
+use super::{File, OpenOptions};
+use futures_util::Stream;
+use std::collections::VecDeque;
+use std::ffi::{CStr, OsStr, OsString};
+use std::future::Future;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::task::JoinHandle;
+
+// The size of the buffer entries are read in, as `getdents64(2)` fills it.
+const BUF_SIZE: usize = 32 * 1024;
+
+/// Returns the entries of the directory at `path`, as a [`ReadDir`].
+///
+/// The directory is opened through the ring. The ring can't read
+/// directories, so its entries are read with `getdents64(2)` on a blocking
+/// thread, many at a time, and never block the runtime thread.
+///
+/// The entries are returned in the order the filesystem lists them, and do
+/// not include `.` and `..`.
+///
+/// # Errors
+///
+/// Fails if `path` doesn't exist or isn't a directory, or if the user lacks
+/// permissions to read it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         let mut entries = fs::read_dir("/some/dir").await?;
+///         while let Some(entry) = entries.next_entry().await? {
+///             println!("{:?}", entry.file_name());
+///         }
+///         Ok::<(), std::io::Error>(())
+///     })?;
+///     Ok(())
+/// }
+/// ```
+pub async fn read_dir<P: AsRef<Path>>(path: P) -> io::Result<ReadDir> {
+    let path = path.as_ref();
+    let dir = OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_DIRECTORY)
+        .open(path)
+        .await?;
+
+    let read_dir = read_dir_of(&dir, path.to_path_buf())?;
+    dir.close().await?;
+    Ok(read_dir)
+}
+
+// Returns the entries of the open directory `dir`, whose entries are joined
+// to `path`.
+pub(crate) fn read_dir_of(dir: &File, path: PathBuf) -> io::Result<ReadDir> {
+    // The blocking thread reading the entries owns a descriptor of its own.
+    let fd = syscall!(fcntl(dir.fd.raw_fd(), libc::F_DUPFD_CLOEXEC, 0))?;
+    // Safety: the descriptor was just duplicated, and is owned here.
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    Ok(ReadDir {
+        path,
+        entries: VecDeque::new(),
+        state: State::Idle(Some((fd, vec![0; BUF_SIZE]))),
+    })
+}
+
+/// The entries of a directory, as returned by [`read_dir`].
+///
+/// Entries are returned by [`next_entry`](ReadDir::next_entry), or as a
+/// [`Stream`].
+pub struct ReadDir {
+    path: PathBuf,
+    entries: VecDeque<DirEntry>,
+    state: State,
+}
+
+enum State {
+    // The descriptor and the buffer, or `None` once all entries were read.
+    Idle(Option<(OwnedFd, Vec<u8>)>),
+    Reading(JoinHandle<io::Result<Batch>>),
+}
+
+// The entries read at once, and the descriptor and buffer to read the next
+// ones with, or `None` if none are left.
+type Batch = (Vec<(OsString, u64, u8)>, Option<(OwnedFd, Vec<u8>)>);
+
+impl ReadDir {
+    /// Returns the next entry of the directory, or `None` once all entries
+    /// were returned.
+    pub async fn next_entry(&mut self) -> io::Result<Option<DirEntry>> {
+        std::future::poll_fn(|cx| self.poll_next_entry(cx)).await
+    }
+
+    /// Polls for the next entry of the directory, or `None` once all entries
+    /// were returned.
+    pub fn poll_next_entry(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Option<DirEntry>>> {
+        loop {
+            if let Some(entry) = self.entries.pop_front() {
+                return Poll::Ready(Ok(Some(entry)));
+            }
+
+            match &mut self.state {
+                State::Idle(next) => match next.take() {
+                    Some((fd, buf)) => {
+                        let read = tokio::task::spawn_blocking(move || read_batch(fd, buf));
+                        self.state = State::Reading(read);
+                    }
+                    None => return Poll::Ready(Ok(None)),
+                },
+                State::Reading(read) => {
+                    let res = ready!(Pin::new(read).poll(cx)).map_err(io::Error::other);
+                    self.state = State::Idle(None);
+                    let (batch, next) = res??;
+
+                    let path = &self.path;
+                    self.entries
+                        .extend(batch.into_iter().map(|(name, ino, d_type)| DirEntry {
+                            path: path.join(&name),
+                            name,
+                            ino,
+                            d_type,
+                        }));
+                    self.state = State::Idle(next);
+                }
+            }
+        }
+    }
+}
+
+impl Stream for ReadDir {
+    type Item = io::Result<DirEntry>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_next_entry(cx).map(Result::transpose)
+    }
+}
+
+impl std::fmt::Debug for ReadDir {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadDir").field("path", &self.path).finish()
+    }
+}
+
+/// An entry of a directory, as returned by [`ReadDir`].
+#[derive(Clone, Debug)]
+pub struct DirEntry {
+    path: PathBuf,
+    name: OsString,
+    ino: u64,
+    d_type: u8,
+}
+
+impl DirEntry {
+    /// Returns the full path of the entry, the path the directory was read
+    /// with joined with the name of the entry.
+    pub fn path(&self) -> PathBuf {
+        self.path.clone()
+    }
+
+    /// Returns the name of the entry, without any leading path.
+    pub fn file_name(&self) -> OsString {
+        self.name.clone()
+    }
+
+    /// Returns the inode number of the entry.
+    pub fn ino(&self) -> u64 {
+        self.ino
+    }
+
+    /// Returns the type of the entry, without following symbolic links.
+    ///
+    /// The type is read along with the entry on most filesystems. On those
+    /// which don't report it, it is fetched with `statx(2)` through the ring.
+    pub async fn file_type(&self) -> io::Result<FileType> {
+        let mode = match self.d_type {
+            libc::DT_REG => libc::S_IFREG,
+            libc::DT_DIR => libc::S_IFDIR,
+            libc::DT_LNK => libc::S_IFLNK,
+            libc::DT_FIFO => libc::S_IFIFO,
+            libc::DT_SOCK => libc::S_IFSOCK,
+            libc::DT_CHR => libc::S_IFCHR,
+            libc::DT_BLK => libc::S_IFBLK,
+            _ => super::symlink_metadata(&self.path).await?.mode() & libc::S_IFMT,
+        };
+        Ok(FileType { mode })
+    }
+}
+
+/// The type of a [`DirEntry`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileType {
+    mode: u32,
+}
+
+impl FileType {
+    /// Returns `true` if the entry is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.mode == libc::S_IFDIR
+    }
+
+    /// Returns `true` if the entry is a regular file.
+    pub fn is_file(&self) -> bool {
+        self.mode == libc::S_IFREG
+    }
+
+    /// Returns `true` if the entry is a symbolic link.
+    pub fn is_symlink(&self) -> bool {
+        self.mode == libc::S_IFLNK
+    }
+}
+
+// Reads the next entries of the directory with `getdents64(2)`.
+fn read_batch(fd: OwnedFd, mut buf: Vec<u8>) -> io::Result<Batch> {
+    let n = loop {
+        let res = syscall!(syscall(
+            libc::SYS_getdents64,
+            fd.as_raw_fd(),
+            buf.as_mut_ptr(),
+            buf.len()
+        ));
+        match res {
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            res => break res? as usize,
+        }
+    };
+    if n == 0 {
+        return Ok((Vec::new(), None));
+    }
+
+    let mut batch = Vec::new();
+    let mut offset = 0;
+    while offset < n {
+        // Safety: the kernel filled `buf` up to `n` with `linux_dirent64`
+        // records, which may not be aligned within it.
+        let record = buf[offset..].as_ptr();
+        let (ino, reclen, d_type) = unsafe {
+            (
+                std::ptr::read_unaligned(record as *const u64),
+                std::ptr::read_unaligned(record.add(16) as *const u16),
+                *record.add(18),
+            )
+        };
+        // The name is NUL-terminated, and padded up to `reclen`.
+        let name = CStr::from_bytes_until_nul(&buf[offset + 19..offset + reclen as usize])
+            .map_err(io::Error::other)?
+            .to_bytes();
+        if name != b"." && name != b".." {
+            batch.push((OsStr::from_bytes(name).to_os_string(), ino, d_type));
+        }
+        offset += reclen as usize;
+    }
+    Ok((batch, Some((fd, buf))))
+}
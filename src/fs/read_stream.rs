@@ -0,0 +1,163 @@
+use crate::fs::File;
+use crate::io::read::{ReadData, ReadTransform};
+use crate::{InFlightOneshot, Submit};
+use futures_util::Stream;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+type InFlightRead = InFlightOneshot<ReadData<Vec<u8>>, ReadTransform<Vec<u8>>>;
+
+// The number of reads kept in flight by default.
+const DEFAULT_DEPTH: usize = 4;
+
+impl File {
+    /// Reads the file from the start in chunks of `chunk_size` bytes, as a
+    /// [`Stream`] of buffers.
+    ///
+    /// The stream keeps several reads in flight ahead of the chunk returned,
+    /// four unless set with [`ReadStream::depth`], so that the device is
+    /// kept busy while the caller processes the data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use tokio_uring::fs::File;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let file = File::open("data.bin").await?;
+    ///
+    ///         let mut chunks = file.read_stream(1024 * 1024).depth(8);
+    ///         let mut total = 0;
+    ///         while let Some(chunk) = chunks.next().await {
+    ///             total += chunk?.len();
+    ///         }
+    ///         println!("{} bytes", total);
+    ///
+    ///         drop(chunks);
+    ///         file.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub fn read_stream(&self, chunk_size: usize) -> ReadStream<'_> {
+        assert!(chunk_size > 0, "chunk_size must be greater than zero");
+
+        ReadStream {
+            file: self,
+            chunk_size,
+            depth: DEFAULT_DEPTH,
+            submit_pos: 0,
+            eof: false,
+            reads: VecDeque::with_capacity(DEFAULT_DEPTH),
+        }
+    }
+}
+
+/// The chunks of a file, as returned by [`File::read_stream`].
+///
+/// Each chunk is a buffer of `chunk_size` bytes, except the last one. The
+/// stream ends at the first chunk read short, which marks the end of the
+/// file, or after the first error.
+pub struct ReadStream<'a> {
+    file: &'a File,
+
+    chunk_size: usize,
+
+    /// Maximum number of reads in flight.
+    depth: usize,
+
+    /// Offset of the next chunk to submit.
+    submit_pos: u64,
+
+    /// Set once the end of the file or an error was reached.
+    eof: bool,
+
+    /// Reads in flight, ordered by offset.
+    reads: VecDeque<InFlightRead>,
+}
+
+impl ReadStream<'_> {
+    /// Sets the number of reads kept in flight.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `depth` is zero.
+    pub fn depth(mut self, depth: usize) -> Self {
+        assert!(depth > 0, "depth must be greater than zero");
+        self.depth = depth;
+        self
+    }
+
+    // Submit reads until `depth` are in flight.
+    fn fill(&mut self) {
+        while !self.eof && self.reads.len() < self.depth {
+            let buf = Vec::with_capacity(self.chunk_size);
+            let read = self.file.read_at(buf, self.submit_pos).submit();
+            self.reads.push_back(read);
+            self.submit_pos += self.chunk_size as u64;
+        }
+    }
+
+    // Drop the reads in flight, ending the stream.
+    fn finish(&mut self) {
+        self.eof = true;
+        self.reads.clear();
+    }
+}
+
+impl Stream for ReadStream<'_> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        this.fill();
+
+        let read = match this.reads.front_mut() {
+            Some(read) => read,
+            None => return Poll::Ready(None),
+        };
+        let res = ready!(Pin::new(read).poll(cx));
+        this.reads.pop_front();
+
+        match res {
+            Ok((0, _)) => {
+                this.finish();
+                Poll::Ready(None)
+            }
+            Ok((n, data)) => {
+                if n < this.chunk_size {
+                    // The chunks behind a short one are empty.
+                    this.finish();
+                } else {
+                    this.fill();
+                }
+                Poll::Ready(Some(Ok(data)))
+            }
+            Err(crate::Error(e, _)) => {
+                this.finish();
+                Poll::Ready(Some(Err(e)))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ReadStream<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadStream")
+            .field("file", self.file)
+            .field("chunk_size", &self.chunk_size)
+            .field("depth", &self.depth)
+            .field("in_flight", &self.reads.len())
+            .finish()
+    }
+}
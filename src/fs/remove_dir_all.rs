@@ -0,0 +1,142 @@
+use super::read_dir::read_dir_of;
+use super::Dir;
+use crate::runtime::driver::op::Op;
+use crate::runtime::op_permit;
+use futures_util::future::{self, LocalBoxFuture};
+use futures_util::{StreamExt, TryStreamExt};
+use std::ffi::OsString;
+use std::io;
+use std::path::Path;
+
+// The number of entries of a directory unlinked at once.
+const CONCURRENCY: usize = 64;
+
+/// Removes a directory at this path, after removing all its contents.
+///
+/// Symbolic links are not followed: a link within the directory is removed
+/// rather than what it points to, and a `path` which is a link is removed
+/// itself.
+///
+/// Directories are opened with `O_NOFOLLOW`, and their entries removed
+/// relative to the open directory, so that replacing a directory with a link
+/// meanwhile can't redirect the removal elsewhere. The entries of each
+/// directory are listed, and then unlinked through the ring, up to 64 at
+/// once. Subdirectories are removed one at a time, keeping a single
+/// directory open per level of the tree. Operations are submitted under the
+/// cap on operations in flight of the runtime, if any, see
+/// [`Builder::max_in_flight`](crate::Builder::max_in_flight).
+///
+/// # Errors
+///
+/// Fails if `path` doesn't exist, or if any entry can't be removed. Entries
+/// which disappear meanwhile, e.g. removed by another process, are not an
+/// error. The removal stops at the first error, leaving the entries not
+/// removed yet in place.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::remove_dir_all;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         remove_dir_all("/some/dir").await?;
+///         Ok::<(), std::io::Error>(())
+///     })?;
+///     Ok(())
+/// }
+/// ```
+pub async fn remove_dir_all<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    let path = path.as_ref();
+    let permit = op_permit().await;
+    if permit
+        .run(super::symlink_metadata(path))
+        .await?
+        .is_symlink()
+    {
+        return unlink(path, 0).await;
+    }
+
+    let dir = op_permit()
+        .await
+        .run(Dir::open_nofollow(None, path))
+        .await?;
+    let res = remove_contents(&dir, path).await;
+    op_permit().await.run(dir.close()).await?;
+    res?;
+
+    unlink(path, libc::AT_REMOVEDIR).await
+}
+
+// A recursive async function requires a boxed future, as in `create_dir_all`.
+fn remove_contents<'a>(dir: &'a Dir, path: &'a Path) -> LocalBoxFuture<'a, io::Result<()>> {
+    Box::pin(async move {
+        let names = list(dir, path).await?;
+
+        // Unlink the entries, keeping aside those which are directories.
+        let subdirs: Vec<OsString> = futures_util::stream::iter(names)
+            .map(|name| async move {
+                match op_permit().await.run(dir.unlink_at(&name)).await {
+                    Ok(()) => Ok(None),
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+                    Err(e) if e.raw_os_error() == Some(libc::EISDIR) => Ok(Some(name)),
+                    Err(e) => Err(e),
+                }
+            })
+            .buffer_unordered(CONCURRENCY)
+            .try_filter_map(future::ok)
+            .try_collect()
+            .await?;
+
+        for name in subdirs {
+            remove_subdir(dir, path, name).await?;
+        }
+        Ok(())
+    })
+}
+
+// Removes the directory `name` of `dir`, after removing all its contents.
+async fn remove_subdir(dir: &Dir, path: &Path, name: OsString) -> io::Result<()> {
+    let subdir = match op_permit()
+        .await
+        .run(Dir::open_nofollow(Some(dir), name.as_ref()))
+        .await
+    {
+        Ok(subdir) => subdir,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        // Replaced by something else than a directory since, e.g. a link.
+        Err(e) if matches!(e.raw_os_error(), Some(libc::ELOOP) | Some(libc::ENOTDIR)) => {
+            return match op_permit().await.run(dir.unlink_at(&name)).await {
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+                res => res,
+            };
+        }
+        Err(e) => return Err(e),
+    };
+
+    let res = remove_contents(&subdir, &path.join(&name)).await;
+    op_permit().await.run(subdir.close()).await?;
+    res?;
+
+    match op_permit().await.run(dir.remove_dir_at(&name)).await {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        res => res,
+    }
+}
+
+async fn unlink(path: &Path, flags: i32) -> io::Result<()> {
+    let permit = op_permit().await;
+    permit
+        .run(async { Op::unlink(None, path, flags)?.await })
+        .await
+}
+
+// Returns the names of the entries of `dir`, whose path is `path`.
+async fn list(dir: &Dir, path: &Path) -> io::Result<Vec<OsString>> {
+    let mut read_dir = read_dir_of(dir.as_file(), path.to_path_buf())?;
+    let mut names = Vec::new();
+    while let Some(entry) = read_dir.next_entry().await? {
+        names.push(entry.file_name());
+    }
+    Ok(names)
+}
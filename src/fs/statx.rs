@@ -76,6 +76,39 @@ impl File {
             mask: libc::STATX_ALL,
         }
     }
+
+    /// Returns the alignment `O_DIRECT` I/O on the file requires, as the
+    /// alignment of the memory buffers and that of the offsets and lengths,
+    /// in bytes.
+    ///
+    /// Returns `None` if the file does not support direct I/O, or if the
+    /// kernel or filesystem does not report the alignment (`STATX_DIOALIGN`,
+    /// Linux 6.1 or later).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    ///
+    /// tokio_uring::start(async {
+    ///     let f = File::open("foo.txt").await.unwrap();
+    ///
+    ///     if let Some((mem_align, offset_align)) = f.dio_alignment().await.unwrap() {
+    ///         println!("buffers aligned to {}, offsets to {}", mem_align, offset_align);
+    ///     }
+    /// })
+    /// ```
+    pub async fn dio_alignment(&self) -> io::Result<Option<(u32, u32)>> {
+        let statx = self
+            .statx_builder()
+            .mask(libc::STATX_DIOALIGN)
+            .statx()
+            .await?;
+        if statx.stx_mask & libc::STATX_DIOALIGN == 0 || statx.stx_dio_mem_align == 0 {
+            return Ok(None);
+        }
+        Ok(Some((statx.stx_dio_mem_align, statx.stx_dio_offset_align)))
+    }
 }
 
 /// Returns statx(2) metadata for a path via a uring call.
@@ -89,7 +122,7 @@ impl File {
 /// when an open file descriptor isn't necessary for other reasons anyway.
 ///
 /// The path can be absolute or relative. A relative path is interpreted against the current
-/// working direcgtory.
+/// working directory.
 ///
 /// More specific uring statx(2) calls can be made with the StatxBuilder.
 ///
@@ -106,6 +139,40 @@ pub async fn statx<P: AsRef<Path>>(path: P) -> io::Result<libc::statx> {
     StatxBuilder::new().pathname(path).unwrap().statx().await
 }
 
+/// Returns statx(2) metadata for a path relative to the directory `dir` via a uring call.
+///
+/// This is [`statx`] with a relative path interpreted against `dir` rather than the current
+/// working directory, which suits checking many files of one directory without opening them.
+/// An absolute path ignores `dir`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::{self, OpenOptions};
+///
+/// tokio_uring::start(async {
+///     let dir = OpenOptions::new()
+///         .read(true)
+///         .custom_flags(libc::O_DIRECTORY)
+///         .open("/var/log")
+///         .await.unwrap();
+///
+///     for name in ["syslog", "messages"] {
+///         if let Ok(statx) = fs::statx_at(&dir, name).await {
+///             println!("{}: {} bytes", name, statx.stx_size);
+///         }
+///     }
+/// })
+/// ```
+pub async fn statx_at<P: AsRef<Path>>(dir: &File, path: P) -> io::Result<libc::statx> {
+    StatxBuilder::new()
+        .dirfd(dir)
+        .flags(0)
+        .pathname(path)?
+        .statx()
+        .await
+}
+
 /// A builder used to make a uring statx(2) call.
 ///
 /// This builder supports the `flags` and `mask` options and can be finished with a call to
@@ -246,8 +313,91 @@ impl StatxBuilder {
         self
     }
 
+    /// Sets or clears `AT_SYMLINK_NOFOLLOW` in the `flags` option.
+    ///
+    /// When set and the path names a symbolic link, the metadata of the link
+    /// itself is returned rather than that of the file it refers to.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// tokio_uring::start(async {
+    ///     // Fetch the metadata of the link, not its target
+    ///     let statx = tokio_uring::fs::StatxBuilder::new()
+    ///         .symlink_nofollow(true)
+    ///         .pathname("link").unwrap()
+    ///         .statx().await.unwrap();
+    /// })
+    /// ```
+    pub fn symlink_nofollow(&mut self, nofollow: bool) -> &mut Self {
+        self.set_flag(libc::AT_SYMLINK_NOFOLLOW, nofollow)
+    }
+
+    /// Sets or clears `AT_EMPTY_PATH` in the `flags` option.
+    ///
+    /// When set and no path is provided, the metadata of the `dirfd` itself is
+    /// returned. This is set by default.
+    pub fn empty_path(&mut self, empty_path: bool) -> &mut Self {
+        self.set_flag(libc::AT_EMPTY_PATH, empty_path)
+    }
+
+    /// Sets or clears `AT_STATX_DONT_SYNC` in the `flags` option.
+    ///
+    /// When set, the kernel returns whatever it has cached locally instead of
+    /// synchronizing the attributes with the server first. This only makes a
+    /// difference on network filesystems, where it avoids a round trip per
+    /// call at the cost of possibly stale values. Setting this clears
+    /// `AT_STATX_FORCE_SYNC`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// tokio_uring::start(async {
+    ///     // Fetch only the size, without syncing with the server
+    ///     let statx = tokio_uring::fs::StatxBuilder::new()
+    ///         .mask(libc::STATX_SIZE)
+    ///         .dont_sync(true)
+    ///         .pathname("foo.txt").unwrap()
+    ///         .statx().await.unwrap();
+    /// })
+    /// ```
+    pub fn dont_sync(&mut self, dont_sync: bool) -> &mut Self {
+        if dont_sync {
+            self.set_flag(libc::AT_STATX_FORCE_SYNC, false);
+        }
+        self.set_flag(libc::AT_STATX_DONT_SYNC, dont_sync)
+    }
+
+    /// Sets or clears `AT_STATX_FORCE_SYNC` in the `flags` option.
+    ///
+    /// When set, the kernel synchronizes the attributes with the server before
+    /// returning them. Setting this clears `AT_STATX_DONT_SYNC`.
+    pub fn force_sync(&mut self, force_sync: bool) -> &mut Self {
+        if force_sync {
+            self.set_flag(libc::AT_STATX_DONT_SYNC, false);
+        }
+        self.set_flag(libc::AT_STATX_FORCE_SYNC, force_sync)
+    }
+
+    fn set_flag(&mut self, flag: i32, set: bool) -> &mut Self {
+        if set {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+        self
+    }
+
     /// Sets the `mask` option, replacing the default.
     ///
+    /// The kernel may return more fields than requested, and may not be able to
+    /// return all of them. The `stx_mask` field of the returned structure
+    /// reports which fields were actually filled in.
+    ///
+    /// The default, `libc::STATX_ALL`, leaves out the fields added after it,
+    /// such as the mount ID (`libc::STATX_MNT_ID`) or the alignment of direct
+    /// I/O (`libc::STATX_DIOALIGN`), which must be requested explicitly.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -0,0 +1,206 @@
+use crate::fs::File;
+use crate::io::SharedFd;
+use crate::runtime::driver::op::Op;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::io;
+use std::rc::Rc;
+use tokio::sync::Notify;
+
+/// Coalesces concurrent `fsync` requests on a file.
+///
+/// Databases and logs with many concurrent committers typically issue one
+/// `fsync` per commit, although a single `fsync` makes every write that
+/// completed before it durable. A `SyncCoalescer` implements group commit:
+/// while a sync is in flight, callers of [`sync_all`] and [`sync_data`] are
+/// batched, and the whole batch is served by a single sync submitted as soon
+/// as the in-flight one completes.
+///
+/// Every caller still gets the guarantee of a dedicated sync: writes which
+/// completed before the call are durable once it returns.
+///
+/// Cloning a `SyncCoalescer` is cheap, and clones share the same batches.
+///
+/// [`sync_all`]: SyncCoalescer::sync_all
+/// [`sync_data`]: SyncCoalescer::sync_data
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::{File, SyncCoalescer};
+/// use tokio_uring::Submit;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         let log = std::rc::Rc::new(File::create("wal.log").await?);
+///         let syncer = SyncCoalescer::new(&log);
+///
+///         let mut commits = Vec::new();
+///         for i in 0..16u64 {
+///             let (log, syncer) = (log.clone(), syncer.clone());
+///             commits.push(tokio_uring::spawn(async move {
+///                 log.write_all_at(vec![b'x'; 512], i * 512).await.unwrap();
+///                 syncer.sync_data().await.unwrap();
+///             }));
+///         }
+///         for commit in commits {
+///             commit.await?;
+///         }
+///
+///         println!("{} syncs for 16 commits", syncer.syncs());
+///         Ok(())
+///     })
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SyncCoalescer {
+    shared: Rc<Shared>,
+}
+
+struct Shared {
+    fd: SharedFd,
+
+    /// The in-flight sync and the batch queued behind it.
+    state: RefCell<State>,
+
+    /// Number of syncs submitted.
+    syncs: Cell<u64>,
+}
+
+#[derive(Default)]
+struct State {
+    in_flight: Option<Rc<Batch>>,
+    queued: Option<Rc<Batch>>,
+}
+
+/// Callers served by a single sync.
+struct Batch {
+    /// Whether any caller asked for the metadata to be synced too.
+    all: Cell<bool>,
+
+    result: RefCell<Option<io::Result<()>>>,
+
+    done: Notify,
+}
+
+impl SyncCoalescer {
+    /// Creates a coalescer for syncs of `file`.
+    ///
+    /// The coalescer shares the file descriptor with `file`, so
+    /// [`File::close`] only completes once the coalescer and its clones have
+    /// been dropped. Syncs issued directly on `file` are not coalesced.
+    pub fn new(file: &File) -> SyncCoalescer {
+        SyncCoalescer {
+            shared: Rc::new(Shared {
+                fd: file.fd.clone(),
+                state: RefCell::new(State::default()),
+                syncs: Cell::new(0),
+            }),
+        }
+    }
+
+    /// Attempts to sync all OS-internal metadata to disk, sharing the sync
+    /// with concurrent callers.
+    ///
+    /// See [`File::sync_all`].
+    pub async fn sync_all(&self) -> io::Result<()> {
+        self.sync(true).await
+    }
+
+    /// Attempts to sync file data to disk, sharing the sync with concurrent
+    /// callers.
+    ///
+    /// A caller of `sync_data` may be served by a full sync requested by
+    /// another caller of the same batch. See [`File::sync_data`].
+    pub async fn sync_data(&self) -> io::Result<()> {
+        self.sync(false).await
+    }
+
+    /// Returns the number of syncs submitted to the kernel so far.
+    pub fn syncs(&self) -> u64 {
+        self.shared.syncs.get()
+    }
+
+    async fn sync(&self, all: bool) -> io::Result<()> {
+        let batch = {
+            let mut state = self.shared.state.borrow_mut();
+
+            if state.in_flight.is_none() {
+                let batch = Batch::new(all);
+                state.in_flight = Some(batch.clone());
+                start(self.shared.clone(), batch.clone());
+                batch
+            } else {
+                // The in-flight sync may have started before our writes
+                // completed, join the next batch.
+                let batch = state.queued.get_or_insert_with(|| Batch::new(all));
+                batch.all.set(batch.all.get() | all);
+                batch.clone()
+            }
+        };
+
+        loop {
+            if let Some(res) = &*batch.result.borrow() {
+                return match res {
+                    Ok(()) => Ok(()),
+                    Err(e) => Err(clone_error(e)),
+                };
+            }
+            batch.done.notified().await;
+        }
+    }
+}
+
+impl fmt::Debug for SyncCoalescer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SyncCoalescer")
+            .field("fd", &self.shared.fd.raw_fd())
+            .field("syncs", &self.shared.syncs.get())
+            .finish()
+    }
+}
+
+impl Batch {
+    fn new(all: bool) -> Rc<Batch> {
+        Rc::new(Batch {
+            all: Cell::new(all),
+            result: RefCell::new(None),
+            done: Notify::new(),
+        })
+    }
+}
+
+// Submit the sync for `batch` from a task of its own, so that it completes,
+// and the queued batch gets started, even if the callers are cancelled.
+fn start(shared: Rc<Shared>, batch: Rc<Batch>) {
+    shared.syncs.set(shared.syncs.get() + 1);
+
+    crate::spawn(async move {
+        let res = if batch.all.get() {
+            Op::fsync(&shared.fd)
+        } else {
+            Op::datasync(&shared.fd)
+        };
+        let res = match res {
+            Ok(op) => op.await,
+            Err(e) => Err(e),
+        };
+
+        *batch.result.borrow_mut() = Some(res);
+        batch.done.notify_waiters();
+
+        let mut state = shared.state.borrow_mut();
+        state.in_flight = state.queued.take();
+        if let Some(next) = state.in_flight.clone() {
+            drop(state);
+            start(shared, next);
+        }
+    });
+}
+
+fn clone_error(e: &io::Error) -> io::Error {
+    match e.raw_os_error() {
+        Some(code) => io::Error::from_raw_os_error(code),
+        None => io::Error::new(e.kind(), e.to_string()),
+    }
+}
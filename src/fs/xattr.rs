@@ -0,0 +1,154 @@
+use super::File;
+use crate::io::{cstr, XattrTarget};
+use crate::runtime::driver::op::Op;
+use std::convert::TryInto;
+use std::ffi::{CString, OsStr, OsString};
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+impl File {
+    /// Returns the value of the extended attribute `name` of the file, or
+    /// `None` if the file has no such attribute.
+    ///
+    /// `name` includes its namespace, e.g. `user.mime_type`. Requires Linux
+    /// 5.19 or later.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     tokio_uring::start(async {
+    ///         let f = File::open("foo.txt").await?;
+    ///
+    ///         if let Some(mime_type) = f.get_xattr("user.mime_type").await? {
+    ///             println!("{}", String::from_utf8_lossy(&mime_type));
+    ///         }
+    ///
+    ///         f.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub async fn get_xattr(&self, name: impl AsRef<OsStr>) -> io::Result<Option<Vec<u8>>> {
+        get(XattrTarget::Fd(self.fd.clone()), name.as_ref()).await
+    }
+
+    /// Sets the extended attribute `name` of the file to `value`, creating
+    /// it or replacing its value.
+    ///
+    /// `name` includes its namespace, e.g. `user.mime_type`. Requires Linux
+    /// 5.19 or later.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `EOPNOTSUPP` if the filesystem doesn't support extended
+    /// attributes or the namespace of `name`.
+    pub async fn set_xattr(&self, name: impl AsRef<OsStr>, value: &[u8]) -> io::Result<()> {
+        let name = name_cstr(name.as_ref())?;
+        Op::set_xattr(XattrTarget::Fd(self.fd.clone()), name, value.to_vec(), 0)?.await
+    }
+}
+
+/// Returns the value of the extended attribute `name` of the file at `path`,
+/// or `None` if the file has no such attribute.
+///
+/// Symbolic links are followed. See [`File::get_xattr`].
+pub async fn getxattr(
+    path: impl AsRef<Path>,
+    name: impl AsRef<OsStr>,
+) -> io::Result<Option<Vec<u8>>> {
+    get(XattrTarget::Path(cstr(path.as_ref())?), name.as_ref()).await
+}
+
+/// Sets the extended attribute `name` of the file at `path` to `value`,
+/// creating it or replacing its value.
+///
+/// Symbolic links are followed. See [`File::set_xattr`].
+///
+/// # Examples
+///
+/// ```no_run
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     tokio_uring::start(async {
+///         tokio_uring::fs::setxattr("foo.txt", "user.mime_type", b"text/plain").await?;
+///         Ok::<(), std::io::Error>(())
+///     })?;
+///     Ok(())
+/// }
+/// ```
+pub async fn setxattr(
+    path: impl AsRef<Path>,
+    name: impl AsRef<OsStr>,
+    value: &[u8],
+) -> io::Result<()> {
+    let target = XattrTarget::Path(cstr(path.as_ref())?);
+    let name = name_cstr(name.as_ref())?;
+    Op::set_xattr(target, name, value.to_vec(), 0)?.await
+}
+
+/// Returns the names of the extended attributes of the file at `path`.
+///
+/// Symbolic links are followed. The ring has no operation to list extended
+/// attributes, so they are listed with `listxattr(2)` on a blocking thread.
+pub async fn listxattr(path: impl AsRef<Path>) -> io::Result<Vec<OsString>> {
+    let path = cstr(path.as_ref())?;
+    tokio::task::spawn_blocking(move || list(&path))
+        .await
+        .map_err(io::Error::other)?
+}
+
+async fn get(target: XattrTarget, name: &OsStr) -> io::Result<Option<Vec<u8>>> {
+    let name = name_cstr(name)?;
+    loop {
+        // The size of the value, then the value, which may have grown in the
+        // meantime.
+        let size = match Op::get_xattr(target.clone(), name.clone(), 0)?.await {
+            Ok((size, _)) => size,
+            Err(e) if e.raw_os_error() == Some(libc::ENODATA) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        if size == 0 {
+            return Ok(Some(Vec::new()));
+        }
+
+        let size = size
+            .try_into()
+            .map_err(|_| io::Error::from_raw_os_error(libc::E2BIG))?;
+        match Op::get_xattr(target.clone(), name.clone(), size)?.await {
+            Ok((_, value)) => return Ok(Some(value)),
+            Err(e) if e.raw_os_error() == Some(libc::ERANGE) => continue,
+            Err(e) if e.raw_os_error() == Some(libc::ENODATA) => return Ok(None),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn list(path: &CString) -> io::Result<Vec<OsString>> {
+    loop {
+        let size = syscall!(listxattr(path.as_ptr(), std::ptr::null_mut(), 0))?;
+        let mut names = vec![0u8; size as usize];
+        let res = syscall!(listxattr(
+            path.as_ptr(),
+            names.as_mut_ptr().cast(),
+            names.len()
+        ));
+        match res {
+            Ok(n) => names.truncate(n as usize),
+            Err(e) if e.raw_os_error() == Some(libc::ERANGE) => continue,
+            Err(e) => return Err(e),
+        }
+        // The names are NUL-terminated, one after the other.
+        return Ok(names
+            .split(|&b| b == 0)
+            .filter(|name| !name.is_empty())
+            .map(|name| OsStr::from_bytes(name).to_os_string())
+            .collect());
+    }
+}
+
+fn name_cstr(name: &OsStr) -> io::Result<CString> {
+    Ok(CString::new(name.as_bytes())?)
+}
@@ -0,0 +1,36 @@
+use crate::runtime::driver::op;
+use crate::runtime::driver::op::{Completable, Op};
+use crate::runtime::CONTEXT;
+use std::io;
+use std::os::unix::io::RawFd;
+
+pub(crate) struct Cancel;
+
+impl Op<Cancel> {
+    /// Cancels all operations in flight on `fd`.
+    pub(crate) fn cancel_fd(fd: RawFd) -> io::Result<Op<Cancel>> {
+        use io_uring::{opcode, types};
+
+        CONTEXT.with(|x| {
+            x.handle()
+                .expect("Not in a runtime context")
+                .submit_op(Cancel, |_| {
+                    let builder = types::CancelBuilder::fd(types::Fd(fd)).all();
+                    opcode::AsyncCancel2::new(builder).build()
+                })
+        })
+    }
+}
+
+impl Completable for Cancel {
+    type Output = io::Result<usize>;
+
+    fn complete(self, cqe: op::CqeResult) -> Self::Output {
+        match cqe.result {
+            Ok(n) => Ok(n as usize),
+            // Nothing was in flight.
+            Err(e) if e.raw_os_error() == Some(libc::ENOENT) => Ok(0),
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -4,9 +4,7 @@ use crate::runtime::CONTEXT;
 use std::io;
 use std::os::unix::io::RawFd;
 
-pub(crate) struct Close {
-    fd: RawFd,
-}
+pub(crate) struct Close;
 
 impl Op<Close> {
     pub(crate) fn close(fd: RawFd) -> io::Result<Op<Close>> {
@@ -15,9 +13,18 @@ impl Op<Close> {
         CONTEXT.with(|x| {
             x.handle()
                 .expect("Not in a runtime context")
-                .submit_op(Close { fd }, |close| {
-                    opcode::Close::new(types::Fd(close.fd)).build()
-                })
+                .submit_op(Close, |_| opcode::Close::new(types::Fd(fd)).build())
+        })
+    }
+
+    /// Closes the direct descriptor in `slot`, emptying the slot.
+    pub(crate) fn close_direct(slot: u32) -> io::Result<Op<Close>> {
+        use io_uring::{opcode, types};
+
+        CONTEXT.with(|x| {
+            x.handle()
+                .expect("Not in a runtime context")
+                .submit_op(Close, |_| opcode::Close::new(types::Fixed(slot)).build())
         })
     }
 }
@@ -0,0 +1,244 @@
+use std::cell::Cell;
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::rc::Rc;
+
+use crate::buf::{BoundedBuf, BoundedBufMut};
+use crate::io::SharedFd;
+use crate::runtime::driver::op::Op;
+use crate::runtime::driver::WeakHandle;
+use crate::runtime::CONTEXT;
+use crate::{UnsubmittedRead, UnsubmittedWrite};
+
+/// A direct descriptor: a file registered in a slot of the table of the
+/// ring, rather than installed in the file table of the process.
+///
+/// Operations on a direct descriptor refer to it by slot (`types::Fixed`),
+/// which spares the kernel from looking up, and reference counting, the file
+/// in the file table the threads of the process share. The table is set up
+/// with [`Builder::direct_descriptors`], and belongs to the ring of the
+/// runtime, so a direct descriptor can only be used on the runtime it was
+/// registered on.
+///
+/// Dropping a direct descriptor closes it once its operations in flight
+/// complete, as [`close`](DirectFd::close) does, and hands its slot back for
+/// reuse.
+///
+/// [`Builder::direct_descriptors`]: crate::Builder::direct_descriptors
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::io::DirectFd;
+/// use tokio_uring::Submit;
+///
+/// tokio_uring::builder().direct_descriptors(16).start(async {
+///     let file = std::fs::File::create("hello.txt").unwrap();
+///     let direct = DirectFd::register(&file).unwrap();
+///     // The direct descriptor refers to the file on its own.
+///     drop(file);
+///
+///     let (n, _) = direct.write_at(b"hello".to_vec(), 0).submit().await.unwrap();
+///     assert_eq!(n, 5);
+///     direct.close().await.unwrap();
+/// });
+/// ```
+pub struct DirectFd {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    slot: u32,
+    driver: WeakHandle,
+
+    // The slot was emptied by an explicit close.
+    closed: Cell<bool>,
+}
+
+impl DirectFd {
+    /// Registers the file `fd` refers to in a free slot of the table of
+    /// direct descriptors of the current runtime.
+    ///
+    /// The direct descriptor holds its own reference to the file, so `fd`
+    /// can be closed afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the runtime has no table of direct descriptors, and with
+    /// `ENFILE` if all of its slots are taken.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a runtime context.
+    pub fn register(fd: &impl AsRawFd) -> io::Result<DirectFd> {
+        let handle = CONTEXT
+            .with(|x| x.handle())
+            .expect("Not in a runtime context");
+        let slot = handle.register_direct(fd.as_raw_fd())?;
+
+        Ok(DirectFd {
+            inner: Rc::new(Inner {
+                slot,
+                driver: (&handle).into(),
+                closed: Cell::new(false),
+            }),
+        })
+    }
+
+    /// Installs the file `fd` refers to in a free slot of the table of direct
+    /// descriptors of the current runtime, from within the ring
+    /// (`IORING_OP_FILES_UPDATE`).
+    ///
+    /// This is [`register`](DirectFd::register) without a system call of its
+    /// own, as the update is submitted along with other operations. A server
+    /// can slot each socket it accepts this way. Requires Linux 5.6 or later.
+    ///
+    /// # Errors
+    ///
+    /// As [`register`](DirectFd::register).
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a runtime context.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::io::DirectFd;
+    /// use tokio_uring::net::TcpListener;
+    ///
+    /// tokio_uring::builder().direct_descriptors(4096).start(async {
+    ///     let listener = TcpListener::bind("127.0.0.1:8080".parse().unwrap()).unwrap();
+    ///     loop {
+    ///         let (stream, _) = listener.accept().await.unwrap();
+    ///         let conn = DirectFd::install(&stream).await.unwrap();
+    ///         // The stream is no longer needed, the connection lives on in
+    ///         // the slot.
+    ///         drop(stream);
+    ///         // Hand `conn` to a task serving the connection.
+    ///     }
+    /// });
+    /// ```
+    pub async fn install(fd: &impl AsRawFd) -> io::Result<DirectFd> {
+        let handle = CONTEXT
+            .with(|x| x.handle())
+            .expect("Not in a runtime context");
+        let slot = handle.alloc_direct()?;
+
+        // The slot is only handed back on failure, as it holds no file then.
+        let direct = DirectFd {
+            inner: Rc::new(Inner {
+                slot,
+                driver: (&handle).into(),
+                closed: Cell::new(true),
+            }),
+        };
+        direct.replace(fd).await?;
+        direct.inner.closed.set(false);
+        Ok(direct)
+    }
+
+    /// Replaces the file in the slot of the direct descriptor with the file
+    /// `fd` refers to, from within the ring (`IORING_OP_FILES_UPDATE`).
+    ///
+    /// The file previously in the slot is closed once operations in flight on
+    /// it complete. Requires Linux 5.6 or later.
+    pub async fn replace(&self, fd: &impl AsRawFd) -> io::Result<()> {
+        Op::files_update(self.inner.slot, vec![fd.as_raw_fd()])?.await?;
+        Ok(())
+    }
+
+    /// Installs the file of the direct descriptor in the file table of the
+    /// process (`IORING_OP_FIXED_FD_INSTALL`), returning a regular file
+    /// descriptor for it.
+    ///
+    /// This hands the file to code outside of `tokio-uring`, e.g. to pass it
+    /// over a unix socket. The direct descriptor remains open, and the
+    /// caller owns the returned descriptor, which is close-on-exec. Requires
+    /// Linux 6.8 or later.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::os::unix::io::FromRawFd;
+    /// use tokio_uring::io::DirectFd;
+    ///
+    /// tokio_uring::builder().direct_descriptors(16).start(async {
+    ///     let file = std::fs::File::open("hello.txt").unwrap();
+    ///     let direct = DirectFd::install(&file).await.unwrap();
+    ///     drop(file);
+    ///
+    ///     let fd = direct.install_fd().await.unwrap();
+    ///     let file = unsafe { std::fs::File::from_raw_fd(fd) };
+    ///     println!("{:?}", file.metadata().unwrap());
+    /// });
+    /// ```
+    pub async fn install_fd(&self) -> io::Result<RawFd> {
+        Op::fixed_fd_install(self)?.await
+    }
+
+    /// Returns the slot of the direct descriptor in the table.
+    pub fn slot(&self) -> u32 {
+        self.inner.slot
+    }
+
+    /// Reads from the file at `pos`, as [`File::read_at`] does.
+    ///
+    /// [`File::read_at`]: crate::fs::File::read_at
+    pub fn read_at<T: BoundedBufMut>(&self, buf: T, pos: u64) -> UnsubmittedRead<T> {
+        UnsubmittedRead::read_at_direct(self, buf, pos)
+    }
+
+    /// Writes to the file at `pos`, as [`File::write_at`] does.
+    ///
+    /// [`File::write_at`]: crate::fs::File::write_at
+    pub fn write_at<T: BoundedBuf>(&self, buf: T, pos: u64) -> UnsubmittedWrite<T> {
+        UnsubmittedWrite::write_at_direct(self, buf, pos)
+    }
+
+    /// Closes the direct descriptor, emptying its slot.
+    ///
+    /// Operations in flight keep the file open until they complete. The slot
+    /// is handed back for reuse once they do.
+    pub async fn close(self) -> io::Result<()> {
+        Op::close_direct(self.inner.slot)?.await?;
+        self.inner.closed.set(true);
+        Ok(())
+    }
+
+    pub(crate) fn clone_ref(&self) -> DirectFd {
+        DirectFd {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        if let Some(driver) = self.driver.upgrade() {
+            if let Err(e) = driver.release_direct(self.slot, !self.closed.get()) {
+                let _ = CONTEXT.try_with(|cx| cx.record_close_error(e));
+            }
+        }
+    }
+}
+
+/// Keeps the file of an operation open while it is in flight, whether it is
+/// a regular or a direct descriptor.
+#[allow(dead_code)] // The descriptor is only held, never read.
+pub(crate) enum FdGuard {
+    Shared(SharedFd),
+    Direct(DirectFd),
+}
+
+impl From<SharedFd> for FdGuard {
+    fn from(fd: SharedFd) -> FdGuard {
+        FdGuard::Shared(fd)
+    }
+}
+
+impl From<DirectFd> for FdGuard {
+    fn from(fd: DirectFd) -> FdGuard {
+        FdGuard::Direct(fd)
+    }
+}
@@ -0,0 +1,249 @@
+use crate::io::poll::PollMulti;
+use crate::io::SharedFd;
+use crate::runtime::driver::op::{MultiCQEFuture, Op};
+use std::cell::Cell;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::Poll;
+
+/// An epoll instance driven by the `io_uring` event loop.
+///
+/// Many C libraries (libpq, librdkafka, c-ares, ...) expose readiness-based
+/// interfaces: they hand out file descriptors and expect to be told when those
+/// become readable or writable. `EpollBridge` lets such libraries run on a
+/// `tokio-uring` runtime without a second reactor thread. Their descriptors
+/// are registered with an epoll instance, and the epoll descriptor itself is
+/// watched with a multishot `io_uring` poll.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{Read, Write};
+/// use std::os::unix::io::AsRawFd;
+/// use std::os::unix::net::UnixStream;
+/// use tokio_uring::io::EpollBridge;
+///
+/// fn main() -> std::io::Result<()> {
+///     tokio_uring::start(async {
+///         // A descriptor handed out by a library, which reads from it once
+///         // it is readable.
+///         let (mut library, mut peer) = UnixStream::pair()?;
+///         library.set_nonblocking(true)?;
+///
+///         let mut bridge = EpollBridge::new()?;
+///         bridge.add(library.as_raw_fd(), libc::EPOLLIN as u32, 1)?;
+///
+///         peer.write_all(b"ping")?;
+///
+///         for event in bridge.wait().await? {
+///             if event.token() == 1 && event.is_readable() {
+///                 // Let the library process its input.
+///                 let mut buf = [0; 4];
+///                 library.read_exact(&mut buf)?;
+///                 assert_eq!(&buf, b"ping");
+///             }
+///         }
+///         Ok(())
+///     })
+/// }
+/// ```
+pub struct EpollBridge {
+    epfd: SharedFd,
+
+    /// Multishot poll on `epfd`, armed on first use.
+    poll: Option<Op<PollMulti, MultiCQEFuture>>,
+
+    /// Number of readiness events posted for `epfd`.
+    ready: Rc<Cell<u64>>,
+
+    /// Value of `ready` when the epoll instance was last drained.
+    seen: u64,
+
+    /// Events returned by the last call to `wait`.
+    events: Vec<Event>,
+}
+
+/// A readiness event returned by [`EpollBridge::wait`].
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Event(libc::epoll_event);
+
+impl EpollBridge {
+    /// Creates a new epoll instance, returning at most 64 events per call to
+    /// [`wait`](EpollBridge::wait).
+    pub fn new() -> io::Result<EpollBridge> {
+        EpollBridge::with_capacity(64)
+    }
+
+    /// Creates a new epoll instance, returning at most `capacity` events per
+    /// call to [`wait`](EpollBridge::wait).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn with_capacity(capacity: usize) -> io::Result<EpollBridge> {
+        assert!(capacity > 0, "capacity must be greater than zero");
+
+        let epfd = syscall!(epoll_create1(libc::EPOLL_CLOEXEC))?;
+
+        Ok(EpollBridge {
+            epfd: SharedFd::new(epfd),
+            poll: None,
+            ready: Rc::new(Cell::new(0)),
+            seen: 0,
+            events: Vec::with_capacity(capacity),
+        })
+    }
+
+    /// Registers `fd` for the events in `interest`, a mask of `libc::EPOLL*`
+    /// flags. Events for `fd` are reported with the given `token`.
+    pub fn add(&self, fd: RawFd, interest: u32, token: u64) -> io::Result<()> {
+        self.ctl(libc::EPOLL_CTL_ADD, fd, interest, token)
+    }
+
+    /// Changes the interest and token of a registered `fd`.
+    pub fn modify(&self, fd: RawFd, interest: u32, token: u64) -> io::Result<()> {
+        self.ctl(libc::EPOLL_CTL_MOD, fd, interest, token)
+    }
+
+    /// Removes `fd` from the epoll instance.
+    pub fn delete(&self, fd: RawFd) -> io::Result<()> {
+        self.ctl(libc::EPOLL_CTL_DEL, fd, 0, 0)
+    }
+
+    fn ctl(&self, op: libc::c_int, fd: RawFd, interest: u32, token: u64) -> io::Result<()> {
+        let mut event = libc::epoll_event {
+            events: interest,
+            u64: token,
+        };
+        syscall!(epoll_ctl(self.epfd.raw_fd(), op, fd, &mut event))?;
+        Ok(())
+    }
+
+    /// Waits until at least one registered descriptor is ready, returning
+    /// the ready events.
+    ///
+    /// Level-triggered registrations are reported again on the next call
+    /// until the library has consumed the readiness.
+    pub async fn wait(&mut self) -> io::Result<&[Event]> {
+        loop {
+            // Snapshot before draining, so readiness posted in between is
+            // not lost.
+            self.seen = self.ready.get();
+
+            let n = syscall!(epoll_wait(
+                self.epfd.raw_fd(),
+                self.events.as_mut_ptr() as *mut libc::epoll_event,
+                self.events.capacity() as libc::c_int,
+                0
+            ))?;
+
+            if n > 0 {
+                // Safety: epoll_wait initialized the first `n` events, and
+                // `Event` is a transparent wrapper around `epoll_event`.
+                unsafe { self.events.set_len(n as usize) };
+                return Ok(&self.events);
+            }
+
+            self.readable().await?;
+        }
+    }
+
+    // Wait until the kernel posted readiness for the epoll descriptor since
+    // it was last drained.
+    async fn readable(&mut self) -> io::Result<()> {
+        poll_fn(|cx| loop {
+            if self.poll.is_none() {
+                self.poll = Some(Op::poll_multi(
+                    &self.epfd,
+                    libc::POLLIN as u32,
+                    self.ready.clone(),
+                )?);
+            }
+
+            let pending = match Pin::new(self.poll.as_mut().unwrap()).poll(cx) {
+                Poll::Ready(res) => {
+                    // The kernel dropped the multishot poll, it is re-armed
+                    // on the next iteration.
+                    self.poll = None;
+                    res?;
+                    false
+                }
+                Poll::Pending => true,
+            };
+
+            if self.ready.get() != self.seen {
+                return Poll::Ready(Ok(()));
+            }
+
+            if pending {
+                return Poll::Pending;
+            }
+        })
+        .await
+    }
+}
+
+impl AsRawFd for EpollBridge {
+    fn as_raw_fd(&self) -> RawFd {
+        self.epfd.raw_fd()
+    }
+}
+
+impl Drop for EpollBridge {
+    fn drop(&mut self) {
+        // The multishot poll keeps the epoll descriptor alive until the
+        // kernel has terminated it.
+        if let Some(poll) = &self.poll {
+            let _ = poll.cancel();
+        }
+    }
+}
+
+impl fmt::Debug for EpollBridge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EpollBridge")
+            .field("epfd", &self.epfd.raw_fd())
+            .finish()
+    }
+}
+
+impl Event {
+    /// Returns the token the descriptor was registered with.
+    pub fn token(&self) -> u64 {
+        self.0.u64
+    }
+
+    /// Returns the raw `libc::EPOLL*` event mask.
+    pub fn events(&self) -> u32 {
+        self.0.events
+    }
+
+    /// Returns `true` if the descriptor is readable.
+    pub fn is_readable(&self) -> bool {
+        self.events() & (libc::EPOLLIN | libc::EPOLLPRI) as u32 != 0
+    }
+
+    /// Returns `true` if the descriptor is writable.
+    pub fn is_writable(&self) -> bool {
+        self.events() & libc::EPOLLOUT as u32 != 0
+    }
+
+    /// Returns `true` if the descriptor reported an error or hang-up.
+    pub fn is_error(&self) -> bool {
+        self.events() & (libc::EPOLLERR | libc::EPOLLHUP) as u32 != 0
+    }
+}
+
+impl fmt::Debug for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Event")
+            .field("token", &self.token())
+            .field("events", &self.events())
+            .finish()
+    }
+}
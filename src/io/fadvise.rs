@@ -0,0 +1,43 @@
+use std::io;
+
+use io_uring::{opcode, types};
+
+use crate::{
+    io::SharedFd,
+    runtime::{
+        driver::op::{Completable, CqeResult, Op},
+        CONTEXT,
+    },
+};
+
+pub(crate) struct Fadvise {
+    fd: SharedFd,
+}
+
+impl Op<Fadvise> {
+    pub(crate) fn fadvise(
+        fd: &SharedFd,
+        offset: u64,
+        len: u32,
+        advice: i32,
+    ) -> io::Result<Op<Fadvise>> {
+        CONTEXT.with(|x| {
+            x.handle().expect("not in a runtime context").submit_op(
+                Fadvise { fd: fd.clone() },
+                |fadvise| {
+                    opcode::Fadvise::new(types::Fd(fadvise.fd.raw_fd()), len as _, advice)
+                        .offset(offset)
+                        .build()
+                },
+            )
+        })
+    }
+}
+
+impl Completable for Fadvise {
+    type Output = io::Result<()>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        cqe.result.map(|_| ())
+    }
+}
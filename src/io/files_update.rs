@@ -0,0 +1,36 @@
+use std::io;
+use std::os::unix::io::RawFd;
+
+use crate::runtime::driver::op::{Completable, CqeResult, Op};
+use crate::runtime::CONTEXT;
+use io_uring::opcode;
+
+pub(crate) struct FilesUpdate {
+    /// The descriptors to install, which the kernel reads once the
+    /// operation runs
+    fds: Vec<RawFd>,
+}
+
+impl Op<FilesUpdate> {
+    /// Installs `fds` in consecutive slots of the table of direct
+    /// descriptors from `offset` on. A descriptor of -1 empties its slot.
+    pub(crate) fn files_update(offset: u32, fds: Vec<RawFd>) -> io::Result<Op<FilesUpdate>> {
+        CONTEXT.with(|x| {
+            x.handle()
+                .expect("Not in a runtime context")
+                .submit_op(FilesUpdate { fds }, |update| {
+                    opcode::FilesUpdate::new(update.fds.as_ptr(), update.fds.len() as _)
+                        .offset(offset as _)
+                        .build()
+                })
+        })
+    }
+}
+
+impl Completable for FilesUpdate {
+    type Output = io::Result<usize>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        cqe.result.map(|n| n as usize)
+    }
+}
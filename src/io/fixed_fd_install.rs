@@ -0,0 +1,54 @@
+use std::io;
+use std::os::unix::io::RawFd;
+
+use crate::io::DirectFd;
+use crate::runtime::driver::op::{Completable, CqeResult, Op};
+use crate::runtime::CONTEXT;
+use io_uring::{opcode, squeue};
+
+/// `IORING_OP_FIXED_FD_INSTALL`, which io-uring has no opcode for yet.
+const FIXED_FD_INSTALL: u8 = 54;
+
+pub(crate) struct FixedFdInstall {
+    /// Holds the slot, preventing it from being reused while the operation
+    /// is in flight.
+    _fd: DirectFd,
+}
+
+impl Op<FixedFdInstall> {
+    pub(crate) fn fixed_fd_install(fd: &DirectFd) -> io::Result<Op<FixedFdInstall>> {
+        let slot = fd.slot();
+
+        CONTEXT.with(|x| {
+            x.handle().expect("Not in a runtime context").submit_op(
+                FixedFdInstall {
+                    _fd: fd.clone_ref(),
+                },
+                |_| fixed_fd_install(slot),
+            )
+        })
+    }
+}
+
+impl Completable for FixedFdInstall {
+    type Output = io::Result<RawFd>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        cqe.result.map(|fd| fd as RawFd)
+    }
+}
+
+// The entry is that of a no-op on the slot, with the opcode swapped. The
+// installed descriptor is close-on-exec, as `install_fd_flags` is left 0.
+fn fixed_fd_install(slot: u32) -> squeue::Entry {
+    let mut sqe = opcode::Nop::new().build().flags(squeue::Flags::FIXED_FILE);
+
+    // Safety: an SQE is an io_uring_sqe, which starts with the opcode, and
+    // has the descriptor at offset 4.
+    unsafe {
+        let raw = &mut sqe as *mut squeue::Entry as *mut u8;
+        *raw = FIXED_FD_INSTALL;
+        (raw.add(4) as *mut i32).write(slot as i32);
+    }
+    sqe
+}
@@ -32,6 +32,25 @@ impl Op<Fsync> {
             )
         })
     }
+
+    pub(crate) fn sync_file_range(
+        fd: &SharedFd,
+        offset: u64,
+        len: u32,
+        flags: u32,
+    ) -> io::Result<Op<Fsync>> {
+        CONTEXT.with(|x| {
+            x.handle().expect("Not in a runtime context").submit_op(
+                Fsync { fd: fd.clone() },
+                |fsync| {
+                    opcode::SyncFileRange::new(types::Fd(fsync.fd.raw_fd()), len)
+                        .offset(offset)
+                        .flags(flags)
+                        .build()
+                },
+            )
+        })
+    }
 }
 
 impl Completable for Fsync {
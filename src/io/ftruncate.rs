@@ -0,0 +1,50 @@
+use std::io;
+use std::os::unix::io::RawFd;
+
+use io_uring::squeue;
+
+use crate::{
+    io::{util::RawSqe, SharedFd},
+    runtime::{
+        driver::op::{Completable, CqeResult, Op},
+        CONTEXT,
+    },
+};
+
+/// The opcode of `IORING_OP_FTRUNCATE`, which `io-uring` has no builder for.
+pub(crate) const IORING_OP_FTRUNCATE: u8 = 55;
+
+pub(crate) struct Ftruncate {
+    fd: SharedFd,
+}
+
+impl Op<Ftruncate> {
+    pub(crate) fn ftruncate(fd: &SharedFd, len: u64) -> io::Result<Op<Ftruncate>> {
+        CONTEXT.with(|x| {
+            x.handle()
+                .expect("not in a runtime context")
+                .submit_op(Ftruncate { fd: fd.clone() }, |ftruncate| {
+                    ftruncate_sqe(ftruncate.fd.raw_fd(), len)
+                })
+        })
+    }
+}
+
+impl Completable for Ftruncate {
+    type Output = io::Result<()>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        cqe.result.map(|_| ())
+    }
+}
+
+fn ftruncate_sqe(fd: RawFd, len: u64) -> squeue::Entry {
+    RawSqe {
+        opcode: IORING_OP_FTRUNCATE,
+        fd,
+        // The length is passed as the offset.
+        off: len,
+        ..Default::default()
+    }
+    .build()
+}
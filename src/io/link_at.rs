@@ -0,0 +1,52 @@
+use crate::runtime::driver::op::{Completable, CqeResult, Op};
+use crate::runtime::CONTEXT;
+use std::ffi::CString;
+use std::io;
+use std::path::Path;
+
+/// Creates a new link to a file.
+///
+/// The given paths are interpreted relative to the current working directory
+/// of the calling process.
+pub(crate) struct LinkAt {
+    pub(crate) from: CString,
+    pub(crate) to: CString,
+}
+
+impl Op<LinkAt> {
+    /// Submit a request to link `to` to the file at `from` with the provided
+    /// `AT_*` flags.
+    pub(crate) fn link_at(from: &Path, to: &Path, flags: i32) -> io::Result<Op<LinkAt>> {
+        use io_uring::{opcode, types};
+
+        let from = super::util::cstr(from)?;
+        let to = super::util::cstr(to)?;
+
+        CONTEXT.with(|x| {
+            x.handle()
+                .expect("Not in a runtime context")
+                .submit_op(LinkAt { from, to }, |link| {
+                    // Get a reference to the memory. The strings are held by
+                    // the operation state until the operation completes.
+                    let from_ref = link.from.as_c_str().as_ptr();
+                    let to_ref = link.to.as_c_str().as_ptr();
+                    opcode::LinkAt::new(
+                        types::Fd(libc::AT_FDCWD),
+                        from_ref,
+                        types::Fd(libc::AT_FDCWD),
+                        to_ref,
+                    )
+                    .flags(flags)
+                    .build()
+                })
+        })
+    }
+}
+
+impl Completable for LinkAt {
+    type Output = io::Result<()>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        cqe.result.map(|_| ())
+    }
+}
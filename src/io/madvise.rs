@@ -0,0 +1,82 @@
+use std::io;
+
+use io_uring::opcode;
+
+use crate::runtime::driver::op::{Completable, CqeResult, Op};
+use crate::runtime::CONTEXT;
+
+/// Advises the kernel of how the `len` bytes of memory starting at `addr`
+/// are going to be accessed, using `madvise(2)` through the ring.
+///
+/// `advice` is one of the `MADV_*` values, e.g.:
+///
+/// * `libc::MADV_WILLNEED`: the range is accessed soon, so the kernel may
+///   start reading the pages of a file mapping in.
+/// * `libc::MADV_SEQUENTIAL`: the range is accessed sequentially, so the
+///   kernel may read ahead more, and drop the pages read soon after.
+/// * `libc::MADV_DONTNEED`: the range isn't accessed anymore, so the kernel
+///   may free its pages. Private anonymous memory reads as zeroes afterwards.
+///
+/// This suits mappings of large files, whose page faults would block the
+/// runtime thread: their pages can be read in ahead of time without leaving
+/// async code.
+///
+/// # Safety
+///
+/// The range must be mapped memory, starting at a page boundary, and stay
+/// mapped until the returned future completes, even if it is dropped before:
+/// dropping it does not stop the advice from being applied. Advice such as
+/// `MADV_DONTNEED` or `MADV_REMOVE` discards the contents of the range, so it
+/// must not be referenced by Rust values which rely on them.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::os::unix::io::AsRawFd;
+///
+/// tokio_uring::start(async {
+///     let file = std::fs::File::open("index.db").unwrap();
+///     let len = file.metadata().unwrap().len() as usize;
+///     let addr = unsafe {
+///         libc::mmap(
+///             std::ptr::null_mut(),
+///             len,
+///             libc::PROT_READ,
+///             libc::MAP_SHARED,
+///             file.as_raw_fd(),
+///             0,
+///         )
+///     };
+///     assert_ne!(addr, libc::MAP_FAILED);
+///
+///     // Read the index in before searching it.
+///     unsafe { tokio_uring::io::madvise(addr.cast(), len as u32, libc::MADV_WILLNEED) }
+///         .await
+///         .unwrap();
+/// });
+/// ```
+pub async unsafe fn madvise(addr: *mut u8, len: u32, advice: i32) -> io::Result<()> {
+    Op::madvise(addr, len, advice)?.await
+}
+
+pub(crate) struct Madvise;
+
+impl Op<Madvise> {
+    pub(crate) fn madvise(addr: *mut u8, len: u32, advice: i32) -> io::Result<Op<Madvise>> {
+        CONTEXT.with(|x| {
+            x.handle()
+                .expect("Not in a runtime context")
+                .submit_op(Madvise, |_| {
+                    opcode::Madvise::new(addr.cast(), len as _, advice).build()
+                })
+        })
+    }
+}
+
+impl Completable for Madvise {
+    type Output = io::Result<()>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        cqe.result.map(|_| ())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::io::SharedFd;
 use crate::runtime::driver::op::{Completable, CqeResult, Op};
 use crate::runtime::CONTEXT;
 
@@ -7,29 +8,42 @@ use std::ffi::CString;
 use std::io;
 use std::path::Path;
 
-/// Create a directory at path relative to the current working directory
-/// of the caller's process.
+/// Create a directory at path relative to a directory, or to the current
+/// working directory of the caller's process.
 pub(crate) struct Mkdir {
+    pub(crate) _dir: Option<SharedFd>,
     pub(crate) _path: CString,
 }
 
 impl Op<Mkdir> {
-    /// Submit a request to create a directory
-    pub(crate) fn make_dir(path: &Path, mode: u32) -> io::Result<Op<Mkdir>> {
+    /// Submit a request to create a directory, relative to `dir` if given
+    pub(crate) fn make_dir(
+        dir: Option<&SharedFd>,
+        path: &Path,
+        mode: u32,
+    ) -> io::Result<Op<Mkdir>> {
         use io_uring::{opcode, types};
 
         let _path = cstr(path)?;
 
         CONTEXT.with(|x| {
-            x.handle()
-                .expect("Not in a runtime context")
-                .submit_op(Mkdir { _path }, |mkdir| {
+            x.handle().expect("Not in a runtime context").submit_op(
+                Mkdir {
+                    _dir: dir.cloned(),
+                    _path,
+                },
+                |mkdir| {
                     let p_ref = mkdir._path.as_c_str().as_ptr();
+                    let dir = mkdir
+                        ._dir
+                        .as_ref()
+                        .map_or(libc::AT_FDCWD, |dir| dir.raw_fd());
 
-                    opcode::MkDirAt::new(types::Fd(libc::AT_FDCWD), p_ref)
+                    opcode::MkDirAt::new(types::Fd(dir), p_ref)
                         .mode(mode)
                         .build()
-                })
+                },
+            )
         })
     }
 }
@@ -1,20 +1,56 @@
+//! Low level I/O helpers for integrating with `tokio-uring`.
+//!
+//! * [`EpollBridge`] drives readiness-based libraries from the `io_uring`
+//!   event loop.
+//! * [`DirectFd`] performs operations on files registered with the ring.
+//! * [`madvise`] advises the kernel of how memory is going to be accessed.
+
 mod accept;
 
+mod cancel;
+
 mod close;
 
 mod connect;
 
+mod direct_fd;
+pub use direct_fd::DirectFd;
+pub(crate) use direct_fd::FdGuard;
+
+mod epoll;
+pub use epoll::{EpollBridge, Event};
+
+mod fadvise;
+
 mod fallocate;
 
+mod files_update;
+
+mod fixed_fd_install;
+
 mod fsync;
 
+mod ftruncate;
+pub(crate) use ftruncate::IORING_OP_FTRUNCATE;
+
+mod link_at;
+
+mod madvise;
+pub use madvise::madvise;
+
 mod mkdir_at;
 
+mod msg_ring;
+
 mod noop;
 pub(crate) use noop::NoOp;
 
+pub(crate) mod nvme;
+
 mod open;
 
+pub(crate) mod poll;
+
 pub(crate) mod read;
 
 mod read_fixed;
@@ -41,8 +77,14 @@ pub(crate) use shared_fd::SharedFd;
 mod socket;
 pub(crate) use socket::Socket;
 
+mod splice;
+
 mod statx;
 
+mod symlink_at;
+
+pub(crate) mod timeout;
+
 mod unlink_at;
 
 mod util;
@@ -56,3 +98,6 @@ pub(crate) mod writev;
 
 mod writev_all;
 pub(crate) use writev_all::writev_at_all;
+
+mod xattr;
+pub(crate) use xattr::XattrTarget;
@@ -0,0 +1,42 @@
+use crate::runtime::driver::op;
+use crate::runtime::driver::op::{Completable, Op};
+use crate::runtime::CONTEXT;
+use std::io;
+use std::os::unix::io::{AsRawFd, OwnedFd};
+use std::sync::Arc;
+
+pub(crate) struct MsgRing {
+    /// Keeps the fd of the receiving ring open until the kernel is done
+    /// with it.
+    ring_fd: Arc<OwnedFd>,
+}
+
+impl Op<MsgRing> {
+    /// Posts a completion with `value` and `user_data` to the ring `ring_fd`.
+    pub(crate) fn msg_ring(
+        ring_fd: Arc<OwnedFd>,
+        value: i32,
+        user_data: u64,
+    ) -> io::Result<Op<MsgRing>> {
+        use io_uring::{opcode, types};
+
+        CONTEXT.with(|x| {
+            x.handle()
+                .expect("Not in a runtime context")
+                .submit_op(MsgRing { ring_fd }, |msg| {
+                    let fd = types::Fd(msg.ring_fd.as_raw_fd());
+                    opcode::MsgRingData::new(fd, value, user_data, None).build()
+                })
+        })
+    }
+}
+
+impl Completable for MsgRing {
+    type Output = io::Result<()>;
+
+    fn complete(self, cqe: op::CqeResult) -> Self::Output {
+        let _ = cqe.result?;
+
+        Ok(())
+    }
+}
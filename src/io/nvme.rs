@@ -0,0 +1,148 @@
+use crate::buf::{BoundedBuf, BoundedBufMut};
+use crate::fs::{NvmeCommand, NvmeStatus};
+use crate::io::{FdGuard, SharedFd};
+use crate::{BigOutputTransform, Result, UnsubmittedBig, WithBuffer};
+use io_uring::cqueue;
+use std::convert::TryFrom;
+use std::io;
+use std::marker::PhantomData;
+use std::ptr;
+
+// `_IOWR('N', 0x80, struct nvme_uring_cmd)` and `_IOWR('N', 0x82, ...)`.
+const NVME_URING_CMD_IO: u32 = 0xc048_4e80;
+const NVME_URING_CMD_ADMIN: u32 = 0xc048_4e82;
+
+/// An unsubmitted NVMe passthrough command without data, or transferring
+/// data to the device.
+pub type UnsubmittedNvme<T> = UnsubmittedBig<NvmeData<T>, NvmeTransform<T>>;
+
+/// An unsubmitted NVMe passthrough command transferring data from the
+/// device.
+pub type UnsubmittedNvmeRead<T> = UnsubmittedBig<NvmeData<T>, NvmeReadTransform<T>>;
+
+#[allow(missing_docs)]
+pub struct NvmeData<T> {
+    /// Holds a strong ref to the FD, preventing the device from being closed
+    /// while the operation is in-flight.
+    _fd: FdGuard,
+
+    buf: T,
+}
+
+#[allow(missing_docs)]
+pub struct NvmeTransform<T> {
+    _phantom: PhantomData<T>,
+}
+
+impl<T> BigOutputTransform for NvmeTransform<T> {
+    type Output = Result<u64, T>;
+    type StoredData = NvmeData<T>;
+
+    fn transform_big_output(self, data: Self::StoredData, cqe: cqueue::Entry32) -> Self::Output {
+        completion(&cqe).with_buffer(data.buf)
+    }
+}
+
+#[allow(missing_docs)]
+pub struct NvmeReadTransform<T> {
+    _phantom: PhantomData<T>,
+}
+
+impl<T: BoundedBufMut> BigOutputTransform for NvmeReadTransform<T> {
+    type Output = Result<u64, T>;
+    type StoredData = NvmeData<T>;
+
+    fn transform_big_output(self, data: Self::StoredData, cqe: cqueue::Entry32) -> Self::Output {
+        // The buffer was initialized before submission, whatever the device
+        // transferred into it.
+        completion(&cqe).with_buffer(data.buf)
+    }
+}
+
+// The result is a negative errno if the command couldn't be issued, and
+// otherwise the status of its completion, with the command specific result
+// in the extra bytes.
+fn completion(cqe: &cqueue::Entry32) -> io::Result<u64> {
+    match cqe.result() {
+        0 => Ok(cqe.big_cqe()[0]),
+        res if res < 0 => Err(io::Error::from_raw_os_error(-res)),
+        res => Err(io::Error::other(NvmeStatus::new(res as u16))),
+    }
+}
+
+fn build(fd: &SharedFd, cmd: &NvmeCommand, addr: u64, len: usize) -> io_uring::squeue::Entry128 {
+    use io_uring::{opcode, types};
+
+    let cmd_op = if cmd.is_admin() {
+        NVME_URING_CMD_ADMIN
+    } else {
+        NVME_URING_CMD_IO
+    };
+    let len = u32::try_from(len).expect("NVMe command buffers must be smaller than 4 GiB");
+    opcode::UringCmd80::new(types::Fd(fd.raw_fd()), cmd_op)
+        .cmd(cmd.to_bytes(addr, len))
+        .build()
+}
+
+impl UnsubmittedNvme<()> {
+    pub(crate) fn nvme(fd: &SharedFd, cmd: &NvmeCommand) -> Self {
+        Self::new(
+            NvmeData {
+                _fd: fd.clone().into(),
+                buf: (),
+            },
+            NvmeTransform {
+                _phantom: PhantomData,
+            },
+            build(fd, cmd, 0, 0),
+        )
+    }
+}
+
+impl<T: BoundedBuf> UnsubmittedNvme<T> {
+    pub(crate) fn nvme_write(fd: &SharedFd, cmd: &NvmeCommand, buf: T) -> Self {
+        // Get raw buffer info
+        let ptr = buf.stable_ptr();
+        let len = buf.bytes_init();
+
+        Self::new(
+            NvmeData {
+                _fd: fd.clone().into(),
+                buf,
+            },
+            NvmeTransform {
+                _phantom: PhantomData,
+            },
+            build(fd, cmd, ptr as u64, len),
+        )
+    }
+}
+
+impl<T: BoundedBufMut> UnsubmittedNvmeRead<T> {
+    pub(crate) fn nvme_read(fd: &SharedFd, cmd: &NvmeCommand, mut buf: T) -> Self {
+        // Get raw buffer info
+        let ptr = buf.stable_mut_ptr();
+        let len = buf.bytes_total();
+
+        // How much the device transfers depends on the command, zero the rest
+        // of the buffer so that all of it is initialized once it completes.
+        let init = buf.bytes_init();
+        // Safety: the range is within the capacity of the buffer, and zeroed
+        // before being marked initialized.
+        unsafe {
+            ptr::write_bytes(ptr.add(init), 0, len - init);
+            buf.set_init(len);
+        }
+
+        Self::new(
+            NvmeData {
+                _fd: fd.clone().into(),
+                buf,
+            },
+            NvmeReadTransform {
+                _phantom: PhantomData,
+            },
+            build(fd, cmd, ptr as u64, len),
+        )
+    }
+}
@@ -3,6 +3,7 @@ use crate::io::SharedFd;
 
 use crate::runtime::driver::op::{Completable, CqeResult, Op};
 use crate::runtime::CONTEXT;
+use io_uring::{opcode, types};
 use std::ffi::CString;
 use std::io;
 use std::path::Path;
@@ -10,14 +11,18 @@ use std::path::Path;
 /// Open a file
 #[allow(dead_code)]
 pub(crate) struct Open {
+    pub(crate) dir: Option<SharedFd>,
     pub(crate) path: CString,
     pub(crate) flags: libc::c_int,
 }
 
 impl Op<Open> {
-    /// Submit a request to open a file.
-    pub(crate) fn open(path: &Path, options: &OpenOptions) -> io::Result<Op<Open>> {
-        use io_uring::{opcode, types};
+    /// Submit a request to open a file, relative to `dir` if given.
+    pub(crate) fn open(
+        dir: Option<&SharedFd>,
+        path: &Path,
+        options: &OpenOptions,
+    ) -> io::Result<Op<Open>> {
         let path = super::util::cstr(path)?;
         let flags = libc::O_CLOEXEC
             | options.access_mode()?
@@ -25,23 +30,95 @@ impl Op<Open> {
             | (options.custom_flags & !libc::O_ACCMODE);
 
         CONTEXT.with(|x| {
-            x.handle()
-                .expect("Not in a runtime context")
-                .submit_op(Open { path, flags }, |open| {
+            x.handle().expect("Not in a runtime context").submit_op(
+                Open {
+                    dir: dir.cloned(),
+                    path,
+                    flags,
+                },
+                |open| {
                     // Get a reference to the memory. The string will be held by the
                     // operation state and will not be accessed again until the operation
                     // completes.
                     let p_ref = open.path.as_c_str().as_ptr();
 
-                    opcode::OpenAt::new(types::Fd(libc::AT_FDCWD), p_ref)
+                    let dir = open.dir.as_ref().map_or(libc::AT_FDCWD, |dir| dir.raw_fd());
+                    let sqe = opcode::OpenAt::new(types::Fd(dir), p_ref)
                         .flags(flags)
                         .mode(options.mode)
-                        .build()
-                })
+                        .build();
+
+                    match options.personality {
+                        Some(personality) => sqe.personality(personality.id()),
+                        None => sqe,
+                    }
+                },
+            )
+        })
+    }
+}
+
+/// Open a file relative to a directory, with `openat2(2)`
+pub(crate) struct OpenAt2 {
+    #[allow(dead_code)]
+    dir: SharedFd,
+    path: CString,
+    how: Box<types::OpenHow>,
+}
+
+impl Op<OpenAt2> {
+    /// Submit a request to open a file at `path` relative to `dir`,
+    /// resolving the path as restricted by `resolve`.
+    pub(crate) fn open_at2(
+        dir: &SharedFd,
+        path: &Path,
+        options: &OpenOptions,
+        resolve: u64,
+    ) -> io::Result<Op<OpenAt2>> {
+        let path = super::util::cstr(path)?;
+        let flags = libc::O_CLOEXEC
+            | options.access_mode()?
+            | options.creation_mode()?
+            | (options.custom_flags & !libc::O_ACCMODE);
+        let mut how = types::OpenHow::new().flags(flags as u64).resolve(resolve);
+        // The kernel rejects a mode unless a file may be created.
+        if flags & libc::O_CREAT != 0 || flags & libc::O_TMPFILE == libc::O_TMPFILE {
+            how = how.mode(options.mode as u64);
+        }
+
+        CONTEXT.with(|x| {
+            x.handle().expect("Not in a runtime context").submit_op(
+                OpenAt2 {
+                    dir: dir.clone(),
+                    path,
+                    how: Box::new(how),
+                },
+                |open| {
+                    let sqe = opcode::OpenAt2::new(
+                        types::Fd(open.dir.raw_fd()),
+                        open.path.as_ptr(),
+                        &*open.how,
+                    )
+                    .build();
+
+                    match options.personality {
+                        Some(personality) => sqe.personality(personality.id()),
+                        None => sqe,
+                    }
+                },
+            )
         })
     }
 }
 
+impl Completable for OpenAt2 {
+    type Output = io::Result<File>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        Ok(File::from_shared_fd(SharedFd::new(cqe.result? as _)))
+    }
+}
+
 impl Completable for Open {
     type Output = io::Result<File>;
 
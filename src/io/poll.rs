@@ -0,0 +1,95 @@
+use crate::io::SharedFd;
+use crate::runtime::driver::op::{Completable, CqeResult, MultiCQEFuture, Op, Updateable};
+use crate::runtime::CONTEXT;
+use std::cell::Cell;
+use std::io;
+use std::rc::Rc;
+
+pub(crate) struct PollAdd {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    fd: SharedFd,
+}
+
+impl Op<PollAdd> {
+    /// Waits until `fd` signals any of the events in `flags`, a mask of
+    /// `libc::POLL*` bits.
+    pub(crate) fn poll_add(fd: &SharedFd, flags: u32) -> io::Result<Op<PollAdd>> {
+        use io_uring::{opcode, types};
+
+        CONTEXT.with(|x| {
+            x.handle()
+                .expect("Not in a runtime context")
+                .submit_op(PollAdd { fd: fd.clone() }, |poll| {
+                    opcode::PollAdd::new(types::Fd(poll.fd.raw_fd()), flags).build()
+                })
+        })
+    }
+}
+
+impl Completable for PollAdd {
+    type Output = io::Result<u32>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        cqe.result
+    }
+}
+
+pub(crate) struct PollMulti {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    fd: SharedFd,
+
+    /// Bumped for every readiness event posted by the kernel.
+    events: Rc<Cell<u64>>,
+}
+
+impl Op<PollMulti, MultiCQEFuture> {
+    /// Arms a multishot poll on `fd`, bumping `events` each time the kernel
+    /// reports one of the events in `flags`.
+    ///
+    /// The returned Op only resolves once the kernel terminates the poll,
+    /// e.g. after it has been cancelled.
+    pub(crate) fn poll_multi(
+        fd: &SharedFd,
+        flags: u32,
+        events: Rc<Cell<u64>>,
+    ) -> io::Result<Op<PollMulti, MultiCQEFuture>> {
+        use io_uring::{opcode, types};
+
+        CONTEXT.with(|x| {
+            x.handle().expect("Not in a runtime context").submit_op(
+                PollMulti {
+                    fd: fd.clone(),
+                    events,
+                },
+                |poll| {
+                    opcode::PollAdd::new(types::Fd(poll.fd.raw_fd()), flags)
+                        .multi(true)
+                        .build()
+                },
+            )
+        })
+    }
+}
+
+impl Completable for PollMulti {
+    type Output = io::Result<()>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        // The final CQE of a multishot poll may still carry an event.
+        let res = cqe.result?;
+        if res != 0 {
+            self.events.set(self.events.get() + 1);
+        }
+        Ok(())
+    }
+}
+
+impl Updateable for PollMulti {
+    fn update(&mut self, cqe: CqeResult) {
+        if cqe.result.is_ok() {
+            self.events.set(self.events.get() + 1);
+        }
+    }
+}
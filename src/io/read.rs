@@ -2,7 +2,8 @@ use io_uring::cqueue::Entry;
 
 use crate::buf::fixed::FixedBuf;
 use crate::buf::BoundedBufMut;
-use crate::io::SharedFd;
+use crate::io::util::add_rw_flags;
+use crate::io::{DirectFd, FdGuard, SharedFd};
 use crate::{OneshotOutputTransform, Result, UnsubmittedOneshot, WithBuffer};
 
 use std::io;
@@ -15,7 +16,7 @@ pub type UnsubmittedRead<T> = UnsubmittedOneshot<ReadData<T>, ReadTransform<T>>;
 pub struct ReadData<T> {
     /// Holds a strong ref to the FD, preventing the file from being closed
     /// while the operation is in-flight.
-    _fd: SharedFd,
+    _fd: FdGuard,
 
     buf: T,
 }
@@ -56,7 +57,7 @@ impl<T: BoundedBufMut> UnsubmittedRead<T> {
 
         Self::new(
             ReadData {
-                _fd: fd.clone(),
+                _fd: fd.clone().into(),
                 buf,
             },
             ReadTransform {
@@ -69,6 +70,29 @@ impl<T: BoundedBufMut> UnsubmittedRead<T> {
     }
 }
 
+impl<T: BoundedBufMut> UnsubmittedRead<T> {
+    pub(crate) fn read_at_direct(fd: &DirectFd, mut buf: T, offset: u64) -> Self {
+        use io_uring::{opcode, types};
+
+        // Get raw buffer info
+        let ptr = buf.stable_mut_ptr();
+        let len = buf.bytes_total();
+
+        Self::new(
+            ReadData {
+                _fd: fd.clone_ref().into(),
+                buf,
+            },
+            ReadTransform {
+                _phantom: PhantomData,
+            },
+            opcode::Read::new(types::Fixed(fd.slot()), ptr, len as _)
+                .offset(offset as _)
+                .build(),
+        )
+    }
+}
+
 impl<T: BoundedBufMut<BufMut = FixedBuf>> UnsubmittedRead<T> {
     pub(crate) fn read_fixed_at(fd: &SharedFd, mut buf: T, offset: u64) -> Self {
         use io_uring::{opcode, types};
@@ -79,7 +103,7 @@ impl<T: BoundedBufMut<BufMut = FixedBuf>> UnsubmittedRead<T> {
         let buf_index = buf.get_buf().buf_index();
         Self::new(
             ReadData {
-                _fd: fd.clone(),
+                _fd: fd.clone().into(),
                 buf,
             },
             ReadTransform {
@@ -106,7 +130,7 @@ impl<T: BoundedBufMut> UnsubmittedRead<T> {
         let len = buf.bytes_total();
         Self::new(
             ReadData {
-                _fd: fd.clone(),
+                _fd: fd.clone().into(),
                 buf,
             },
             ReadTransform {
@@ -118,3 +142,48 @@ impl<T: BoundedBufMut> UnsubmittedRead<T> {
         )
     }
 }
+
+impl<T: BoundedBufMut> UnsubmittedRead<T> {
+    /// Adds the `RWF_*` flags `flags` to the read, as passed to
+    /// `preadv2(2)`.
+    pub fn rw_flags(mut self, flags: i32) -> Self {
+        self.sqe = add_rw_flags(self.sqe, flags);
+        self
+    }
+
+    /// Fail the read with `EAGAIN` rather than wait for the device if the
+    /// data isn't in the page cache (`RWF_NOWAIT`).
+    ///
+    /// This tells cached reads, which are served right away, from those
+    /// which would have to wait for the device, e.g. to serve the latter on a
+    /// separate queue.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    /// use tokio_uring::Submit;
+    ///
+    /// tokio_uring::start(async {
+    ///     let file = File::open("data.bin").await.unwrap();
+    ///
+    ///     match file.read_at(vec![0; 4096], 0).nowait().submit().await {
+    ///         Ok((n, _)) => println!("{} bytes from the page cache", n),
+    ///         Err(tokio_uring::Error(e, _)) if e.raw_os_error() == Some(libc::EAGAIN) => {
+    ///             println!("not cached")
+    ///         }
+    ///         Err(e) => panic!("{}", e),
+    ///     }
+    /// });
+    /// ```
+    pub fn nowait(self) -> Self {
+        self.rw_flags(libc::RWF_NOWAIT)
+    }
+
+    /// Poll for the completion of the read rather than wait for an
+    /// interrupt (`RWF_HIPRI`), on files opened with `O_DIRECT` on devices
+    /// with polled queues.
+    pub fn hipri(self) -> Self {
+        self.rw_flags(libc::RWF_HIPRI)
+    }
+}
@@ -4,6 +4,7 @@ use crate::io::SharedFd;
 use crate::runtime::driver::op::{self, Completable, Op};
 use crate::Result;
 use crate::WithBuffer;
+use io_uring::squeue;
 
 use crate::runtime::CONTEXT;
 use std::io;
@@ -47,6 +48,56 @@ where
             )
         })
     }
+
+    /// Reads into `bufs` in order from `offset` on, with each read linked to
+    /// the next one. The reads are submitted all at once or not at all.
+    pub(crate) fn read_fixed_at_linked(
+        fd: &SharedFd,
+        bufs: Vec<T>,
+        offset: u64,
+    ) -> std::result::Result<Vec<Op<ReadFixed<T>>>, crate::Error<Vec<T>>> {
+        use io_uring::{opcode, types};
+
+        let last = bufs.len().saturating_sub(1);
+        let bufs = bufs
+            .into_iter()
+            .map(|buf| ReadFixed {
+                fd: fd.clone(),
+                buf,
+            })
+            .collect();
+
+        let mut i = 0;
+        let mut offset = offset;
+        let res = CONTEXT.with(|x| {
+            x.handle()
+                .expect("Not in a runtime context")
+                .submit_linked_ops(bufs, |read_fixed| {
+                    // Get raw buffer info
+                    let ptr = read_fixed.buf.stable_mut_ptr();
+                    let len = read_fixed.buf.bytes_total();
+                    let buf_index = read_fixed.buf.get_buf().buf_index();
+                    let flags = if i < last {
+                        squeue::Flags::IO_LINK
+                    } else {
+                        squeue::Flags::empty()
+                    };
+                    let sqe =
+                        opcode::ReadFixed::new(types::Fd(fd.raw_fd()), ptr, len as _, buf_index)
+                            .offset(offset as _)
+                            .build()
+                            .flags(flags);
+                    i += 1;
+                    offset += len as u64;
+                    sqe
+                })
+        });
+
+        res.map_err(|crate::Error(e, data)| {
+            let bufs = data.into_iter().map(|read_fixed| read_fixed.buf).collect();
+            crate::Error(e, bufs)
+        })
+    }
 }
 
 impl<T> Completable for ReadFixed<T>
@@ -1,3 +1,4 @@
+use crate::io::SharedFd;
 use crate::runtime::driver::op::{Completable, CqeResult, Op};
 use crate::runtime::CONTEXT;
 use std::ffi::CString;
@@ -6,17 +7,27 @@ use std::path::Path;
 
 /// Renames a file, moving it between directories if required.
 ///
-/// The given paths are interpreted relative to the current working directory
-/// of the calling process.
+/// The given paths are interpreted relative to their directories if given, or
+/// to the current working directory of the calling process.
 pub(crate) struct RenameAt {
+    #[allow(dead_code)]
+    pub(crate) from_dir: Option<SharedFd>,
     pub(crate) from: CString,
+    #[allow(dead_code)]
+    pub(crate) to_dir: Option<SharedFd>,
     pub(crate) to: CString,
 }
 
 impl Op<RenameAt> {
     /// Submit a request to rename a specified path to a new name with
-    /// the provided flags.
-    pub(crate) fn rename_at(from: &Path, to: &Path, flags: u32) -> io::Result<Op<RenameAt>> {
+    /// the provided flags, each relative to its directory if given.
+    pub(crate) fn rename_at(
+        from_dir: Option<&SharedFd>,
+        from: &Path,
+        to_dir: Option<&SharedFd>,
+        to: &Path,
+        flags: u32,
+    ) -> io::Result<Op<RenameAt>> {
         use io_uring::{opcode, types};
 
         let from = super::util::cstr(from)?;
@@ -24,17 +35,25 @@ impl Op<RenameAt> {
 
         CONTEXT.with(|x| {
             x.handle().expect("Not in a runtime context").submit_op(
-                RenameAt { from, to },
+                RenameAt {
+                    from_dir: from_dir.cloned(),
+                    from,
+                    to_dir: to_dir.cloned(),
+                    to,
+                },
                 |rename| {
                     // Get a reference to the memory. The string will be held by the
                     // operation state and will not be accessed again until the operation
                     // completes.
                     let from_ref = rename.from.as_c_str().as_ptr();
                     let to_ref = rename.to.as_c_str().as_ptr();
+                    let raw = |dir: &Option<SharedFd>| {
+                        dir.as_ref().map_or(libc::AT_FDCWD, |dir| dir.raw_fd())
+                    };
                     opcode::RenameAt::new(
-                        types::Fd(libc::AT_FDCWD),
+                        types::Fd(raw(&rename.from_dir)),
                         from_ref,
-                        types::Fd(libc::AT_FDCWD),
+                        types::Fd(raw(&rename.to_dir)),
                         to_ref,
                     )
                     .flags(flags)
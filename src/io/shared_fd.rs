@@ -1,14 +1,9 @@
 use std::future::poll_fn;
 
-use std::{
-    cell::RefCell,
-    io,
-    os::unix::io::{FromRawFd, RawFd},
-    rc::Rc,
-    task::Waker,
-};
+use std::{cell::RefCell, io, os::unix::io::RawFd, rc::Rc, task::Waker};
 
 use crate::runtime::driver::op::Op;
+use crate::runtime::CONTEXT;
 
 // Tracks in-flight operations on a file descriptor. Ensures all in-flight
 // operations complete before submitting the close.
@@ -58,11 +53,26 @@ impl SharedFd {
         self.inner.fd
     }
 
+    /// Cancels all operations in flight on the FD, returning how many were
+    /// cancelled.
+    pub(crate) async fn cancel_ops(&self) -> io::Result<usize> {
+        Op::cancel_fd(self.inner.fd)?.await
+    }
+
     /// An FD cannot be closed until all in-flight operation have completed.
     /// This prevents bugs where in-flight reads could operate on the incorrect
     /// file descriptor.
     ///
+    /// Operations still in flight are cancelled first, so that operations
+    /// which may never complete on their own, such as a read from a silent
+    /// peer, don't hold up the close.
     pub(crate) async fn close(&mut self) -> io::Result<()> {
+        if Rc::strong_count(&self.inner) > 1 {
+            // Kernels before 5.19 can't cancel by FD, the close then waits
+            // for the operations to complete.
+            let _ = self.cancel_ops().await;
+        }
+
         loop {
             // Get a mutable reference to Inner, indicating there are no
             // in-flight operations on the FD.
@@ -155,6 +165,9 @@ impl Drop for Inner {
         if let State::Closed = *state {
             return;
         }
-        let _ = unsafe { std::fs::File::from_raw_fd(self.fd) };
+        if let Err(e) = syscall!(close(self.fd)) {
+            // The thread may be exiting, in which case there is nobody to tell.
+            let _ = CONTEXT.try_with(|cx| cx.record_close_error(e));
+        }
     }
 }
@@ -1,4 +1,5 @@
 use crate::io::write::UnsubmittedWrite;
+use crate::runtime;
 use crate::runtime::driver::op::{Op, Submit};
 use crate::{
     buf::fixed::FixedBuf,
@@ -6,6 +7,7 @@ use crate::{
     io::SharedFd,
     UnsubmittedOneshot,
 };
+use io_uring::opcode;
 use std::{
     io,
     net::SocketAddr,
@@ -146,6 +148,11 @@ impl Socket {
     }
 
     pub(crate) async fn send_zc<T: BoundedBuf>(&self, buf: T) -> crate::Result<usize, T> {
+        // Zero-copy sends require Linux 6.0.
+        if !runtime::supports(opcode::SendZc::CODE) {
+            return self.send_to(buf, None).await;
+        }
+
         let op = Op::send_zc(&self.fd, buf).unwrap();
         op.await
     }
@@ -166,6 +173,11 @@ impl Socket {
         socket_addr: Option<SocketAddr>,
         msg_control: Option<U>,
     ) -> (io::Result<usize>, Vec<T>, Option<U>) {
+        // Zero-copy sends require Linux 6.1.
+        if !runtime::supports(opcode::SendMsgZc::CODE) {
+            return self.sendmsg(io_slices, socket_addr, msg_control).await;
+        }
+
         let op = Op::sendmsg_zc(&self.fd, io_slices, socket_addr, msg_control).unwrap();
         op.await
     }
@@ -198,6 +210,24 @@ impl Socket {
         op.await
     }
 
+    pub(crate) async fn readable(&self) -> io::Result<()> {
+        let op = Op::poll_add(&self.fd, (libc::POLLIN | libc::POLLRDHUP) as u32)?;
+        op.await.map(drop)
+    }
+
+    pub(crate) async fn cancel_ops(&self) -> io::Result<usize> {
+        self.fd.cancel_ops().await
+    }
+
+    pub(crate) async fn close(mut self) -> io::Result<()> {
+        self.fd.close().await
+    }
+
+    pub(crate) async fn writable(&self) -> io::Result<()> {
+        let op = Op::poll_add(&self.fd, libc::POLLOUT as u32)?;
+        op.await.map(drop)
+    }
+
     pub(crate) async fn accept(&self) -> io::Result<(Socket, Option<SocketAddr>)> {
         let op = Op::accept(&self.fd)?;
         op.await
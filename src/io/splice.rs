@@ -0,0 +1,54 @@
+use std::io;
+
+use io_uring::{opcode, types};
+
+use crate::io::SharedFd;
+use crate::runtime::driver::op::{Completable, CqeResult, Op};
+use crate::runtime::CONTEXT;
+
+/// Move data between two descriptors, one of which is a pipe
+pub(crate) struct Splice {
+    #[allow(dead_code)]
+    fd_in: SharedFd,
+    #[allow(dead_code)]
+    fd_out: SharedFd,
+}
+
+impl Op<Splice> {
+    /// Submit a request to move up to `len` bytes from `fd_in` at `off_in`
+    /// to `fd_out` at `off_out`. The offset of a pipe must be -1.
+    pub(crate) fn splice(
+        fd_in: &SharedFd,
+        off_in: i64,
+        fd_out: &SharedFd,
+        off_out: i64,
+        len: u32,
+    ) -> io::Result<Op<Splice>> {
+        CONTEXT.with(|x| {
+            x.handle().expect("Not in a runtime context").submit_op(
+                Splice {
+                    fd_in: fd_in.clone(),
+                    fd_out: fd_out.clone(),
+                },
+                |splice| {
+                    opcode::Splice::new(
+                        types::Fd(splice.fd_in.raw_fd()),
+                        off_in,
+                        types::Fd(splice.fd_out.raw_fd()),
+                        off_out,
+                        len,
+                    )
+                    .build()
+                },
+            )
+        })
+    }
+}
+
+impl Completable for Splice {
+    type Output = io::Result<usize>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        cqe.result.map(|n| n as usize)
+    }
+}
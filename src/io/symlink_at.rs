@@ -0,0 +1,45 @@
+use crate::runtime::driver::op::{Completable, CqeResult, Op};
+use crate::runtime::CONTEXT;
+
+use super::util::cstr;
+
+use std::ffi::CString;
+use std::io;
+use std::path::Path;
+
+/// Create a symbolic link at a path relative to the current working directory
+/// of the caller's process.
+pub(crate) struct Symlink {
+    pub(crate) _target: CString,
+    pub(crate) _link: CString,
+}
+
+impl Op<Symlink> {
+    /// Submit a request to create a symbolic link to `target` at `link`.
+    pub(crate) fn symlink(target: &Path, link: &Path) -> io::Result<Op<Symlink>> {
+        use io_uring::{opcode, types};
+
+        let _target = cstr(target)?;
+        let _link = cstr(link)?;
+
+        CONTEXT.with(|x| {
+            x.handle().expect("Not in a runtime context").submit_op(
+                Symlink { _target, _link },
+                |symlink| {
+                    let target_ref = symlink._target.as_c_str().as_ptr();
+                    let link_ref = symlink._link.as_c_str().as_ptr();
+
+                    opcode::SymlinkAt::new(types::Fd(libc::AT_FDCWD), target_ref, link_ref).build()
+                },
+            )
+        })
+    }
+}
+
+impl Completable for Symlink {
+    type Output = io::Result<()>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        cqe.result.map(|_| ())
+    }
+}
@@ -0,0 +1,87 @@
+use std::io;
+use std::time::Duration;
+
+use crate::runtime::driver::op::{Completable, CqeResult, Op};
+use crate::runtime::CONTEXT;
+use io_uring::{opcode, types};
+
+pub(crate) struct Timeout {
+    // Read by the kernel once the timeout is submitted
+    ts: Box<types::Timespec>,
+}
+
+impl Op<Timeout> {
+    /// Completes once `duration` has passed.
+    pub(crate) fn timeout(duration: Duration) -> io::Result<Op<Timeout>> {
+        let ts = Box::new(types::Timespec::from(duration));
+
+        CONTEXT.with(|x| {
+            x.handle()
+                .expect("Not in a runtime context")
+                .submit_op(Timeout { ts }, |timeout| {
+                    opcode::Timeout::new(&*timeout.ts).build()
+                })
+        })
+    }
+}
+
+pub(crate) struct TimeoutUpdate {
+    // Read by the kernel once the update is submitted
+    ts: Option<Box<types::Timespec>>,
+}
+
+impl Op<TimeoutUpdate> {
+    /// Moves the expiry of the timeout `target` to `duration` from now
+    /// (`IORING_TIMEOUT_UPDATE`).
+    pub(crate) fn timeout_update(
+        target: &Op<Timeout>,
+        duration: Duration,
+    ) -> io::Result<Op<TimeoutUpdate>> {
+        let ts = Box::new(types::Timespec::from(duration));
+        let target = target.index() as u64;
+
+        CONTEXT.with(|x| {
+            x.handle().expect("Not in a runtime context").submit_op(
+                TimeoutUpdate { ts: Some(ts) },
+                |update| {
+                    let ts: &types::Timespec = update.ts.as_ref().unwrap();
+                    opcode::TimeoutUpdate::new(target, ts).build()
+                },
+            )
+        })
+    }
+
+    /// Removes the timeout `target`, which completes with `ECANCELED`
+    /// (`IORING_OP_TIMEOUT_REMOVE`).
+    pub(crate) fn timeout_remove(target: &Op<Timeout>) -> io::Result<Op<TimeoutUpdate>> {
+        let target = target.index() as u64;
+
+        CONTEXT.with(|x| {
+            x.handle()
+                .expect("Not in a runtime context")
+                .submit_op(TimeoutUpdate { ts: None }, |_| {
+                    opcode::TimeoutRemove::new(target).build()
+                })
+        })
+    }
+}
+
+impl Completable for TimeoutUpdate {
+    type Output = io::Result<()>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        cqe.result.map(|_| ())
+    }
+}
+
+impl Completable for Timeout {
+    type Output = io::Result<()>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        match cqe.result {
+            // The timeout expired
+            Err(e) if e.raw_os_error() == Some(libc::ETIME) => Ok(()),
+            res => res.map(|_| ()),
+        }
+    }
+}
@@ -6,3 +6,47 @@ pub(crate) fn cstr(p: &Path) -> io::Result<CString> {
     use std::os::unix::ffi::OsStrExt;
     Ok(CString::new(p.as_os_str().as_bytes())?)
 }
+
+/// A submission queue entry built field by field, for the operations
+/// `io-uring` has no builder for.
+///
+/// Fields left to their default are zero.
+#[repr(C)]
+#[derive(Default)]
+pub(crate) struct RawSqe {
+    pub(crate) opcode: u8,
+    pub(crate) flags: u8,
+    pub(crate) ioprio: u16,
+    pub(crate) fd: i32,
+    /// The offset, or `addr2`.
+    pub(crate) off: u64,
+    pub(crate) addr: u64,
+    pub(crate) len: u32,
+    /// The flags of the operation, e.g. `xattr_flags` or `rw_flags`.
+    pub(crate) op_flags: u32,
+    pub(crate) user_data: u64,
+    pub(crate) buf_index: u16,
+    pub(crate) personality: u16,
+    pub(crate) file_index: i32,
+    pub(crate) addr3: u64,
+    pub(crate) pad: u64,
+}
+
+impl RawSqe {
+    pub(crate) fn from_entry(sqe: io_uring::squeue::Entry) -> RawSqe {
+        // Safety: an SQE is an io_uring_sqe, which has the same layout.
+        unsafe { std::mem::transmute::<io_uring::squeue::Entry, RawSqe>(sqe) }
+    }
+
+    pub(crate) fn build(self) -> io_uring::squeue::Entry {
+        // Safety: an SQE is an io_uring_sqe, which has the same layout.
+        unsafe { std::mem::transmute::<RawSqe, io_uring::squeue::Entry>(self) }
+    }
+}
+
+// Adds the `RWF_*` flags `flags` to the read or write `sqe`.
+pub(crate) fn add_rw_flags(sqe: io_uring::squeue::Entry, flags: i32) -> io_uring::squeue::Entry {
+    let mut raw = RawSqe::from_entry(sqe);
+    raw.op_flags |= flags as u32;
+    raw.build()
+}
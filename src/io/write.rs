@@ -1,6 +1,8 @@
 use crate::buf::fixed::FixedBuf;
+use crate::io::util::add_rw_flags;
+use crate::io::{DirectFd, FdGuard, SharedFd};
 use crate::WithBuffer;
-use crate::{buf::BoundedBuf, io::SharedFd, OneshotOutputTransform, Result, UnsubmittedOneshot};
+use crate::{buf::BoundedBuf, OneshotOutputTransform, Result, UnsubmittedOneshot};
 use io_uring::cqueue::Entry;
 use std::io;
 use std::marker::PhantomData;
@@ -12,7 +14,7 @@ pub type UnsubmittedWrite<T> = UnsubmittedOneshot<WriteData<T>, WriteTransform<T
 pub struct WriteData<T> {
     /// Holds a strong ref to the FD, preventing the file from being closed
     /// while the operation is in-flight.
-    _fd: SharedFd,
+    _fd: FdGuard,
 
     buf: T,
 }
@@ -47,7 +49,7 @@ impl<T: BoundedBuf> UnsubmittedWrite<T> {
 
         Self::new(
             WriteData {
-                _fd: fd.clone(),
+                _fd: fd.clone().into(),
                 buf,
             },
             WriteTransform {
@@ -60,6 +62,29 @@ impl<T: BoundedBuf> UnsubmittedWrite<T> {
     }
 }
 
+impl<T: BoundedBuf> UnsubmittedWrite<T> {
+    pub(crate) fn write_at_direct(fd: &DirectFd, buf: T, offset: u64) -> Self {
+        use io_uring::{opcode, types};
+
+        // Get raw buffer info
+        let ptr = buf.stable_ptr();
+        let len = buf.bytes_init();
+
+        Self::new(
+            WriteData {
+                _fd: fd.clone_ref().into(),
+                buf,
+            },
+            WriteTransform {
+                _phantom: PhantomData,
+            },
+            opcode::Write::new(types::Fixed(fd.slot()), ptr, len as _)
+                .offset(offset as _)
+                .build(),
+        )
+    }
+}
+
 impl<T: BoundedBuf<Buf = FixedBuf>> UnsubmittedWrite<T> {
     pub(crate) fn write_fixed_at(fd: &SharedFd, buf: T, offset: u64) -> Self {
         use io_uring::{opcode, types};
@@ -71,7 +96,7 @@ impl<T: BoundedBuf<Buf = FixedBuf>> UnsubmittedWrite<T> {
 
         Self::new(
             WriteData {
-                _fd: fd.clone(),
+                _fd: fd.clone().into(),
                 buf,
             },
             WriteTransform {
@@ -99,7 +124,7 @@ impl<T: BoundedBuf> UnsubmittedWrite<T> {
 
         Self::new(
             WriteData {
-                _fd: fd.clone(),
+                _fd: fd.clone().into(),
                 buf,
             },
             WriteTransform {
@@ -111,3 +136,59 @@ impl<T: BoundedBuf> UnsubmittedWrite<T> {
         )
     }
 }
+
+impl<T: BoundedBuf> UnsubmittedWrite<T> {
+    /// Adds the `RWF_*` flags `flags` to the write, as passed to
+    /// `pwritev2(2)`.
+    pub fn rw_flags(mut self, flags: i32) -> Self {
+        self.sqe = add_rw_flags(self.sqe, flags);
+        self
+    }
+
+    /// Make the data of the write durable before it completes, as
+    /// [`File::sync_data`](crate::fs::File::sync_data) does for the whole
+    /// file (`RWF_DSYNC`).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    /// use tokio_uring::Submit;
+    ///
+    /// tokio_uring::start(async {
+    ///     let file = File::create("journal").await.unwrap();
+    ///
+    ///     // The record is on stable storage once the write completes.
+    ///     file.write_at(b"commit".to_vec(), 0).dsync().submit().await.unwrap();
+    /// });
+    /// ```
+    pub fn dsync(self) -> Self {
+        self.rw_flags(libc::RWF_DSYNC)
+    }
+
+    /// Make the data and the metadata of the write durable before it
+    /// completes, as [`File::sync_all`](crate::fs::File::sync_all) does for
+    /// the whole file (`RWF_SYNC`).
+    pub fn sync(self) -> Self {
+        self.rw_flags(libc::RWF_SYNC)
+    }
+
+    /// Fail the write with `EAGAIN` rather than wait, e.g. for the allocation
+    /// of blocks (`RWF_NOWAIT`).
+    pub fn nowait(self) -> Self {
+        self.rw_flags(libc::RWF_NOWAIT)
+    }
+
+    /// Poll for the completion of the write rather than wait for an
+    /// interrupt (`RWF_HIPRI`), on files opened with `O_DIRECT` on devices
+    /// with polled queues.
+    pub fn hipri(self) -> Self {
+        self.rw_flags(libc::RWF_HIPRI)
+    }
+
+    /// Write at the end of the file, ignoring the offset, as if the file was
+    /// opened with `O_APPEND` (`RWF_APPEND`).
+    pub fn append(self) -> Self {
+        self.rw_flags(libc::RWF_APPEND)
+    }
+}
@@ -33,7 +33,7 @@ pub(crate) async fn writev_at_all<T: BoundedBuf>(
         })
         .collect();
 
-    let mut iovs_ptr = iovs.as_ptr();
+    let mut iovs_ptr = iovs.as_mut_ptr();
     let mut iovs_len: u32 = iovs.len() as _;
 
     let mut total: usize = 0;
@@ -72,8 +72,9 @@ pub(crate) async fn writev_at_all<T: BoundedBuf>(
 
         // Consume n and iovs_len until one or the other is exhausted.
         while n != 0 && iovs_len > 0 {
-            // safety: iovs_len > 0, so safe to dereference the const *.
-            let mut iovec = unsafe { *iovs_ptr };
+            // safety: iovs_len > 0, so safe to dereference the pointer. The iovec is updated in
+            // place so a partial write is reflected in the next call to writev.
+            let iovec = unsafe { &mut *iovs_ptr };
             let iov_len = iovec.iov_len;
             if n >= iov_len {
                 n -= iov_len;
@@ -0,0 +1,148 @@
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::io;
+
+use crate::io::util::RawSqe;
+use crate::io::SharedFd;
+use crate::runtime::driver::op::{Completable, CqeResult, Op};
+use crate::runtime::CONTEXT;
+
+// The opcodes of the extended attribute operations, which `io-uring` has no
+// builders for.
+const IORING_OP_FSETXATTR: u8 = 41;
+const IORING_OP_SETXATTR: u8 = 42;
+const IORING_OP_FGETXATTR: u8 = 43;
+const IORING_OP_GETXATTR: u8 = 44;
+
+/// The file an extended attribute belongs to: an open file, or a path
+/// relative to the current working directory of the caller's process.
+#[derive(Clone)]
+pub(crate) enum XattrTarget {
+    Fd(SharedFd),
+    Path(CString),
+}
+
+impl XattrTarget {
+    // Returns the opcode of the operation on the target, and the descriptor
+    // and path to pass it.
+    fn sqe_parts(&self, fd_op: u8, path_op: u8) -> (u8, i32, u64) {
+        match self {
+            XattrTarget::Fd(fd) => (fd_op, fd.raw_fd(), 0),
+            XattrTarget::Path(path) => (path_op, libc::AT_FDCWD, path.as_ptr() as u64),
+        }
+    }
+}
+
+/// Get the value of an extended attribute
+pub(crate) struct GetXattr {
+    target: XattrTarget,
+    name: CString,
+    value: Vec<u8>,
+}
+
+impl Op<GetXattr> {
+    /// Submit a request to read the value of the attribute `name` into a
+    /// buffer of `size` bytes, or only its size if `size` is zero.
+    pub(crate) fn get_xattr(
+        target: XattrTarget,
+        name: CString,
+        size: u32,
+    ) -> io::Result<Op<GetXattr>> {
+        CONTEXT.with(|x| {
+            x.handle().expect("Not in a runtime context").submit_op(
+                GetXattr {
+                    target,
+                    name,
+                    value: Vec::with_capacity(size as usize),
+                },
+                |get| {
+                    let (opcode, fd, path) = get
+                        .target
+                        .sqe_parts(IORING_OP_FGETXATTR, IORING_OP_GETXATTR);
+                    RawSqe {
+                        opcode,
+                        fd,
+                        addr: get.name.as_ptr() as u64,
+                        // The value is passed as `addr2`.
+                        off: get.value.as_mut_ptr() as u64,
+                        len: size,
+                        addr3: path,
+                        ..Default::default()
+                    }
+                    .build()
+                },
+            )
+        })
+    }
+}
+
+impl Completable for GetXattr {
+    type Output = io::Result<(usize, Vec<u8>)>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        let n = cqe.result? as usize;
+        let mut value = self.value;
+        // The kernel returns the size of the value without reading it if the
+        // buffer is empty.
+        if value.capacity() > 0 {
+            // Safety: the kernel initialized the first `n` bytes of the buffer.
+            unsafe { value.set_len(n) };
+        }
+        Ok((n, value))
+    }
+}
+
+/// Set the value of an extended attribute
+pub(crate) struct SetXattr {
+    target: XattrTarget,
+    name: CString,
+    value: Vec<u8>,
+}
+
+impl Op<SetXattr> {
+    /// Submit a request to set the attribute `name` to `value`, with the
+    /// `XATTR_*` flags `flags`.
+    pub(crate) fn set_xattr(
+        target: XattrTarget,
+        name: CString,
+        value: Vec<u8>,
+        flags: i32,
+    ) -> io::Result<Op<SetXattr>> {
+        let len =
+            u32::try_from(value.len()).map_err(|_| io::Error::from_raw_os_error(libc::E2BIG))?;
+
+        CONTEXT.with(|x| {
+            x.handle().expect("Not in a runtime context").submit_op(
+                SetXattr {
+                    target,
+                    name,
+                    value,
+                },
+                |set| {
+                    let (opcode, fd, path) = set
+                        .target
+                        .sqe_parts(IORING_OP_FSETXATTR, IORING_OP_SETXATTR);
+                    RawSqe {
+                        opcode,
+                        fd,
+                        addr: set.name.as_ptr() as u64,
+                        off: set.value.as_ptr() as u64,
+                        len,
+                        op_flags: flags as u32,
+                        addr3: path,
+                        ..Default::default()
+                    }
+                    .build()
+                },
+            )
+        })
+    }
+}
+
+impl Completable for SetXattr {
+    type Output = io::Result<()>;
+
+    fn complete(self, cqe: CqeResult) -> Self::Output {
+        cqe.result.map(|_| ())
+    }
+}
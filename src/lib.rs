@@ -55,6 +55,20 @@
 //! will happen in the background. There is no guarantee as to **when** the
 //! implicit close-on-drop operation happens, so it is recommended to explicitly
 //! call `close()`.
+//!
+//! # Kernel support
+//!
+//! There is no backend besides `io-uring`: the crate only builds for Linux,
+//! and starting a runtime fails where `io-uring` is unavailable, with
+//! `ENOSYS` on kernels built without it, or `EPERM` where it is disabled
+//! through the `kernel.io_uring_disabled` sysctl. Within a running runtime,
+//! operations the kernel lacks fail with `EINVAL`, except for the few with a
+//! fallback, such as zero-copy sends.
+//!
+//! Crates which must also run elsewhere, e.g. on macOS or on older CI
+//! kernels, should put their use of `tokio-uring` behind
+//! `#[cfg(target_os = "linux")]`, and fall back to Tokio's own `fs` and `net`
+//! types where [`probe`] fails or reports missing operations.
 
 #![warn(missing_docs)]
 
@@ -71,7 +85,7 @@ macro_rules! syscall {
 
 #[macro_use]
 mod future;
-mod io;
+pub mod io;
 #[allow(missing_docs)]
 pub mod runtime;
 mod types;
@@ -79,17 +93,24 @@ mod types;
 pub mod buf;
 pub mod fs;
 pub mod net;
+pub mod process;
+pub mod time;
 
+pub use io::nvme::*;
 pub use io::read::*;
 pub use io::readv::*;
 pub use io::write::*;
 pub use io::writev::*;
 pub use runtime::driver::op::{
-    InFlightOneshot, Link, LinkedInFlightOneshot, OneshotOutputTransform, Submit,
-    UnsubmittedOneshot,
+    BigOutputTransform, InFlightBig, InFlightOneshot, Link, LinkedInFlightOneshot,
+    OneshotOutputTransform, Submit, UnsubmittedBig, UnsubmittedOneshot,
 };
+pub use runtime::probe;
 pub use runtime::spawn;
+pub use runtime::with_ring;
+pub use runtime::Driver;
 pub use runtime::Runtime;
+pub use runtime::RuntimeHandle;
 pub use types::*;
 
 use crate::runtime::driver::op::Op;
@@ -169,12 +190,88 @@ pub fn uring_builder() -> io_uring::Builder {
 
 /// Builder API that can create and start the `io_uring` runtime with non-default parameters,
 /// while abstracting away the underlying io_uring crate.
-// #[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct Builder {
     entries: u32,
     urb: io_uring::Builder,
+    custom_urb: bool,
+    sqe128: bool,
+    cqe32: bool,
+    direct_descriptors: Option<u32>,
+    cqe_batch: usize,
+    submit_batch: usize,
+    completion_budget: usize,
+    cqe_repeek: bool,
+    on_driver_error: Option<ErrorCallback>,
+    on_park: Option<ParkCallback>,
+    on_unpark: Option<ParkCallback>,
+    restrictions: Option<runtime::Restrictions>,
+    attach_wq: Option<std::sync::Weak<std::os::unix::io::OwnedFd>>,
+    iowq_affinity: Option<IowqAffinity>,
+    pin_to_cpu: Option<usize>,
+    sqpoll: Option<u32>,
+    sq_thread_cpu: Option<u32>,
+    iopoll: bool,
+    single_issuer: bool,
+    defer_taskrun: bool,
+    coop_taskrun: bool,
+    napi: Option<(u32, bool)>,
+    busy_poll: Option<(runtime::BusyPoll, bool)>,
+    shutdown_timeout: Option<std::time::Duration>,
+    cq_overflow: runtime::CqOverflow,
+    preallocate_ops: usize,
+    max_in_flight: Option<usize>,
+    op_timeout: Option<std::time::Duration>,
+    #[cfg(feature = "mock")]
+    mock: bool,
+}
+
+#[derive(Clone)]
+enum IowqAffinity {
+    Thread,
+    Cpus(Vec<usize>),
+}
+
+impl IowqAffinity {
+    fn cpu_set(&self) -> std::io::Result<libc::cpu_set_t> {
+        // Safety: cpu_set_t is a plain bit mask.
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        match self {
+            IowqAffinity::Thread => {
+                let size = std::mem::size_of::<libc::cpu_set_t>();
+                syscall!(sched_getaffinity(0, size, &mut set))?;
+            }
+            IowqAffinity::Cpus(cpus) => return cpu_set(cpus),
+        }
+        Ok(set)
+    }
 }
 
+fn cpu_set(cpus: &[usize]) -> std::io::Result<libc::cpu_set_t> {
+    // Safety: cpu_set_t is a plain bit mask.
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &cpu in cpus {
+        if cpu >= libc::CPU_SETSIZE as usize {
+            return Err(std::io::Error::from_raw_os_error(libc::EINVAL));
+        }
+        // Safety: the index is within the set.
+        unsafe { libc::CPU_SET(cpu, &mut set) };
+    }
+    Ok(set)
+}
+
+/// Pins the calling thread to `cpu`.
+fn pin_thread(cpu: usize) -> std::io::Result<()> {
+    let set = cpu_set(&[cpu])?;
+    let size = std::mem::size_of::<libc::cpu_set_t>();
+    syscall!(sched_setaffinity(0, size, &set))?;
+    Ok(())
+}
+
+type ErrorCallback = std::sync::Arc<dyn Fn(&runtime::DriverError) + Send + Sync>;
+
+type ParkCallback = std::sync::Arc<dyn Fn() + Send + Sync>;
+
 /// Constructs a [`Builder`] with default settings.
 ///
 /// Use this to alter submission and completion queue parameters, and to create the io_uring
@@ -185,6 +282,36 @@ pub fn builder() -> Builder {
     Builder {
         entries: 256,
         urb: io_uring::IoUring::builder(),
+        custom_urb: false,
+        sqe128: false,
+        cqe32: false,
+        direct_descriptors: None,
+        cqe_batch: usize::MAX,
+        submit_batch: usize::MAX,
+        completion_budget: usize::MAX,
+        cqe_repeek: false,
+        on_driver_error: None,
+        on_park: None,
+        on_unpark: None,
+        restrictions: None,
+        attach_wq: None,
+        iowq_affinity: None,
+        pin_to_cpu: None,
+        sqpoll: None,
+        sq_thread_cpu: None,
+        iopoll: false,
+        single_issuer: false,
+        defer_taskrun: false,
+        coop_taskrun: false,
+        napi: None,
+        busy_poll: None,
+        shutdown_timeout: None,
+        cq_overflow: runtime::CqOverflow::Buffer,
+        preallocate_ops: 64,
+        max_in_flight: None,
+        op_timeout: None,
+        #[cfg(feature = "mock")]
+        mock: false,
     }
 }
 
@@ -200,12 +327,680 @@ impl Builder {
         self
     }
 
+    /// Sets the maximum number of completion queue entries the driver reaps
+    /// before handing the consumed slots back to the kernel and peeking at the
+    /// completion queue again.
+    ///
+    /// Smaller batches release completion queue space to the kernel sooner,
+    /// which helps latency-sensitive servers running with a small completion
+    /// queue. Larger batches amortize the synchronization with the kernel over
+    /// more completions, which suits throughput-oriented jobs.
+    ///
+    /// The default is unbounded: every completion visible when the driver wakes
+    /// is processed in a single batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch` is zero.
+    pub fn cqe_batch(&mut self, batch: usize) -> &mut Self {
+        assert!(batch > 0, "cqe_batch must be greater than zero");
+        self.cqe_batch = batch;
+        self
+    }
+
+    /// Sets the maximum number of completions the driver processes before
+    /// yielding to other tasks on the runtime thread.
+    ///
+    /// A flood of completions is otherwise processed all at once, which can
+    /// hold up timers and other tasks. Unlike [`cqe_batch`](Self::cqe_batch),
+    /// which only paces the hand-off of completion queue space to the kernel,
+    /// the budget lets other tasks run before the rest are processed.
+    ///
+    /// The default is unbounded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `budget` is zero.
+    pub fn completion_budget(&mut self, budget: usize) -> &mut Self {
+        assert!(budget > 0, "completion_budget must be greater than zero");
+        self.completion_budget = budget;
+        self
+    }
+
+    /// Sets the number of submission queue entries which accumulate before the
+    /// driver submits them to the kernel.
+    ///
+    /// Operations are otherwise submitted once the runtime has no more tasks
+    /// to run, or when the submission queue fills up. Smaller batches start
+    /// operations sooner, larger ones amortize the system call over more
+    /// operations. [`runtime::flush`] submits pending operations on demand.
+    ///
+    /// The default is unbounded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch` is zero.
+    pub fn submit_batch(&mut self, batch: usize) -> &mut Self {
+        assert!(batch > 0, "submit_batch must be greater than zero");
+        self.submit_batch = batch;
+        self
+    }
+
+    /// Sets whether the driver peeks at the completion queue one more time
+    /// after draining it, before going back to sleep.
+    ///
+    /// Re-peeking picks up completions which arrived while the previous ones
+    /// were being processed, at the cost of an extra check when the queue is
+    /// idle.
+    ///
+    /// The default is `false`.
+    pub fn cqe_repeek(&mut self, repeek: bool) -> &mut Self {
+        self.cqe_repeek = repeek;
+        self
+    }
+
+    /// Installs a callback invoked on internal driver errors.
+    ///
+    /// Some failures of the driver are not tied to any operation: submitting
+    /// entries to the kernel while the runtime parks, completions the driver
+    /// does not recognize, or failed cancellations. By default these are
+    /// swallowed or, if the driver cannot proceed, cause a panic. The callback
+    /// receives a [`DriverError`] describing the condition, e.g. to log it or
+    /// raise an alert, before the driver carries on as it would otherwise.
+    ///
+    /// The callback runs from within the driver, and must not submit
+    /// operations on the runtime.
+    ///
+    /// [`DriverError`]: runtime::DriverError
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// tokio_uring::builder()
+    ///     .on_driver_error(|e| eprintln!("io_uring driver error: {}", e))
+    ///     .start(async {
+    ///         // ...
+    ///     });
+    /// ```
+    pub fn on_driver_error<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(&runtime::DriverError) + Send + Sync + 'static,
+    {
+        self.on_driver_error = Some(std::sync::Arc::new(f));
+        self
+    }
+
+    /// Installs a callback invoked before the runtime parks, waiting for
+    /// completions or other events.
+    ///
+    /// The runtime parks once it has no task left to run. The callback runs
+    /// after the pending submissions were flushed to the kernel, right
+    /// before the thread blocks, which makes it the place to flush metrics,
+    /// advance an epoch, or spin for a while before sleeping. The runtime
+    /// never parks while busy polling, see [`Builder::busy_poll`].
+    ///
+    /// The callback runs on the runtime thread, outside of any task, and
+    /// must not submit operations on the runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let parks = Arc::new(AtomicU64::new(0));
+    /// let counter = parks.clone();
+    ///
+    /// tokio_uring::builder()
+    ///     .on_park(move || {
+    ///         counter.fetch_add(1, Ordering::Relaxed);
+    ///     })
+    ///     .start(async {
+    ///         // ...
+    ///     });
+    ///
+    /// println!("parked {} times", parks.load(Ordering::Relaxed));
+    /// ```
+    pub fn on_park<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_park = Some(std::sync::Arc::new(f));
+        self
+    }
+
+    /// Installs a callback invoked once the runtime wakes up after parking,
+    /// before it runs any task.
+    ///
+    /// See [`Builder::on_park`].
+    pub fn on_unpark<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_unpark = Some(std::sync::Arc::new(f));
+        self
+    }
+
+    /// Enables kernel-side submission polling (`IORING_SETUP_SQPOLL`).
+    ///
+    /// A kernel thread polls the submission queue and picks up operations as
+    /// soon as they are queued, so the runtime does not need a system call to
+    /// submit them. After `idle_ms` milliseconds without work the thread goes
+    /// to sleep, and the runtime wakes it on the next submission.
+    ///
+    /// This trades a CPU busy polling for lower submission latency. Requires
+    /// Linux 5.11 or later for unprivileged use.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// tokio_uring::builder().sqpoll(2000).start(async {
+    ///     // Operations are picked up by the kernel's polling thread.
+    /// });
+    /// ```
+    pub fn sqpoll(&mut self, idle_ms: u32) -> &mut Self {
+        self.sqpoll = Some(idle_ms);
+        self
+    }
+
+    /// Sets how long the submission polling thread spins without work before
+    /// it goes to sleep, enabling submission polling.
+    ///
+    /// This is [`sqpoll`](Builder::sqpoll) with the idle time given as a
+    /// [`Duration`](std::time::Duration), rounded down to milliseconds. A
+    /// sleeping thread is woken by the runtime on the next submission.
+    pub fn sq_thread_idle(&mut self, idle: std::time::Duration) -> &mut Self {
+        let idle_ms = std::cmp::min(idle.as_millis(), u128::from(u32::MAX)) as u32;
+        self.sqpoll(idle_ms)
+    }
+
+    /// Pins the submission polling thread to `cpu` (`IORING_SETUP_SQ_AFF`).
+    ///
+    /// This only takes effect with submission polling enabled through
+    /// [`sqpoll`](Builder::sqpoll) or [`sq_thread_idle`](Builder::sq_thread_idle).
+    /// Creating the runtime fails if the CPU is offline or out of range.
+    pub fn sq_thread_cpu(&mut self, cpu: u32) -> &mut Self {
+        self.sq_thread_cpu = Some(cpu);
+        self
+    }
+
+    /// Lets operations still in flight when the runtime shuts down complete
+    /// for up to `timeout` before they are cancelled.
+    ///
+    /// The runtime shuts down once the future passed to
+    /// [`start`](Builder::start) completes, or when it is dropped or
+    /// [shut down](Runtime::shutdown). Its tasks are dropped, and by default
+    /// the operations they left in flight are cancelled right away. With a
+    /// shutdown timeout, the runtime first waits for these operations to
+    /// complete, e.g. so that writes reach the file. Operations still in
+    /// flight at the deadline are cancelled, and the runtime waits for the
+    /// kernel to release them. The [`ShutdownReport`](runtime::ShutdownReport)
+    /// tells the operations which completed from those which were cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// tokio_uring::builder()
+    ///     .shutdown_timeout(Duration::from_secs(5))
+    ///     .start(async {
+    ///         // Operations left in flight get 5 seconds to complete.
+    ///     });
+    /// ```
+    pub fn shutdown_timeout(&mut self, timeout: std::time::Duration) -> &mut Self {
+        self.shutdown_timeout = Some(timeout);
+        self
+    }
+
+    /// Busy-polls network devices for completions of socket operations
+    /// (`IORING_REGISTER_NAPI`).
+    ///
+    /// When the runtime waits for network completions, the kernel polls the
+    /// receive queues of the sockets involved for up to `timeout` instead of
+    /// waiting for an interrupt, trading CPU time for latency. With
+    /// `prefer_busy_poll`, device interrupts are deferred while polling.
+    /// The timeout is rounded down to microseconds. Creating the runtime
+    /// fails on kernels without NAPI support, before Linux 6.9.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// tokio_uring::builder()
+    ///     .napi_busy_poll(Duration::from_micros(50), true)
+    ///     .start(async {
+    ///         // Socket completions are busy-polled.
+    ///     });
+    /// ```
+    pub fn napi_busy_poll(
+        &mut self,
+        timeout: std::time::Duration,
+        prefer_busy_poll: bool,
+    ) -> &mut Self {
+        let timeout = std::cmp::min(timeout.as_micros(), u128::from(u32::MAX)) as u32;
+        self.napi = Some((timeout, prefer_busy_poll));
+        self
+    }
+
+    /// Spins on the completion queue instead of going to sleep until the ring
+    /// signals completions.
+    ///
+    /// This is for latency targets in the microseconds, where waking up the
+    /// runtime thread dominates. See [`BusyPoll`](runtime::BusyPoll) for the
+    /// modes. With `pause`, the runtime issues a spin-loop hint (such as the
+    /// x86 `pause` instruction) every time around, which saves power and
+    /// frees resources for a sibling hyper-thread.
+    ///
+    /// Has no effect on rings set up for I/O polling, which are always
+    /// polled, nor with [`Driver::attach`](crate::Driver::attach).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use tokio_uring::runtime::BusyPoll;
+    ///
+    /// tokio_uring::builder()
+    ///     .busy_poll(BusyPoll::Timeout(Duration::from_millis(1)), true)
+    ///     .start(async {
+    ///         // Completions are picked up without waking the thread, until
+    ///         // none arrived for a millisecond.
+    ///     });
+    /// ```
+    pub fn busy_poll(&mut self, mode: runtime::BusyPoll, pause: bool) -> &mut Self {
+        self.busy_poll = Some((mode, pause));
+        self
+    }
+
+    /// Sets up the ring for busy-polled I/O (`IORING_SETUP_IOPOLL`).
+    ///
+    /// Instead of waiting for the device to raise an interrupt, the runtime
+    /// polls for completions for as long as operations are in flight. This
+    /// removes interrupt overhead from `O_DIRECT` workloads on fast devices,
+    /// such as NVMe drives with poll queues, at the cost of a busy CPU.
+    ///
+    /// Only reads and writes on files opened with `O_DIRECT`, on filesystems
+    /// and devices supporting polling, can be submitted to such a ring; other
+    /// operations, including opening files, fail with `EINVAL` or
+    /// `EOPNOTSUPP`. Open files with the standard library and convert them
+    /// with [`File::from_std`](fs::File::from_std).
+    ///
+    /// The default is `false`.
+    pub fn iopoll(&mut self, iopoll: bool) -> &mut Self {
+        self.iopoll = iopoll;
+        self
+    }
+
+    /// Hints the kernel that only the runtime thread submits to the ring
+    /// (`IORING_SETUP_SINGLE_ISSUER`), which lets it skip some locking.
+    ///
+    /// The runtime already submits from its own thread only, so this is safe
+    /// to enable. Requires Linux 6.0 or later.
+    ///
+    /// The default is `false`.
+    pub fn single_issuer(&mut self, single_issuer: bool) -> &mut Self {
+        self.single_issuer = single_issuer;
+        self
+    }
+
+    /// Defers completion work until the runtime asks for completions
+    /// (`IORING_SETUP_DEFER_TASKRUN`).
+    ///
+    /// By default, the kernel interrupts the runtime thread to post
+    /// completions as soon as operations finish. With this set, completions
+    /// are only posted when the runtime collects them, batching the work and
+    /// avoiding inter-processor interrupts. Implies
+    /// [`single_issuer`](Builder::single_issuer). Requires Linux 6.1 or later.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// tokio_uring::builder().defer_taskrun(true).start(async {
+    ///     // Completions are posted when the runtime collects them.
+    /// });
+    /// ```
+    pub fn defer_taskrun(&mut self, defer_taskrun: bool) -> &mut Self {
+        self.defer_taskrun = defer_taskrun;
+        self
+    }
+
+    /// Stops the kernel from interrupting the runtime thread to post
+    /// completions (`IORING_SETUP_COOP_TASKRUN`).
+    ///
+    /// Completions are instead posted the next time the thread enters the
+    /// kernel, such as when the runtime parks or submits. The kernel flags
+    /// pending completions (`IORING_SETUP_TASKRUN_FLAG`), and the driver
+    /// collects them before reaping the completion queue. Requires Linux 5.19
+    /// or later.
+    ///
+    /// The default is `false`.
+    pub fn coop_taskrun(&mut self, coop_taskrun: bool) -> &mut Self {
+        self.coop_taskrun = coop_taskrun;
+        self
+    }
+
+    /// Sets what the runtime does when its completion queue overflows.
+    ///
+    /// See [`CqOverflow`](runtime::CqOverflow) for the policies. The default
+    /// is [`CqOverflow::Buffer`](runtime::CqOverflow::Buffer).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::runtime::CqOverflow;
+    ///
+    /// tokio_uring::builder()
+    ///     .cq_overflow(CqOverflow::Backpressure)
+    ///     .start(async {
+    ///         // Submissions wait for completions rather than overflow.
+    ///     });
+    /// ```
+    pub fn cq_overflow(&mut self, policy: runtime::CqOverflow) -> &mut Self {
+        self.cq_overflow = policy;
+        self
+    }
+
+    /// Sets whether operations go to a mock backend rather than the kernel.
+    ///
+    /// The operations submitted by the runtime are recorded, and only
+    /// complete once completed with [`MockDriver`](runtime::MockDriver). This
+    /// lets tests of code built on `tokio-uring` simulate short reads,
+    /// errors and overflows deterministically. The ring is still set up, but
+    /// nothing is submitted to it.
+    ///
+    /// Requires the `mock` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::runtime::MockDriver;
+    ///
+    /// tokio_uring::builder().mock(true).start(async {
+    ///     let mock = MockDriver::current();
+    ///     // ...
+    /// });
+    /// ```
+    #[cfg(feature = "mock")]
+    pub fn mock(&mut self, mock: bool) -> &mut Self {
+        self.mock = mock;
+        self
+    }
+
+    /// Sets the number of operations the runtime allocates state for up
+    /// front.
+    ///
+    /// The state of operations in flight is kept in storage which grows as
+    /// needed. Preallocating it for the expected peak avoids reallocating it
+    /// while serving. Together with [`max_in_flight`](Builder::max_in_flight)
+    /// this bounds the memory used for operations.
+    ///
+    /// The default is 64.
+    pub fn preallocate_ops(&mut self, n: usize) -> &mut Self {
+        self.preallocate_ops = n;
+        self
+    }
+
+    /// Caps the number of operations in flight on the runtime at once.
+    ///
+    /// Submitting beyond the cap fails with
+    /// [`SubmitError::InFlightLimit`],
+    /// which operations returning `io::Error` carry with
+    /// `io::ErrorKind::WouldBlock`, and `submit()` panics with. To wait for
+    /// room instead, acquire an [`OpPermit`](runtime::OpPermit) with
+    /// [`op_permit`](runtime::op_permit) before submitting. Operations whose
+    /// futures were dropped count until they complete.
+    ///
+    /// The default is uncapped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// tokio_uring::builder()
+    ///     .preallocate_ops(1024)
+    ///     .max_in_flight(1024)
+    ///     .start(async {
+    ///         let permit = tokio_uring::runtime::op_permit().await;
+    ///         permit.run(tokio_uring::no_op()).await.unwrap();
+    ///     });
+    /// ```
+    pub fn max_in_flight(&mut self, n: usize) -> &mut Self {
+        self.max_in_flight = Some(n);
+        self
+    }
+
+    /// Sets a deadline for every operation submitted to the runtime, after
+    /// which it fails with `ETIMEDOUT`.
+    ///
+    /// Each operation is submitted with a timeout linked to it
+    /// (`IORING_OP_LINK_TIMEOUT`), which cancels it once expired. This keeps
+    /// a misbehaving disk or peer from holding the buffers and file
+    /// descriptors of an operation forever. Operations override it with
+    /// [`UnsubmittedOneshot::timeout`] and [`UnsubmittedOneshot::no_timeout`].
+    ///
+    /// Operations which wait for events by design, such as accepting
+    /// connections and polling for readiness, only get a deadline of their
+    /// own. Nor do operations linked to others, or submitted with big
+    /// entries.
+    ///
+    /// The default is no deadline.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// tokio_uring::builder()
+    ///     .op_timeout(Duration::from_secs(30))
+    ///     .start(async {
+    ///         // Operations taking longer than 30 seconds fail.
+    ///     });
+    /// ```
+    pub fn op_timeout(&mut self, timeout: std::time::Duration) -> &mut Self {
+        self.op_timeout = Some(timeout);
+        self
+    }
+
+    /// Restricts the ring to an allow-list of operations.
+    ///
+    /// The ring is created disabled, the restrictions are installed, and the
+    /// ring is enabled before the runtime starts. Operations outside of the
+    /// list then fail with `EACCES`. Requires Linux 5.10 or later.
+    ///
+    /// See [`Restrictions`](runtime::Restrictions) for details and an example.
+    pub fn restrictions(&mut self, restrictions: &runtime::Restrictions) -> &mut Self {
+        self.restrictions = Some(restrictions.clone());
+        self
+    }
+
+    /// Shares the async worker pool of another runtime's ring.
+    ///
+    /// Operations which cannot complete inline, such as buffered file I/O,
+    /// are punted by the kernel to a pool of worker threads. By default every
+    /// ring gets its own pool, so a process running one runtime per core can
+    /// end up with a large number of kernel workers. Attaching the ring to the
+    /// pool of the runtime behind `handle` (`IORING_SETUP_ATTACH_WQ`) makes
+    /// them share a single pool.
+    ///
+    /// The runtime behind `handle` must still be running when this runtime is
+    /// created, otherwise creation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let primary = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    /// let handle = primary.handle();
+    ///
+    /// let secondary = std::thread::spawn(move || {
+    ///     tokio_uring::builder().attach_wq(&handle).start(async {
+    ///         // Blocking work is handled by the workers of `primary`.
+    ///     })
+    /// });
+    /// # secondary.join().unwrap();
+    /// ```
+    pub fn attach_wq(&mut self, handle: &RuntimeHandle) -> &mut Self {
+        self.attach_wq = Some(handle.ring_fd().clone());
+        self
+    }
+
+    /// Pins the kernel's async workers of the ring to the given CPUs.
+    ///
+    /// Operations which cannot complete inline are handed to kernel worker
+    /// threads, which by default may run on any CPU. Restricting them keeps
+    /// the work of the ring on the CPUs of a thread-per-core deployment
+    /// (`IORING_REGISTER_IOWQ_AFF`). Requires Linux 5.14 or later.
+    ///
+    /// Creating the runtime fails if a CPU is out of range or offline.
+    pub fn iowq_cpus(&mut self, cpus: &[usize]) -> &mut Self {
+        self.iowq_affinity = Some(IowqAffinity::Cpus(cpus.to_vec()));
+        self
+    }
+
+    /// Pins the kernel's async workers of the ring to the CPUs the runtime
+    /// thread may run on.
+    ///
+    /// The affinity of the thread creating the runtime is captured when the
+    /// runtime is created, so pin the thread before, or with
+    /// [`pin_to_cpu`](Builder::pin_to_cpu). See
+    /// [`iowq_cpus`](Builder::iowq_cpus).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// std::thread::spawn(|| {
+    ///     // Pin this thread, e.g. with sched_setaffinity, then:
+    ///     tokio_uring::builder().iowq_thread_affinity().start(async {
+    ///         // Blocking work stays on the CPUs of this thread.
+    ///     })
+    /// });
+    /// ```
+    pub fn iowq_thread_affinity(&mut self) -> &mut Self {
+        self.iowq_affinity = Some(IowqAffinity::Thread);
+        self
+    }
+
+    /// Pins the thread running the runtime to CPU `cpu`.
+    ///
+    /// The thread creating the runtime is pinned as it does, which saves
+    /// thread-per-core deployments pinning each thread before creating its
+    /// runtime. The workers of a [`MultiThreadRuntime`] are pinned to
+    /// consecutive CPUs, worker `i` to CPU `cpu + i`.
+    ///
+    /// Creating the runtime fails if the CPU is out of range or offline.
+    ///
+    /// [`MultiThreadRuntime`]: runtime::MultiThreadRuntime
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let threads: Vec<_> = (0..4)
+    ///     .map(|cpu| {
+    ///         std::thread::spawn(move || {
+    ///             tokio_uring::builder().pin_to_cpu(cpu).start(async {
+    ///                 // Runs on CPU `cpu` only.
+    ///             })
+    ///         })
+    ///     })
+    ///     .collect();
+    ///
+    /// for thread in threads {
+    ///     thread.join().unwrap();
+    /// }
+    /// ```
+    pub fn pin_to_cpu(&mut self, cpu: usize) -> &mut Self {
+        self.pin_to_cpu = Some(cpu);
+        self
+    }
+
+    /// Sets up the ring with 128 byte submission queue entries
+    /// (`IORING_SETUP_SQE128`), as needed by `IORING_OP_URING_CMD` passthrough
+    /// commands carrying a command of more than 16 bytes, NVMe's among them.
+    ///
+    /// Every operation works on such a ring, and big entries can be submitted
+    /// with [`UnsubmittedBig`]. Each entry takes twice the memory, and
+    /// [`with_ring`] is unavailable.
+    ///
+    /// Creating the runtime fails with `InvalidInput` if a custom
+    /// [`uring_builder`] is set as well, and with `EINVAL` on kernels before
+    /// 5.19.
+    ///
+    /// [`uring_builder`]: Builder::uring_builder
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// tokio_uring::builder().sqe128().cqe32().start(async {
+    ///     // Passthrough commands can be submitted here.
+    /// });
+    /// ```
+    pub fn sqe128(&mut self) -> &mut Self {
+        self.sqe128 = true;
+        self
+    }
+
+    /// Sets up the ring with 32 byte completion queue entries
+    /// (`IORING_SETUP_CQE32`), whose extra 16 bytes carry the results of
+    /// `IORING_OP_URING_CMD` passthrough commands.
+    ///
+    /// Every operation works on such a ring, and the extra bytes of a
+    /// completion can be read with [`UnsubmittedBig`]. [`with_ring`] is
+    /// unavailable.
+    ///
+    /// Creating the runtime fails with `InvalidInput` if a custom
+    /// [`uring_builder`] is set as well, and with `EINVAL` on kernels before
+    /// 5.19.
+    ///
+    /// [`uring_builder`]: Builder::uring_builder
+    pub fn cqe32(&mut self) -> &mut Self {
+        self.cqe32 = true;
+        self
+    }
+
+    /// Sets up a table of `n` direct descriptors, files registered with the
+    /// ring rather than installed in the file table of the process.
+    ///
+    /// Operations on a [`DirectFd`] skip looking up the file in the file
+    /// table of the process, which threads of a process share and contend
+    /// on. This pays off for servers with many connections. The table is
+    /// registered empty, and slots are handed out as files are registered
+    /// with [`DirectFd::register`].
+    ///
+    /// Creating the runtime fails on kernels before 5.19.
+    ///
+    /// [`DirectFd`]: io::DirectFd
+    /// [`DirectFd::register`]: io::DirectFd::register
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::io::DirectFd;
+    /// use tokio_uring::Submit;
+    ///
+    /// tokio_uring::builder().direct_descriptors(1024).start(async {
+    ///     let file = std::fs::File::open("hello.txt").unwrap();
+    ///     let direct = DirectFd::register(&file).unwrap();
+    ///     drop(file);
+    ///
+    ///     let (n, buf) = direct.read_at(vec![0; 4096], 0).submit().await.unwrap();
+    ///     println!("read {:?}", &buf[..n]);
+    ///     direct.close().await.unwrap();
+    /// });
+    /// ```
+    pub fn direct_descriptors(&mut self, n: u32) -> &mut Self {
+        self.direct_descriptors = Some(n);
+        self
+    }
+
     /// Replaces the default [`io_uring::Builder`], which controls the settings for the
     /// inner `io_uring` API.
     ///
     /// Refer to the [`io_uring::Builder`] documentation for all the supported methods.
     pub fn uring_builder(&mut self, b: &io_uring::Builder) -> &mut Self {
         self.urb = b.clone();
+        self.custom_urb = true;
         self
     }
 
@@ -240,6 +1035,25 @@ impl Builder {
         let rt = runtime::Runtime::new(self).unwrap();
         rt.block_on(future)
     }
+
+    /// Starts an `io_uring` enabled Tokio runtime, and shuts it down once
+    /// `future` completes, reporting what was left in flight.
+    ///
+    /// See [`Runtime::shutdown`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let (output, report) = tokio_uring::builder().start_with_report(async {
+    ///     // ...
+    /// });
+    /// println!("{} operations cancelled on shutdown", report.cancelled);
+    /// ```
+    pub fn start_with_report<F: Future>(&self, future: F) -> (F::Output, runtime::ShutdownReport) {
+        let rt = runtime::Runtime::new(self).unwrap();
+        let output = rt.block_on(future);
+        (output, rt.shutdown())
+    }
 }
 
 /// The simplest possible operation. Just posts a completion event, nothing else.
@@ -258,6 +1072,6 @@ impl Builder {
 /// }
 /// ```
 pub async fn no_op() -> std::io::Result<()> {
-    let op = Op::<io::NoOp>::no_op().unwrap();
+    let op = Op::<io::NoOp>::no_op()?;
     op.await
 }
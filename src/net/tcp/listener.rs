@@ -130,9 +130,8 @@ impl TcpListener {
     pub async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
         let (socket, socket_addr) = self.inner.accept().await?;
         let stream = TcpStream { inner: socket };
-        let socket_addr = socket_addr.ok_or_else(|| {
-            io::Error::new(io::ErrorKind::Other, "Could not get socket IP address")
-        })?;
+        let socket_addr =
+            socket_addr.ok_or_else(|| io::Error::other("Could not get socket IP address"))?;
         Ok((stream, socket_addr))
     }
 }
@@ -224,6 +224,73 @@ impl TcpStream {
         self.inner.writev(buf).await
     }
 
+    /// Waits for the stream to become readable.
+    ///
+    /// This resolves once data can be read from the stream without waiting, or
+    /// the peer has shut down its writing half, or an error is pending. This
+    /// allows sizing a buffer before issuing a [`read`], e.g. when driving a
+    /// sans-io protocol state machine.
+    ///
+    /// Readiness is only a hint: another task may consume the data first.
+    ///
+    /// [`read`]: Self::read
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::net::TcpStream;
+    ///
+    /// tokio_uring::start(async {
+    ///     let stream = TcpStream::connect("127.0.0.1:8080".parse().unwrap()).await.unwrap();
+    ///
+    ///     stream.readable().await.unwrap();
+    ///     let (n, buf) = stream.read(vec![0; 4096]).await.unwrap();
+    ///     println!("{:?}", &buf[..n]);
+    /// });
+    /// ```
+    pub async fn readable(&self) -> io::Result<()> {
+        self.inner.readable().await
+    }
+
+    /// Waits for the stream to become writable.
+    ///
+    /// This resolves once data can be written to the stream without waiting,
+    /// or an error is pending. As with [`readable`], readiness is only a hint.
+    ///
+    /// [`readable`]: Self::readable
+    pub async fn writable(&self) -> io::Result<()> {
+        self.inner.writable().await
+    }
+
+    /// Cancels all operations in flight on the stream, returning how many
+    /// were cancelled.
+    ///
+    /// The cancelled operations fail with `ECANCELED`, e.g. to stop reads
+    /// from a peer which stopped sending. Requires Linux 5.19 or later.
+    pub async fn cancel_ops(&self) -> io::Result<usize> {
+        self.inner.cancel_ops().await
+    }
+
+    /// Closes the stream using the uring asynchronous close operation.
+    ///
+    /// Operations still in flight on the stream are cancelled, see
+    /// [`cancel_ops`](TcpStream::cancel_ops), and the close waits for them to
+    /// complete.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::net::TcpStream;
+    ///
+    /// tokio_uring::start(async {
+    ///     let stream = TcpStream::connect("127.0.0.1:8080".parse().unwrap()).await.unwrap();
+    ///     stream.close().await.unwrap();
+    /// });
+    /// ```
+    pub async fn close(self) -> io::Result<()> {
+        self.inner.close().await
+    }
+
     /// Shuts down the read, write, or both halves of this connection.
     ///
     /// This function will cause all pending and future I/O on the specified portions to return
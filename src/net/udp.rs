@@ -234,6 +234,9 @@ impl UdpSocket {
     /// > at writes over around 10 KB.
     ///
     /// Note: Using fixed buffers [#54](https://github.com/tokio-rs/tokio-uring/pull/54), avoids the page-pinning overhead
+    ///
+    /// Falls back to a copying send on kernels without zero-copy sends,
+    /// before Linux 6.0, or when the ring is restricted from them.
     pub async fn send_zc<T: BoundedBuf>(&self, buf: T) -> crate::Result<usize, T> {
         self.inner.send_zc(buf).await
     }
@@ -276,6 +279,9 @@ impl UdpSocket {
     ///
     /// Can be used with socket_addr: None on connected sockets, which can have performance
     /// benefits if multiple datagrams are sent to the same destination address.
+    ///
+    /// Falls back to [`Self::sendmsg`] on kernels without zero-copy sends,
+    /// before Linux 6.1, or when the ring is restricted from them.
     pub async fn sendmsg_zc<T: BoundedBuf, U: BoundedBuf>(
         &self,
         io_slices: Vec<T>,
@@ -0,0 +1,103 @@
+use crate::process::PidFd;
+use std::io;
+use std::process::{Command, ExitStatus};
+
+/// A child process monitored through a [`PidFd`].
+///
+/// The standard library's [`Command`] is used to configure and spawn the
+/// process. Its exit is then awaited with an `io_uring` poll on the child's
+/// pidfd rather than a blocking `waitpid` or a `SIGCHLD` handler.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::process::Command;
+/// use tokio_uring::process::Child;
+///
+/// fn main() -> std::io::Result<()> {
+///     tokio_uring::start(async {
+///         let mut child = Child::spawn(Command::new("sleep").arg("60"))?;
+///
+///         child.pidfd().send_signal(libc::SIGTERM)?;
+///         let status = child.wait().await?;
+///         println!("child exited with {}", status);
+///
+///         Ok(())
+///     })
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Child {
+    inner: std::process::Child,
+    pidfd: PidFd,
+}
+
+impl Child {
+    /// Spawns `command` as a child process.
+    pub fn spawn(command: &mut Command) -> io::Result<Child> {
+        Child::from_std(command.spawn()?)
+    }
+
+    /// Takes ownership of a child spawned by the standard library.
+    ///
+    /// The child must not have been waited on yet, otherwise its pid may
+    /// already refer to another process.
+    pub fn from_std(child: std::process::Child) -> io::Result<Child> {
+        let pidfd = PidFd::open(child.id())?;
+        Ok(Child {
+            inner: child,
+            pidfd,
+        })
+    }
+
+    /// Returns the OS-assigned process identifier of the child.
+    pub fn id(&self) -> u32 {
+        self.inner.id()
+    }
+
+    /// Returns the pidfd referring to the child.
+    pub fn pidfd(&self) -> &PidFd {
+        &self.pidfd
+    }
+
+    /// Returns a reference to the underlying standard library child, e.g. to
+    /// access its stdio handles.
+    pub fn as_std(&self) -> &std::process::Child {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying standard library child.
+    pub fn as_std_mut(&mut self) -> &mut std::process::Child {
+        &mut self.inner
+    }
+
+    /// Sends `SIGKILL` to the child through its pidfd.
+    ///
+    /// Does nothing if the child has already been reaped.
+    pub fn kill(&mut self) -> io::Result<()> {
+        if self.inner.try_wait()?.is_some() {
+            return Ok(());
+        }
+        self.pidfd.send_signal(libc::SIGKILL)
+    }
+
+    /// Waits for the child to exit, reaps it and returns its exit status.
+    ///
+    /// The stdin handle of the child, if any, is not closed before waiting,
+    /// take it through [`as_std_mut`](Child::as_std_mut) if the child needs
+    /// to see end of input.
+    pub async fn wait(&mut self) -> io::Result<ExitStatus> {
+        loop {
+            if let Some(status) = self.inner.try_wait()? {
+                return Ok(status);
+            }
+            self.pidfd.exited().await?;
+        }
+    }
+
+    /// Returns the exit status if the child has exited, reaping it, without
+    /// waiting.
+    pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.inner.try_wait()
+    }
+}
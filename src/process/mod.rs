@@ -0,0 +1,17 @@
+//! Process supervision for `tokio-uring`.
+//!
+//! This module provides [`PidFd`], a file descriptor referring to a process,
+//! and [`Child`], a spawned child process that is monitored through its
+//! `PidFd`. Unlike a numeric pid, a `PidFd` can never refer to a recycled
+//! process, so waiting on it and signalling through it is race-free.
+//!
+//! Process file descriptors require Linux 5.3 or later.
+//!
+//! [`PidFd`]: PidFd
+//! [`Child`]: Child
+
+mod child;
+mod pidfd;
+
+pub use child::Child;
+pub use pidfd::PidFd;
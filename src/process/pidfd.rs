@@ -0,0 +1,104 @@
+use crate::io::SharedFd;
+use crate::runtime::driver::op::Op;
+use std::fmt;
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+
+/// A file descriptor referring to a process.
+///
+/// A `PidFd` stays bound to the process it was opened for, even after the
+/// process has exited and its pid has been reused. Awaiting [`exited`] uses an
+/// `io_uring` poll on the descriptor, and [`send_signal`] delivers signals
+/// without the pid-reuse race of `kill(2)`.
+///
+/// [`exited`]: PidFd::exited
+/// [`send_signal`]: PidFd::send_signal
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::process::PidFd;
+///
+/// fn main() -> std::io::Result<()> {
+///     tokio_uring::start(async {
+///         let pidfd = PidFd::open(1234)?;
+///
+///         // Ask the process to terminate, then wait for it to go away.
+///         pidfd.send_signal(libc::SIGTERM)?;
+///         pidfd.exited().await?;
+///
+///         pidfd.close().await
+///     })
+/// }
+/// ```
+pub struct PidFd {
+    fd: SharedFd,
+}
+
+impl PidFd {
+    /// Opens a file descriptor referring to the process with the given pid.
+    ///
+    /// The returned descriptor has the close-on-exec flag set.
+    pub fn open(pid: u32) -> io::Result<PidFd> {
+        let fd = syscall!(syscall(libc::SYS_pidfd_open, pid as libc::pid_t, 0))?;
+        Ok(PidFd {
+            fd: SharedFd::new(fd as RawFd),
+        })
+    }
+
+    /// Waits for the process to exit.
+    ///
+    /// This completes once the process has terminated. It does not reap the
+    /// process; for a child of the current process use [`Child::wait`] to
+    /// collect its exit status.
+    ///
+    /// [`Child::wait`]: crate::process::Child::wait
+    pub async fn exited(&self) -> io::Result<()> {
+        Op::poll_add(&self.fd, libc::POLLIN as u32)?.await?;
+        Ok(())
+    }
+
+    /// Sends the signal `signal` to the process.
+    ///
+    /// Fails with `ESRCH` if the process has already exited.
+    pub fn send_signal(&self, signal: i32) -> io::Result<()> {
+        syscall!(syscall(
+            libc::SYS_pidfd_send_signal,
+            self.fd.raw_fd(),
+            signal,
+            std::ptr::null::<libc::siginfo_t>(),
+            0
+        ))?;
+        Ok(())
+    }
+
+    /// Closes the descriptor.
+    ///
+    /// See [`File::close`](crate::fs::File::close) for why an explicit close
+    /// is preferred over dropping.
+    pub async fn close(mut self) -> io::Result<()> {
+        self.fd.close().await
+    }
+}
+
+impl FromRawFd for PidFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        PidFd {
+            fd: SharedFd::new(fd),
+        }
+    }
+}
+
+impl AsRawFd for PidFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.raw_fd()
+    }
+}
+
+impl fmt::Debug for PidFd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PidFd")
+            .field("fd", &self.fd.raw_fd())
+            .finish()
+    }
+}
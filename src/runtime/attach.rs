@@ -0,0 +1,142 @@
+use std::future::{poll_fn, Future};
+use std::io;
+use std::rc::Rc;
+use std::task::Poll;
+
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+use crate::runtime::{driver, DriverError, RingFd, CONTEXT};
+
+/// An `io_uring` driver attached to a Tokio runtime which was not started by
+/// `tokio-uring`, as returned by [`Driver::attach`].
+///
+/// While the driver is alive, `tokio-uring` resources can be used on the
+/// thread it was attached on, without [`start`](crate::start). Its ring is
+/// registered with the Tokio reactor, and a local task submits operations
+/// and processes their completions.
+///
+/// `tokio-uring` resources are bound to the ring's thread, so they must be
+/// used from the future passed to `block_on`, or from tasks of a
+/// [`LocalSet`](tokio::task::LocalSet). Dropping the driver cancels the
+/// operations still in flight.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio::task::LocalSet;
+/// use tokio_uring::fs::File;
+///
+/// let rt = tokio::runtime::Builder::new_current_thread()
+///     .enable_all()
+///     .build()
+///     .unwrap();
+///
+/// LocalSet::new().block_on(&rt, async {
+///     let _driver = tokio_uring::Driver::attach().unwrap();
+///
+///     let file = File::open("hello.txt").await.unwrap();
+///     file.close().await.unwrap();
+/// });
+/// ```
+pub struct Driver {
+    handle: driver::Handle,
+    task: JoinHandle<()>,
+}
+
+impl Driver {
+    /// Attaches a driver with the default configuration to the current
+    /// Tokio runtime.
+    ///
+    /// See [`attach_with`](Driver::attach_with).
+    pub fn attach() -> io::Result<Driver> {
+        Driver::attach_with(&crate::builder())
+    }
+
+    /// Attaches a driver configured by `b` to the current Tokio runtime.
+    ///
+    /// Returns an `AlreadyExists` error if a `tokio-uring` runtime or driver
+    /// is already in use on this thread, and an error if not called within a
+    /// Tokio runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if not called within a [`LocalSet`](tokio::task::LocalSet).
+    pub fn attach_with(b: &crate::Builder) -> io::Result<Driver> {
+        if tokio::runtime::Handle::try_current().is_err() {
+            return Err(io::Error::other("not within a Tokio runtime"));
+        }
+        if CONTEXT.with(|cx| cx.is_set()) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "a tokio-uring runtime is already in use on this thread",
+            ));
+        }
+
+        let handle = driver::Handle::new(b)?;
+
+        // Without the runtime's park hook, submissions are flushed by the
+        // driving task, which they wake.
+        let submitted = handle.notify_submissions();
+        let task = if b.iopoll {
+            tokio::task::spawn_local(super::drive_uring_polled(handle.clone(), submitted))
+        } else {
+            let fd = RingFd::new(handle.clone())?;
+            tokio::task::spawn_local(drive_uring_attached(fd, submitted))
+        };
+
+        CONTEXT.with(|cx| cx.set_handle(handle.clone()));
+
+        Ok(Driver { handle, task })
+    }
+}
+
+impl Drop for Driver {
+    fn drop(&mut self) {
+        self.task.abort();
+        CONTEXT.with(|cx| cx.unset_driver());
+        // Stop cancelling dropped operations one by one, the driver cancels
+        // all of them when the last handle goes away.
+        self.handle.shut_down();
+    }
+}
+
+// Flushes submissions when woken by one, and processes completions when the
+// ring is readable.
+pub(super) async fn drive_uring_attached(mut driver: RingFd, submitted: Rc<Notify>) {
+    loop {
+        let handle = driver.get_ref();
+        if let Err(e) = handle.flush() {
+            if e.kind() != io::ErrorKind::Interrupted {
+                handle.report_error(DriverError::Submit(e));
+            }
+        }
+
+        let notified = submitted.notified();
+        tokio::pin!(notified);
+
+        let exhausted = poll_fn(|cx| {
+            if notified.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(false);
+            }
+
+            match driver.poll_read_ready(cx) {
+                Poll::Ready(Ok(mut guard)) => {
+                    // Completions left over keep the ring readable.
+                    let exhausted = guard.get_inner().dispatch_budgeted();
+                    if !exhausted {
+                        guard.clear_ready();
+                    }
+                    Poll::Ready(exhausted)
+                }
+                Poll::Ready(Err(e)) => panic!("failed to poll the ring: {}", e),
+                Poll::Pending => Poll::Pending,
+            }
+        })
+        .await;
+
+        if exhausted {
+            tokio::task::yield_now().await;
+        }
+    }
+}
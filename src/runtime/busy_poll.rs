@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+/// How a runtime spins on its completion queue, set with
+/// [`Builder::busy_poll`](crate::Builder::busy_poll).
+///
+/// A spinning runtime checks the completion queue every time it runs out of
+/// tasks instead of going to sleep until the ring signals completions, which
+/// takes the wake-up out of the latency of every operation. The thread stays
+/// busy while spinning, even with nothing to do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BusyPoll {
+    /// Spin for as long as the runtime runs.
+    Always,
+
+    /// Spin until no completion arrived for the duration, then go to sleep
+    /// until the next one.
+    Timeout(Duration),
+}
@@ -1,10 +1,18 @@
 use crate::runtime::driver;
 use crate::runtime::driver::{Handle, WeakHandle};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::io;
 
 /// Owns the driver and resides in thread-local storage.
 pub struct RuntimeContext {
     driver: RefCell<Option<driver::Handle>>,
+
+    /// The further ring operations are submitted to while polling a future
+    /// passed to `on_ring`, if any.
+    selected: Cell<Option<usize>>,
+
+    /// Errors of implicit closes, collected while the runtime shuts down.
+    close_errors: RefCell<Option<Vec<io::Error>>>,
 }
 
 impl RuntimeContext {
@@ -12,6 +20,8 @@ impl RuntimeContext {
     pub(crate) const fn new() -> Self {
         Self {
             driver: RefCell::new(None),
+            selected: Cell::new(None),
+            close_errors: RefCell::new(None),
         }
     }
 
@@ -41,10 +51,45 @@ impl RuntimeContext {
             .unwrap_or(false)
     }
 
+    /// Returns the ring operations are submitted to: the ring selected with
+    /// `on_ring`, or the primary ring of the runtime.
     pub fn handle(&self) -> Option<Handle> {
+        let primary = self.driver.borrow().clone()?;
+        match self.selected.get() {
+            Some(index) => primary.ring(index),
+            None => Some(primary),
+        }
+    }
+
+    /// Returns the primary ring of the runtime, regardless of the ring
+    /// selected.
+    pub(crate) fn primary(&self) -> Option<Handle> {
         self.driver.borrow().clone()
     }
 
+    /// Selects the ring operations are submitted to, returning the ring
+    /// selected before.
+    pub(crate) fn select(&self, ring: Option<usize>) -> Option<usize> {
+        self.selected.replace(ring)
+    }
+
+    /// Start collecting the errors of implicit closes.
+    pub(crate) fn collect_close_errors(&self) {
+        *self.close_errors.borrow_mut() = Some(Vec::new());
+    }
+
+    /// Stop collecting the errors of implicit closes, returning those seen.
+    pub(crate) fn take_close_errors(&self) -> Vec<io::Error> {
+        self.close_errors.borrow_mut().take().unwrap_or_default()
+    }
+
+    /// Record the error of an implicit close, if errors are being collected.
+    pub(crate) fn record_close_error(&self, error: io::Error) {
+        if let Some(errors) = self.close_errors.borrow_mut().as_mut() {
+            errors.push(error);
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn weak(&self) -> Option<WeakHandle> {
         self.driver.borrow().as_ref().map(Into::into)
@@ -0,0 +1,47 @@
+//! The slots of the table of direct descriptors, registered sparse with the
+//! ring when the runtime starts.
+
+use std::io;
+
+pub(crate) struct FileTable {
+    /// The number of slots in the table
+    size: u32,
+
+    /// Slots released, to be reused first
+    free: Vec<u32>,
+
+    /// The slots from this one on have never been handed out
+    next: u32,
+}
+
+impl FileTable {
+    pub(crate) fn new(size: u32) -> FileTable {
+        FileTable {
+            size,
+            free: Vec::new(),
+            next: 0,
+        }
+    }
+
+    /// Hands out a free slot, failing with `ENFILE` if there is none.
+    pub(crate) fn alloc(&mut self) -> io::Result<u32> {
+        if let Some(slot) = self.free.pop() {
+            return Ok(slot);
+        }
+        if self.next == self.size {
+            return Err(io::Error::from_raw_os_error(libc::ENFILE));
+        }
+        self.next += 1;
+        Ok(self.next - 1)
+    }
+
+    /// Returns whether no slot is handed out.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.free.len() as u32 == self.next
+    }
+
+    pub(crate) fn release(&mut self, slot: u32) {
+        debug_assert!(slot < self.next && !self.free.contains(&slot));
+        self.free.push(slot);
+    }
+}
@@ -22,7 +22,7 @@ use std::task::{Context, Poll};
 
 use crate::buf::fixed::FixedBuffers;
 use crate::runtime::driver::op::{Completable, MultiCQEFuture, Op, Updateable};
-use crate::runtime::driver::Driver;
+use crate::runtime::driver::{Driver, OpTimeout};
 
 #[derive(Clone)]
 pub struct Handle {
@@ -41,12 +41,86 @@ impl Handle {
         })
     }
 
-    pub(crate) fn dispatch_completions(&self) {
-        self.inner.borrow_mut().dispatch_completions()
+    pub(crate) fn dispatch_budgeted(&self) -> bool {
+        self.inner.borrow_mut().dispatch_budgeted()
+    }
+
+    pub(crate) fn spin(&self) -> bool {
+        self.inner.borrow_mut().spin()
+    }
+
+    pub(crate) fn drain(&self) -> super::Drain {
+        self.inner.borrow_mut().drain()
+    }
+
+    pub(crate) fn shut_down(&self) {
+        self.inner.borrow_mut().shut_down()
+    }
+
+    pub(crate) fn polled(&self) -> Option<Rc<tokio::sync::Notify>> {
+        self.inner.borrow().polled()
+    }
+
+    pub(crate) fn notify_submissions(&self) -> Rc<tokio::sync::Notify> {
+        self.inner.borrow_mut().notify_submissions()
+    }
+
+    pub(crate) fn poll_completions(&self) -> bool {
+        self.inner.borrow_mut().poll_completions()
     }
 
     pub(crate) fn flush(&self) -> io::Result<usize> {
-        self.inner.borrow_mut().uring.submit()
+        // With submission polling, this only enters the kernel to wake the SQ
+        // thread once it has gone to sleep (`IORING_SQ_NEED_WAKEUP`).
+        self.inner.borrow_mut().submit_and_wait(0)
+    }
+
+    pub(crate) fn with_ring<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&io_uring::IoUring) -> R,
+    {
+        let driver = self.inner.borrow();
+        let ring = driver
+            .uring
+            .as_default()
+            .expect("the ring has big entries, see `Builder::sqe128` and `Builder::cqe32`");
+        f(ring)
+    }
+
+    pub(crate) fn poll_msg(&self, cx: &mut Context<'_>) -> Poll<crate::runtime::Message> {
+        self.inner.borrow_mut().poll_msg(cx)
+    }
+
+    pub(crate) fn poll_permit(&self, cx: &mut Context<'_>) -> Poll<()> {
+        self.inner.borrow_mut().poll_permit(cx)
+    }
+
+    pub(crate) fn release_permit(&self) {
+        self.inner.borrow_mut().release_permit()
+    }
+
+    pub(crate) fn poll_sq_space(&self, cx: &mut Context<'_>) -> Poll<usize> {
+        self.inner.borrow_mut().poll_sq_space(cx)
+    }
+
+    pub(crate) fn supports(&self, opcode: u8) -> bool {
+        self.inner.borrow().supports(opcode)
+    }
+
+    pub(crate) fn ring_fd(&self) -> std::sync::Weak<std::os::unix::io::OwnedFd> {
+        self.inner.borrow().ring_fd()
+    }
+
+    pub(crate) fn ring_stats(&self) -> crate::runtime::RingStats {
+        self.inner.borrow().ring_stats()
+    }
+
+    pub(crate) fn dump_inflight(&self) -> Vec<crate::runtime::InflightOp> {
+        self.inner.borrow().dump_inflight()
+    }
+
+    pub(crate) fn report_error(&self, error: crate::runtime::DriverError) {
+        self.inner.borrow().report_error(error)
     }
 
     pub(crate) fn register_buffers(
@@ -63,6 +137,10 @@ impl Handle {
         self.inner.borrow_mut().unregister_buffers(buffers)
     }
 
+    pub(crate) fn registered_buffers(&self) -> Option<Rc<RefCell<dyn FixedBuffers>>> {
+        self.inner.borrow().fixed_buffers.clone()
+    }
+
     pub fn register_files(&self, fds: &[RawFd]) -> io::Result<()> {
         self.inner.borrow_mut().register_files(fds)
     }
@@ -71,20 +149,143 @@ impl Handle {
         self.inner.borrow_mut().unregister_files()
     }
 
-    pub(crate) fn submit_op_2(&self, sqe: squeue::Entry) -> usize {
-        self.inner.borrow_mut().submit_op_2(sqe)
+    pub(crate) fn alloc_direct(&self) -> io::Result<u32> {
+        self.inner.borrow_mut().alloc_direct()
+    }
+
+    pub(crate) fn register_direct(&self, fd: RawFd) -> io::Result<u32> {
+        self.inner.borrow_mut().register_direct(fd)
+    }
+
+    pub(crate) fn release_direct(&self, slot: u32, close: bool) -> io::Result<()> {
+        self.inner.borrow_mut().release_direct(slot, close)
+    }
+
+    pub(crate) fn register_personality(&self) -> io::Result<u16> {
+        self.inner.borrow_mut().register_personality()
+    }
+
+    pub(crate) fn unregister_personality(&self, id: u16) -> io::Result<()> {
+        self.inner.borrow_mut().unregister_personality(id)
+    }
+
+    pub(crate) fn register_eventfd(&self, fd: RawFd, async_only: bool) -> io::Result<()> {
+        self.inner.borrow_mut().register_eventfd(fd, async_only)
+    }
+
+    pub(crate) fn unregister_eventfd(&self) -> io::Result<()> {
+        self.inner.borrow_mut().unregister_eventfd()
+    }
+
+    pub(crate) fn resize(&self, sq_entries: u32, cq_entries: u32) -> io::Result<()> {
+        self.inner.borrow_mut().resize(sq_entries, cq_entries)
+    }
+
+    pub(crate) fn poll_generation(&self, cx: &Context<'_>) -> u64 {
+        self.inner.borrow_mut().poll_generation(cx)
+    }
+
+    #[cfg(feature = "mock")]
+    pub(crate) fn is_mock(&self) -> bool {
+        self.inner.borrow().is_mock()
+    }
+
+    #[cfg(feature = "mock")]
+    pub(crate) fn mock_submitted(&self) -> Vec<crate::runtime::MockOp> {
+        self.inner.borrow_mut().mock_submitted()
+    }
+
+    #[cfg(feature = "mock")]
+    pub(crate) fn mock_pending(&self) -> Vec<crate::runtime::MockOp> {
+        self.inner.borrow_mut().mock_pending()
+    }
+
+    #[cfg(feature = "mock")]
+    pub(crate) fn mock_cancelled(&self) -> Vec<u64> {
+        self.inner.borrow_mut().mock_cancelled()
+    }
+
+    #[cfg(feature = "mock")]
+    pub(crate) fn mock_complete(&self, user_data: u64, result: i32, flags: u32) {
+        self.inner
+            .borrow_mut()
+            .mock_complete(user_data, result, flags)
+    }
+
+    #[cfg(feature = "mock")]
+    pub(crate) fn mock_complete_read(&self, user_data: u64, data: &[u8]) {
+        self.inner.borrow_mut().mock_complete_read(user_data, data)
+    }
+
+    #[cfg(feature = "mock")]
+    pub(crate) fn mock_overflow(&self) {
+        self.inner.borrow_mut().mock_overflow()
+    }
+
+    pub(crate) fn submit_op_2(&self, sqe: squeue::Entry, timeout: OpTimeout) -> usize {
+        self.inner.borrow_mut().submit_op_2(sqe, timeout)
+    }
+
+    pub(crate) fn try_submit_op_2(
+        &self,
+        sqe: squeue::Entry,
+        timeout: OpTimeout,
+    ) -> Result<usize, crate::SubmitError> {
+        self.inner.borrow_mut().try_submit_op_2(sqe, timeout)
+    }
+
+    pub(crate) fn try_submit_op_128(
+        &self,
+        sqe: squeue::Entry128,
+    ) -> Result<usize, crate::SubmitError> {
+        self.inner.borrow_mut().try_submit_op_128(sqe)
     }
 
     pub fn submit_ops(&self, sqes: impl Iterator<Item = squeue::Entry>) -> Vec<usize> {
         self.inner.borrow_mut().submit_ops(sqes)
     }
 
-    pub(crate) fn submit_op<T, S, F>(&self, data: T, f: F) -> io::Result<Op<T, S>>
+    pub(crate) fn submit_op<T, S, F>(&self, mut data: T, f: F) -> io::Result<Op<T, S>>
     where
         T: Completable,
         F: FnOnce(&mut T) -> squeue::Entry,
     {
-        self.inner.borrow_mut().submit_op(data, f, self.into())
+        let sqe = f(&mut data);
+        let handle = self.routed(&sqe);
+        let mut driver = handle.inner.borrow_mut();
+        driver.submit_op(data, |_| sqe, (&handle).into())
+    }
+
+    pub(crate) fn submit_linked_ops<T, S, F>(
+        &self,
+        data: Vec<T>,
+        f: F,
+    ) -> Result<Vec<Op<T, S>>, crate::Error<Vec<T>>>
+    where
+        T: Completable,
+        F: FnMut(&mut T) -> squeue::Entry,
+    {
+        let handle = self.clone();
+        let mut driver = self.inner.borrow_mut();
+        driver.submit_linked_ops(data, f, (&handle).into())
+    }
+
+    /// Returns the ring `sqe` is routed to, see [`crate::runtime::route`].
+    pub(crate) fn routed(&self, sqe: &squeue::Entry) -> Handle {
+        let routed = self.inner.borrow().routed(sqe);
+        routed.unwrap_or_else(|| self.clone())
+    }
+
+    pub(crate) fn add_ring(&self, ring: Handle, task: tokio::task::JoinHandle<()>) -> usize {
+        self.inner.borrow_mut().add_ring(ring, task)
+    }
+
+    pub(crate) fn ring(&self, index: usize) -> Option<Handle> {
+        self.inner.borrow().ring(index)
+    }
+
+    pub(crate) fn route(&self, opcode: u8, index: Option<usize>) {
+        self.inner.borrow_mut().route(opcode, index)
     }
 
     pub(crate) fn poll_op<T>(&self, op: &mut Op<T>, cx: &mut Context<'_>) -> Poll<T::Output>
@@ -98,6 +299,10 @@ impl Handle {
         self.inner.borrow_mut().poll_op_2(index, cx)
     }
 
+    pub(crate) fn poll_op_32(&self, index: usize, cx: &mut Context<'_>) -> Poll<cqueue::Entry32> {
+        self.inner.borrow_mut().poll_op_32(index, cx)
+    }
+
     pub(crate) fn poll_multishot_op<T>(
         &self,
         op: &mut Op<T, MultiCQEFuture>,
@@ -109,6 +314,10 @@ impl Handle {
         self.inner.borrow_mut().poll_multishot_op(op, cx)
     }
 
+    pub(crate) fn cancel_op<T, CqeType>(&self, op: &Op<T, CqeType>) -> io::Result<()> {
+        self.inner.borrow_mut().cancel_op(op.index())
+    }
+
     pub(crate) fn remove_op<T, CqeType>(&self, op: &mut Op<T, CqeType>) {
         self.inner.borrow_mut().remove_op(op)
     }
@@ -128,7 +337,7 @@ impl WeakHandle {
 
 impl AsRawFd for Handle {
     fn as_raw_fd(&self) -> RawFd {
-        self.inner.borrow().uring.as_raw_fd()
+        self.inner.borrow().as_raw_fd()
     }
 }
 
@@ -0,0 +1,144 @@
+//! The mock backend of the driver, behind the `mock` feature.
+//!
+//! Operations are recorded as they are submitted rather than handed to the
+//! kernel, and complete once a test completes them.
+
+use std::collections::HashMap;
+use std::mem;
+
+use io_uring::{cqueue, opcode, squeue};
+
+use super::{raw_cqe, report_error, Driver};
+use crate::runtime::{DriverError, MockOp};
+
+#[derive(Default)]
+pub(super) struct Mock {
+    /// Operations in flight, by user data
+    pending: HashMap<u64, MockOp>,
+
+    /// Operations submitted since last taken, in order
+    submitted: Vec<MockOp>,
+
+    /// Operations the driver asked to cancel since last taken
+    cancelled: Vec<u64>,
+}
+
+impl Mock {
+    pub(super) fn record(&mut self, sqe: &squeue::Entry) {
+        // Safety: an SQE is an io_uring_sqe: the opcode, flags, ioprio and
+        // descriptor, then the offset, address, length, operation flags and
+        // user data.
+        let op = unsafe {
+            let raw = sqe as *const squeue::Entry as *const u8;
+            MockOp {
+                opcode: *raw,
+                flags: *raw.add(1),
+                fd: (raw.add(4) as *const i32).read(),
+                offset: (raw.add(8) as *const u64).read(),
+                addr: (raw.add(16) as *const u64).read(),
+                len: (raw.add(24) as *const u32).read(),
+                op_flags: (raw.add(28) as *const u32).read(),
+                user_data: (raw.add(32) as *const u64).read(),
+            }
+        };
+        self.pending.insert(op.user_data, op.clone());
+        self.submitted.push(op);
+    }
+
+    pub(super) fn cancel(&mut self, user_data: u64) {
+        self.cancelled.push(user_data);
+    }
+}
+
+impl Driver {
+    fn mock(&mut self) -> &mut Mock {
+        self.mock.as_mut().expect("the runtime has no mock driver")
+    }
+
+    pub(crate) fn mock_submitted(&mut self) -> Vec<MockOp> {
+        mem::take(&mut self.mock().submitted)
+    }
+
+    pub(crate) fn mock_pending(&mut self) -> Vec<MockOp> {
+        let mut ops: Vec<MockOp> = self.mock().pending.values().cloned().collect();
+        ops.sort_by_key(|op| op.user_data);
+        ops
+    }
+
+    pub(crate) fn mock_cancelled(&mut self) -> Vec<u64> {
+        mem::take(&mut self.mock().cancelled)
+    }
+
+    /// Completes the operation with `user_data`, as the kernel would with a
+    /// completion queue entry.
+    pub(crate) fn mock_complete(&mut self, user_data: u64, result: i32, flags: u32) {
+        let pending = &mut self.mock().pending;
+        if cqueue::more(flags) {
+            assert!(
+                pending.contains_key(&user_data),
+                "no operation in flight with user data {}",
+                user_data
+            );
+        } else {
+            pending
+                .remove(&user_data)
+                .unwrap_or_else(|| panic!("no operation in flight with user data {}", user_data));
+        }
+
+        if let Some(drain) = &mut self.draining {
+            if !cqueue::more(flags) && self.ops.is_ignored(user_data as _) {
+                if result == -libc::ECANCELED {
+                    drain.cancelled += 1;
+                } else {
+                    drain.completed += 1;
+                }
+            }
+        }
+
+        if !self
+            .ops
+            .complete(user_data as _, raw_cqe(user_data, result, flags), None)
+        {
+            report_error(
+                &self.on_error,
+                DriverError::UnexpectedCompletion { user_data, result },
+            );
+        }
+        self.wake_permit_waiters();
+    }
+
+    /// Completes the read with `user_data`, copying `data` to its buffer.
+    pub(crate) fn mock_complete_read(&mut self, user_data: u64, data: &[u8]) {
+        let op = self
+            .mock()
+            .pending
+            .get(&user_data)
+            .unwrap_or_else(|| panic!("no operation in flight with user data {}", user_data));
+        assert!(
+            op.opcode == opcode::Read::CODE || op.opcode == opcode::ReadFixed::CODE,
+            "operation with user data {} is not a read",
+            user_data
+        );
+
+        let n = data.len().min(op.len as usize);
+        // Safety: the buffer of the read is held by the driver until the read
+        // completes, and is `len` bytes long.
+        unsafe { std::ptr::copy_nonoverlapping(data.as_ptr(), op.addr as *mut u8, n) };
+        self.mock_complete(user_data, n as i32, 0);
+    }
+
+    /// Reports the completion queue overflowed, as the kernel would with
+    /// `IORING_SQ_CQ_OVERFLOW`.
+    pub(crate) fn mock_overflow(&mut self) {
+        self.mock();
+        self.overflowed();
+    }
+
+    /// Completes the operations still in flight as cancelled, on shutdown.
+    pub(super) fn mock_cancel_all(&mut self) {
+        let pending: Vec<u64> = self.mock().pending.keys().copied().collect();
+        for user_data in pending {
+            self.mock_complete(user_data, -libc::ECANCELED, 0);
+        }
+    }
+}
@@ -1,30 +1,175 @@
 use crate::buf::fixed::FixedBuffers;
 use crate::runtime::driver::op::{Completable, Lifecycle, MultiCQEFuture, Op, Updateable};
-use io_uring::opcode::AsyncCancel;
-use io_uring::{cqueue, squeue, IoUring};
+use crate::runtime::msg_ring::MSG_TAG;
+use crate::runtime::{CqOverflow, DriverError, InflightOp, Message, Probe, RingStats};
+use crate::SubmitError;
+use io_uring::opcode::{self, AsyncCancel};
+use io_uring::types::{SubmitArgs, Timespec};
+use io_uring::{cqueue, squeue};
 use slab::Slab;
 use std::cell::RefCell;
-use std::os::unix::io::{AsRawFd, RawFd};
+use std::collections::{HashMap, VecDeque};
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd, RawFd};
 use std::rc::Rc;
-use std::task::{Context, Poll};
+use std::sync::{Arc, Weak};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
 use std::{io, mem};
+use tokio::sync::Notify;
 
+use files::FileTable;
 pub(crate) use handle::*;
+use ring::Ring;
 
+mod files;
 mod handle;
+#[cfg(feature = "mock")]
+mod mock;
 pub(crate) mod op;
+mod ring;
+mod trace;
 
 pub(crate) struct Driver {
     /// In-flight operations
     ops: Ops,
 
     /// IoUring bindings
-    uring: IoUring,
+    uring: Ring,
 
     /// Reference to the currently registered buffers.
     /// Ensures that the buffers are not dropped until
     /// after the io-uring runtime has terminated.
     fixed_buffers: Option<Rc<RefCell<dyn FixedBuffers>>>,
+
+    /// Maximum number of CQEs reaped before the completion queue is synced again
+    cqe_batch: usize,
+
+    /// Entries pending in the submission queue which trigger a submission
+    submit_batch: usize,
+
+    /// Completions processed before yielding to other tasks
+    completion_budget: usize,
+
+    /// Whether to peek at the completion queue again once it has been drained
+    cqe_repeek: bool,
+
+    /// Callback invoked on internal errors
+    on_error: Option<crate::ErrorCallback>,
+
+    /// Outcome of the in-flight operations, tracked while draining
+    draining: Option<Drain>,
+
+    /// Times the submission queue was full
+    sq_full: u64,
+
+    /// Times the completion queue was found overflowed
+    cq_overflow: u64,
+
+    /// Completions dropped by the kernel as the completion queue overflowed
+    cq_dropped: u32,
+
+    /// What to do when the completion queue overflows
+    cq_overflow_policy: CqOverflow,
+
+    /// Last recommended number of entries reported as an error
+    recommended_entries: u32,
+
+    /// Signalled when an operation is submitted, for rings not flushed by the
+    /// runtime parking: rings set up for I/O polling, which post no completion
+    /// events on their own, and rings attached to a foreign Tokio runtime
+    polled: Option<Rc<Notify>>,
+
+    /// Whether completions are deferred until the driver asks for them
+    defer_taskrun: bool,
+
+    /// Whether completions wait for the runtime thread to enter the kernel
+    coop_taskrun: bool,
+
+    /// Index of the ring fd registered with itself, used to enter the kernel
+    /// without looking up the ring fd each time
+    ring_index: Option<u32>,
+
+    /// How long in-flight operations may take to complete on shutdown
+    /// before they are cancelled
+    shutdown_timeout: Option<Duration>,
+
+    /// Whether the runtime is shutting down
+    shutting_down: bool,
+
+    /// Messages posted to the ring by other rings, not yet received
+    messages: VecDeque<Message>,
+
+    /// Waker of the task waiting for a message
+    msg_waker: Option<Waker>,
+
+    /// Wakers of tasks waiting for room in the submission queue
+    sq_waiters: Vec<Waker>,
+
+    /// The chain of linked operations being submitted
+    chain: Vec<Linked>,
+
+    /// Operations supported by the kernel, used to pick fallbacks
+    probe: Option<Probe>,
+
+    /// Operations allowed on the ring, if restricted
+    restrictions: Option<crate::runtime::Restrictions>,
+
+    /// Slots of the table of direct descriptors, if set up
+    files: Option<FileTable>,
+
+    /// The configuration the ring is re-created with
+    builder: crate::Builder,
+
+    /// The fd of the runtime, a duplicate of the ring fd which is pointed at
+    /// the new ring when the ring is re-created. Other threads hold weak
+    /// references, so it can't be closed while they use it.
+    ring_fd: Arc<OwnedFd>,
+
+    /// Times the ring was re-created
+    generation: u64,
+
+    /// Waker of the task waiting for the ring to be re-created
+    generation_waker: Option<Waker>,
+
+    /// Whether files are registered with `register_files`
+    files_registered: bool,
+
+    /// Personalities registered with the ring
+    personalities: usize,
+
+    /// The eventfd registered with the ring, and whether it is only
+    /// signalled for asynchronous completions
+    eventfd: Option<(RawFd, bool)>,
+
+    /// Further rings of the runtime, along with the tasks driving them
+    rings: Vec<(Handle, tokio::task::JoinHandle<()>)>,
+
+    /// The rings operations are routed to by opcode, as indices in `rings`
+    routes: HashMap<u8, usize>,
+
+    /// The most operations in flight at once, if capped
+    max_in_flight: Option<usize>,
+
+    /// The deadline of operations submitted without one of their own
+    op_timeout: Option<Duration>,
+
+    /// Slots for operations held by permits
+    permits: usize,
+
+    /// Tasks waiting for a permit
+    permit_waiters: Vec<Waker>,
+
+    /// The operations submitted to the mock backend, if the driver is one
+    #[cfg(feature = "mock")]
+    mock: Option<mock::Mock>,
+}
+
+/// Outcome of the operations in flight when the driver drained.
+#[derive(Debug, Default)]
+pub(crate) struct Drain {
+    pub(crate) in_flight: usize,
+    pub(crate) completed: usize,
+    pub(crate) cancelled: usize,
 }
 
 struct Ops {
@@ -34,139 +179,1214 @@ struct Ops {
 
     /// Received but unserviced Op completions
     completions: Slab<op::Completion>,
+
+    /// The largest number of operations in flight at once
+    peak: usize,
+
+    /// Operations submitted which have yet to post their final completion
+    in_flight: usize,
+
+    /// Chains of linked operations, some of which post no completion if
+    /// they succeed
+    chains: Vec<Vec<Linked>>,
+
+    /// The extra 16 bytes of the big completions of operations submitted
+    /// with big entries, once they complete
+    big: HashMap<usize, Option<[u64; 2]>>,
+
+    /// What the operations were submitted as, and when
+    submitted: Vec<Option<Submitted>>,
+
+    /// The timespecs of the deadlines linked to operations, which must live
+    /// until the kernel reads them, and when they expire
+    deadlines: HashMap<usize, (Box<Timespec>, Instant)>,
+}
+
+/// The deadline an operation is submitted with.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) enum OpTimeout {
+    /// The deadline set with `Builder::op_timeout`, if any.
+    #[default]
+    Default,
+
+    /// No deadline.
+    Disabled,
+
+    /// A deadline of its own.
+    After(Duration),
+}
+
+// Tags the user data of the timeouts linked to operations, along with the
+// index of the operation.
+const DEADLINE_TAG: u64 = 1 << 62;
+
+// The user data of cancellation requests. No operation gets this index, and
+// unlike `u64::MAX` it can't be mistaken for a message, whose data may take
+// any value below the tag.
+const CANCEL: u64 = DEADLINE_TAG - 1;
+
+/// What an operation was submitted as, and when.
+#[derive(Clone, Copy)]
+struct Submitted {
+    opcode: u8,
+    fd: i32,
+    flags: u8,
+    at: Instant,
+}
+
+impl Submitted {
+    fn new(sqe: &squeue::Entry) -> Submitted {
+        // Safety: an SQE is an io_uring_sqe, which starts with the opcode and
+        // flags, and has the descriptor at offset 4.
+        let (opcode, flags, fd) = unsafe {
+            let raw = sqe as *const squeue::Entry as *const u8;
+            (*raw, *raw.add(1), (raw.add(4) as *const i32).read())
+        };
+        Submitted {
+            opcode,
+            fd,
+            flags,
+            at: Instant::now(),
+        }
+    }
+}
+
+/// An operation in a chain of linked operations.
+struct Linked {
+    index: usize,
+    flags: u8,
+    /// The result it completes with if it succeeds without a completion.
+    skipped: i32,
+    done: bool,
+}
+
+impl Linked {
+    fn new(index: usize, sqe: &squeue::Entry) -> Linked {
+        // Safety: an SQE is an io_uring_sqe, which starts with the opcode and
+        // the flags, and has the length at offset 24.
+        let (code, flags, len) = unsafe {
+            let raw = sqe as *const squeue::Entry as *const u8;
+            (*raw, *raw.add(1), (raw.add(24) as *const u32).read())
+        };
+
+        // A read or write transferring less than asked fails its link, and
+        // posts its completion even when skipping it on success. Skipped,
+        // it transferred everything.
+        let transfers = [
+            opcode::Read::CODE,
+            opcode::Write::CODE,
+            opcode::ReadFixed::CODE,
+            opcode::WriteFixed::CODE,
+        ];
+        let skipped = if transfers.contains(&code) {
+            len as i32
+        } else {
+            0
+        };
+
+        Linked {
+            index,
+            flags,
+            skipped,
+            done: false,
+        }
+    }
+
+    fn skips_success(&self) -> bool {
+        self.flags & squeue::Flags::SKIP_SUCCESS.bits() != 0
+    }
 }
 
 impl Driver {
     pub(crate) fn new(b: &crate::Builder) -> io::Result<Driver> {
-        let uring = b.urb.build(b.entries)?;
+        let (uring, probe) = build_ring(b, None)?;
+        let ring_index = register_ring_fd(&uring);
+
+        let fd = syscall!(fcntl(uring.as_raw_fd(), libc::F_DUPFD_CLOEXEC, 0))?;
+        // Safety: the duplicate is owned by nothing else.
+        let ring_fd = Arc::new(unsafe { OwnedFd::from_raw_fd(fd) });
 
         Ok(Driver {
-            ops: Ops::new(),
+            ops: Ops::new(b.preallocate_ops),
             uring,
             fixed_buffers: None,
+            cqe_batch: b.cqe_batch,
+            submit_batch: b.submit_batch,
+            completion_budget: b.completion_budget,
+            cqe_repeek: b.cqe_repeek,
+            on_error: b.on_driver_error.clone(),
+            draining: None,
+            sq_full: 0,
+            cq_overflow: 0,
+            cq_dropped: 0,
+            cq_overflow_policy: b.cq_overflow,
+            recommended_entries: 0,
+            polled: b.iopoll.then(|| Rc::new(Notify::new())),
+            defer_taskrun: b.defer_taskrun,
+            coop_taskrun: b.coop_taskrun,
+            ring_index,
+            shutdown_timeout: b.shutdown_timeout,
+            shutting_down: false,
+            messages: VecDeque::new(),
+            msg_waker: None,
+            sq_waiters: Vec::new(),
+            chain: Vec::new(),
+            probe,
+            restrictions: b.restrictions.clone(),
+            files: b.direct_descriptors.map(FileTable::new),
+            builder: b.clone(),
+            ring_fd,
+            generation: 0,
+            generation_waker: None,
+            files_registered: false,
+            personalities: 0,
+            eventfd: None,
+            rings: Vec::new(),
+            routes: HashMap::new(),
+            max_in_flight: b.max_in_flight,
+            op_timeout: b.op_timeout,
+            permits: 0,
+            permit_waiters: Vec::new(),
+            #[cfg(feature = "mock")]
+            mock: b.mock.then(mock::Mock::default),
         })
     }
 
-    fn wait(&self) -> io::Result<usize> {
-        self.uring.submit_and_wait(1)
-    }
+    /// Adds a further ring to the runtime, driven by `task`, returning its
+    /// index.
+    pub(crate) fn add_ring(&mut self, ring: Handle, task: tokio::task::JoinHandle<()>) -> usize {
+        self.rings.push((ring, task));
+        self.rings.len() - 1
+    }
+
+    /// Returns the further ring at `index`.
+    pub(crate) fn ring(&self, index: usize) -> Option<Handle> {
+        self.rings.get(index).map(|(ring, _)| ring.clone())
+    }
+
+    /// Routes operations with `opcode` to the further ring at `index`, or
+    /// back to this ring.
+    pub(crate) fn route(&mut self, opcode: u8, index: Option<usize>) {
+        match index {
+            Some(index) => self.routes.insert(opcode, index),
+            None => self.routes.remove(&opcode),
+        };
+    }
+
+    /// Returns the ring `sqe` is routed to, if not this one.
+    ///
+    /// Direct descriptors and fixed buffers are registered with this ring, so
+    /// operations using them stay. So do linked operations, as a chain only
+    /// links operations of the same ring.
+    pub(crate) fn routed(&self, sqe: &squeue::Entry) -> Option<Handle> {
+        if self.routes.is_empty() {
+            return None;
+        }
+
+        let op = Submitted::new(sqe);
+        let fixed = op.flags & squeue::Flags::FIXED_FILE.bits() != 0
+            || op.opcode == opcode::ReadFixed::CODE
+            || op.opcode == opcode::WriteFixed::CODE;
+        let linked = (squeue::Flags::IO_LINK | squeue::Flags::IO_HARDLINK).bits();
+        if fixed || op.flags & linked != 0 || !self.chain.is_empty() {
+            return None;
+        }
+        self.routes
+            .get(&op.opcode)
+            .and_then(|&index| self.ring(index))
+    }
+
+    /// Re-creates the ring with `sq_entries` and `cq_entries` entries,
+    /// carrying over the fixed buffers and the eventfd registered with it.
+    ///
+    /// Fails with `EBUSY` while operations are in flight, or while files or
+    /// personalities are registered, as these can't be carried over.
+    pub(crate) fn resize(&mut self, sq_entries: u32, cq_entries: u32) -> io::Result<()> {
+        // Complete whatever the ring has done already.
+        self.submit()?;
+        self.dispatch_completions();
+
+        let direct = self.files.as_ref().is_some_and(|files| !files.is_empty());
+        if self.ops.in_flight > 0 || direct || self.files_registered || self.personalities > 0 {
+            return Err(io::Error::from_raw_os_error(libc::EBUSY));
+        }
+
+        let mut b = self.builder.clone();
+        b.entries = sq_entries;
+        let (uring, probe) = build_ring(&b, Some(cq_entries))?;
+
+        if let Some(buffers) = &self.fixed_buffers {
+            let buffers = buffers.borrow();
+            // Safety: the buffers are registered with the current ring
+            // already, which keeps them alive.
+            unsafe { uring.submitter().register_buffers(buffers.iovecs()) }?;
+        }
+        if let Some((fd, async_only)) = self.eventfd {
+            register_eventfd(&uring, fd, async_only)?;
+        }
+
+        // The fd of the runtime refers to the new ring from here on. Messages
+        // posted to the old ring up to now are still received.
+        syscall!(dup3(
+            uring.as_raw_fd(),
+            self.ring_fd.as_raw_fd(),
+            libc::O_CLOEXEC
+        ))?;
+        if let Err(e) = self.get_events() {
+            self.report_error(DriverError::Submit(e));
+        }
+        self.dispatch_completions();
+
+        // The registered ring fd holds on to the old ring.
+        if let Some(index) = self.ring_index.take() {
+            unregister_ring_fd(&self.uring, index);
+        }
+        self.ring_index = register_ring_fd(&uring);
+        self.uring = uring;
+        self.probe = probe;
+        self.recommended_entries = 0;
+
+        self.generation += 1;
+        if let Some(waker) = self.generation_waker.take() {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    /// Returns the generation of the ring, which changes each time it is
+    /// re-created, waking `cx` when it does.
+    pub(crate) fn poll_generation(&mut self, cx: &Context<'_>) -> u64 {
+        match &self.generation_waker {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            _ => self.generation_waker = Some(cx.waker().clone()),
+        }
+        self.generation
+    }
+
+    /// Returns a weak reference to the fd of the runtime, which is only
+    /// valid while the driver is alive.
+    pub(crate) fn ring_fd(&self) -> Weak<OwnedFd> {
+        Arc::downgrade(&self.ring_fd)
+    }
+
+    pub(crate) fn report_error(&self, error: DriverError) {
+        report_error(&self.on_error, error)
+    }
+
+    fn wait(&mut self) -> io::Result<usize> {
+        self.submit_and_wait(1)
+    }
+
+    /// Submits queued entries and waits for `want` completions, as
+    /// [`Submitter::submit_and_wait`](io_uring::Submitter::submit_and_wait)
+    /// does, through the registered ring fd if there is one.
+    pub(crate) fn submit_and_wait(&mut self, want: usize) -> io::Result<usize> {
+        let params = self.uring.params();
+        let iopoll = params.is_setup_iopoll();
+        let sqpoll = params.is_setup_sqpoll();
+
+        let sq = self.uring.submission();
+        let len = sq.len();
+        let (need_wakeup, cq_overflow) = (sq.need_wakeup(), sq.cq_overflow());
+        drop(sq);
+
+        let mut flags = 0;
+        if want > 0 || iopoll || cq_overflow {
+            flags |= IORING_ENTER_GETEVENTS;
+        }
+        if sqpoll {
+            if need_wakeup {
+                flags |= IORING_ENTER_SQ_WAKEUP;
+            } else if want == 0 {
+                // The SQ thread is awake and picks up the entries on its own.
+                return Ok(len);
+            }
+        }
+
+        self.enter(len as u32, want as u32, flags)
+    }
+
+    fn enter(&self, to_submit: u32, min_complete: u32, flags: u32) -> io::Result<usize> {
+        let res = self.enter_ring(to_submit, min_complete, flags);
+        trace::enter(to_submit, min_complete, &res);
+        res
+    }
+
+    fn enter_ring(&self, to_submit: u32, min_complete: u32, flags: u32) -> io::Result<usize> {
+        let index = match self.ring_index {
+            Some(index) => index,
+            // Safety: no signal mask is passed.
+            None => unsafe {
+                return self.uring.submitter().enter::<libc::sigset_t>(
+                    to_submit,
+                    min_complete,
+                    flags,
+                    None,
+                );
+            },
+        };
+
+        // Safety: the index refers to this ring, and no signal mask is passed.
+        let res = unsafe {
+            libc::syscall(
+                libc::SYS_io_uring_enter,
+                index,
+                to_submit,
+                min_complete,
+                flags | IORING_ENTER_REGISTERED_RING,
+                std::ptr::null::<libc::sigset_t>(),
+                0usize,
+            )
+        };
+        if res < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(res as usize)
+        }
+    }
+
+    // only used in tests rn
+    #[allow(unused)]
+    pub(super) fn num_operations(&self) -> usize {
+        self.ops.lifecycle.len()
+    }
+
+    pub(crate) fn submit(&mut self) -> io::Result<()> {
+        loop {
+            match self.submit_and_wait(0) {
+                Ok(_) => {
+                    self.uring.submission().sync();
+                    return Ok(());
+                }
+                Err(ref e) if e.raw_os_error() == Some(libc::EBUSY) => {
+                    self.dispatch_completions();
+                }
+                Err(e) if e.raw_os_error() != Some(libc::EINTR) => {
+                    return Err(e);
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    // Wait for completions until `n` more ops fit in the completion queue, if
+    // configured to apply backpressure.
+    fn reserve(&mut self, n: usize) {
+        if self.cq_overflow_policy != CqOverflow::Backpressure {
+            return;
+        }
+
+        let capacity = self.uring.params().cq_entries() as usize;
+        while self.ops.in_flight > 0 && self.ops.in_flight + n > capacity {
+            if let Err(e) = self.wait() {
+                if e.kind() != io::ErrorKind::Interrupted {
+                    self.report_error(DriverError::Submit(e));
+                    return;
+                }
+            }
+            self.dispatch_completions();
+        }
+    }
+
+    // Fails if `n` more operations would exceed the cap on operations in
+    // flight. Permits hold their slots aside.
+    fn admit(&self, n: usize) -> Result<(), SubmitError> {
+        match self.max_in_flight {
+            Some(max) if self.ops.in_flight + self.permits + n > max => {
+                Err(SubmitError::InFlightLimit)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Polls for a slot under the cap on operations in flight, holding it
+    /// aside until released.
+    pub(crate) fn poll_permit(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.admit(1).is_ok() {
+            self.permits += 1;
+            return Poll::Ready(());
+        }
+
+        self.permit_waiters.push(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Releases the slot held by a permit, to submit with it or to give it
+    /// back.
+    pub(crate) fn release_permit(&mut self) {
+        self.permits -= 1;
+        self.wake_permit_waiters();
+    }
+
+    fn wake_permit_waiters(&mut self) {
+        if self.admit(1).is_ok() {
+            for waker in self.permit_waiters.drain(..) {
+                waker.wake();
+            }
+        }
+    }
+
+    fn insert_op(&mut self) -> usize {
+        if let Some(polled) = &self.polled {
+            polled.notify_one();
+        }
+        self.ops.insert()
+    }
+
+    /// Returns a notification signalled whenever an operation is submitted,
+    /// if the ring is set up for I/O polling.
+    pub(crate) fn polled(&self) -> Option<Rc<Notify>> {
+        self.polled.clone()
+    }
+
+    /// Returns a notification signalled whenever an operation is submitted.
+    pub(crate) fn notify_submissions(&mut self) -> Rc<Notify> {
+        self.polled
+            .get_or_insert_with(|| Rc::new(Notify::new()))
+            .clone()
+    }
+
+    /// Submits queued entries and reaps the completions of polled I/O,
+    /// without waiting. Returns whether operations are still in flight.
+    pub(crate) fn poll_completions(&mut self) -> bool {
+        // On an I/O polling ring, entering the kernel with GETEVENTS is what
+        // polls for completions.
+        if let Err(e) = self.submit() {
+            self.report_error(DriverError::Submit(e));
+        }
+        self.dispatch_budgeted();
+        !self.ops.lifecycle.is_empty()
+    }
+
+    /// Submits queued entries and processes completions without waiting, on
+    /// a runtime spinning on the completion queue. Returns whether there were
+    /// completions to process.
+    pub(crate) fn spin(&mut self) -> bool {
+        if !self.uring.submission().is_empty() {
+            match self.submit_and_wait(0) {
+                Ok(_) => {}
+                Err(ref e)
+                    if matches!(
+                        e.raw_os_error(),
+                        Some(libc::EBUSY) | Some(libc::EAGAIN) | Some(libc::EINTR)
+                    ) => {}
+                Err(e) => self.report_error(DriverError::Submit(e)),
+            }
+        }
+
+        // Deferred completions are only posted by entering the kernel, which
+        // dispatching does.
+        let ready = !self.uring.completion().is_empty();
+        if ready || self.defer_taskrun {
+            self.dispatch_budgeted();
+        }
+        ready
+    }
+
+    pub(crate) fn poll_msg(&mut self, cx: &mut Context<'_>) -> Poll<Message> {
+        match self.messages.pop_front() {
+            Some(msg) => Poll::Ready(msg),
+            None => {
+                self.msg_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Returns `false` if the ring can't run the operation `opcode`, as the
+    /// kernel doesn't support it or the ring is restricted.
+    pub(crate) fn supports(&self, opcode: u8) -> bool {
+        self.probe.as_ref().is_none_or(|p| p.is_supported(opcode))
+            && self
+                .restrictions
+                .as_ref()
+                .is_none_or(|r| r.allows_op(opcode))
+    }
+
+    pub(crate) fn ring_stats(&self) -> RingStats {
+        let params = self.uring.params();
+        RingStats {
+            sq_entries: params.sq_entries(),
+            cq_entries: params.cq_entries(),
+            sq_full: self.sq_full,
+            cq_overflow: self.cq_overflow,
+            cq_dropped: self.cq_dropped,
+            nodrop: params.is_feature_nodrop(),
+            peak_in_flight: self.ops.peak,
+            registered_ring: self.ring_index.is_some(),
+        }
+    }
+
+    pub(crate) fn dump_inflight(&self) -> Vec<InflightOp> {
+        let mut ops: Vec<InflightOp> = self
+            .ops
+            .lifecycle
+            .iter()
+            .filter(|(_, cycle)| match cycle {
+                Lifecycle::Completed(_) => false,
+                Lifecycle::CompletionList(indices) => indices
+                    .peek_end(&self.ops.completions)
+                    .is_some_and(|cqe| cqueue::more(cqe.flags)),
+                _ => true,
+            })
+            .filter_map(|(index, cycle)| {
+                let op = self.ops.submitted(index)?;
+                Some(InflightOp {
+                    user_data: index as u64,
+                    opcode: op.opcode,
+                    fd: op.fd,
+                    fixed_file: op.flags & squeue::Flags::FIXED_FILE.bits() != 0,
+                    submitted: op.at,
+                    abandoned: matches!(cycle, Lifecycle::Ignored(..)),
+                })
+            })
+            .collect();
+        ops.sort_by_key(|op| op.submitted);
+        ops
+    }
+
+    // The completion queue overflowed.
+    fn overflowed(&mut self) {
+        self.cq_overflow += 1;
+        self.check_sizing();
+
+        if self.cq_overflow_policy == CqOverflow::Panic {
+            panic!("completion queue overflowed");
+        }
+    }
+
+    // Report the ring as undersized if the recommended size grew.
+    fn check_sizing(&mut self) {
+        let stats = self.ring_stats();
+        if let Some(entries) = stats.recommended_entries() {
+            if entries > self.recommended_entries {
+                self.recommended_entries = entries;
+                self.report_error(DriverError::Undersized(stats));
+            }
+        }
+    }
+
+    pub(crate) fn dispatch_completions(&mut self) {
+        self.dispatch(usize::MAX);
+    }
+
+    /// Processes completions up to the completion budget. Returns whether
+    /// the budget ran out with completions left to process.
+    pub(crate) fn dispatch_budgeted(&mut self) -> bool {
+        self.dispatch(self.completion_budget)
+    }
+
+    fn dispatch(&mut self, mut budget: usize) -> bool {
+        let mut exhausted = false;
+
+        // Deferred completions are only posted once the kernel is asked for
+        // them, which runs the pending task work.
+        // Cooperative completions are pending until the thread next enters
+        // the kernel; the kernel flags them so they need not wait for that.
+        let taskrun = self.coop_taskrun && self.uring.submission().taskrun();
+        if self.defer_taskrun || taskrun {
+            if let Err(e) = self.get_events() {
+                self.report_error(DriverError::Submit(e));
+            }
+        }
+
+        loop {
+            let on_error = &self.on_error;
+            let mut cq = self.uring.completion();
+            cq.sync();
+            self.cq_dropped = cq.overflow();
+
+            if budget == 0 {
+                exhausted = !cq.is_empty();
+                break;
+            }
+
+            let mut reaped = 0;
+            let batch = self.cqe_batch.min(budget);
+
+            // Dropping the queue at the end of the batch hands the consumed
+            // entries back to the kernel.
+            for (cqe, big) in cq.take(batch) {
+                reaped += 1;
+
+                if cqe.user_data() == CANCEL {
+                    // Result of the cancellation action. There isn't anything we
+                    // need to do here. We must wait for the CQE for the operation
+                    // that was canceled. The operation may have completed or be
+                    // completing already.
+                    let res = cqe.result();
+                    if res < 0 && res != -libc::ENOENT && res != -libc::EALREADY {
+                        let e = io::Error::from_raw_os_error(-res);
+                        report_error(on_error, DriverError::Cancel(e));
+                    }
+                    continue;
+                }
+
+                let (user_data, result) = (cqe.user_data(), cqe.result());
+
+                if user_data & MSG_TAG != 0 {
+                    // A message from another ring.
+                    self.messages
+                        .push_back(Message::new(result, user_data & !MSG_TAG));
+                    if let Some(waker) = self.msg_waker.take() {
+                        waker.wake();
+                    }
+                    continue;
+                }
+
+                if user_data & DEADLINE_TAG != 0 {
+                    // Result of the timeout linked to an operation. An
+                    // expired one cancels the operation, which then
+                    // completes on its own.
+                    continue;
+                }
+
+                if let Some(drain) = &mut self.draining {
+                    if !cqueue::more(cqe.flags()) && self.ops.is_ignored(user_data as _) {
+                        if result == -libc::ECANCELED {
+                            drain.cancelled += 1;
+                        } else {
+                            drain.completed += 1;
+                        }
+                    }
+                }
+
+                if !self.ops.complete(user_data as _, cqe, big) {
+                    report_error(
+                        on_error,
+                        DriverError::UnexpectedCompletion { user_data, result },
+                    );
+                }
+            }
+            trace::dispatch(reaped, self.ops.in_flight);
+
+            // Completions the queue had no room for are held back by the
+            // kernel until flushed. Nothing else flushes them when the SQ
+            // thread submits on our behalf.
+            if self.uring.submission().cq_overflow() {
+                self.overflowed();
+
+                if let Err(e) = self.get_events() {
+                    self.report_error(DriverError::Submit(e));
+                    break;
+                }
+                continue;
+            }
+
+            budget -= reaped;
+
+            // A full batch may have left entries behind, peek again. Otherwise
+            // the queue was drained, only peek again if configured to.
+            if reaped < batch && !(self.cqe_repeek && reaped > 0) {
+                break;
+            }
+        }
+
+        // Reaping completions lets the kernel take the entries it had no room
+        // for, check for room again.
+        for waker in self.sq_waiters.drain(..) {
+            waker.wake();
+        }
+        self.wake_permit_waiters();
+
+        exhausted
+    }
+
+    /// Polls for room in the submission queue, returning the number of free
+    /// entries once there is at least one.
+    pub(crate) fn poll_sq_space(&mut self, cx: &mut Context<'_>) -> Poll<usize> {
+        if let Some(space) = self.sq_space() {
+            return Poll::Ready(space);
+        }
+
+        // Flush the queue to the kernel to make room.
+        match self.submit_and_wait(0) {
+            Ok(_) => {}
+            Err(ref e)
+                if matches!(
+                    e.raw_os_error(),
+                    Some(libc::EBUSY) | Some(libc::EAGAIN) | Some(libc::EINTR)
+                ) => {}
+            Err(e) => self.report_error(DriverError::Submit(e)),
+        }
+
+        if let Some(space) = self.sq_space() {
+            return Poll::Ready(space);
+        }
+
+        if self.uring.params().is_setup_sqpoll() {
+            // The SQ thread consumes entries on its own schedule, without
+            // anything to wait on. Yield to other tasks meanwhile.
+            cx.waker().wake_by_ref();
+        } else {
+            self.sq_waiters.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+
+    fn sq_space(&mut self) -> Option<usize> {
+        let mut sq = self.uring.submission();
+        sq.sync();
+        Some(sq.capacity() - sq.len()).filter(|&space| space > 0)
+    }
+
+    // Enter the kernel to collect completion events, which flushes
+    // overflowed completions and runs deferred task work.
+    fn get_events(&mut self) -> io::Result<()> {
+        loop {
+            match self.enter(0, 0, IORING_ENTER_GETEVENTS) {
+                Err(ref e) if e.raw_os_error() == Some(libc::EINTR) => continue,
+                res => return res.map(drop),
+            }
+        }
+    }
+
+    pub(crate) fn register_buffers(
+        &mut self,
+        buffers: Rc<RefCell<dyn FixedBuffers>>,
+    ) -> io::Result<()> {
+        {
+            let buffers = buffers.borrow();
+            let iovecs = buffers.iovecs();
+            unsafe { self.uring.submitter().register_buffers(iovecs) }.map_err(|e| {
+                // Surface RLIMIT_MEMLOCK accounting on pinning failures.
+                let len = iovecs.iter().map(|iov| iov.iov_len).sum();
+                crate::buf::fixed::memlock_error(e, len)
+            })?;
+        }
+
+        self.fixed_buffers = Some(buffers);
+        Ok(())
+    }
+
+    pub(crate) fn unregister_buffers(
+        &mut self,
+        buffers: Rc<RefCell<dyn FixedBuffers>>,
+    ) -> io::Result<()> {
+        if let Some(currently_registered) = &self.fixed_buffers {
+            if Rc::ptr_eq(&buffers, currently_registered) {
+                self.uring.submitter().unregister_buffers()?;
+                self.fixed_buffers = None;
+                return Ok(());
+            }
+        }
+        Err(io::Error::other(
+            "fixed buffers are not currently registered",
+        ))
+    }
+
+    pub(crate) fn register_files(&mut self, fds: &[RawFd]) -> io::Result<()> {
+        self.uring.submitter().register_files(fds)?;
+        self.files_registered = true;
+
+        Ok(())
+    }
+
+    pub(crate) fn unregister_files(&mut self) -> io::Result<()> {
+        self.uring.submitter().unregister_files()?;
+        self.files_registered = false;
+
+        Ok(())
+    }
+
+    pub(crate) fn register_personality(&mut self) -> io::Result<u16> {
+        let id = self.uring.submitter().register_personality()?;
+        self.personalities += 1;
+        Ok(id)
+    }
+
+    pub(crate) fn unregister_personality(&mut self, id: u16) -> io::Result<()> {
+        self.uring.submitter().unregister_personality(id)?;
+        self.personalities -= 1;
+        Ok(())
+    }
+
+    pub(crate) fn register_eventfd(&mut self, fd: RawFd, async_only: bool) -> io::Result<()> {
+        register_eventfd(&self.uring, fd, async_only)?;
+        self.eventfd = Some((fd, async_only));
+        Ok(())
+    }
+
+    pub(crate) fn unregister_eventfd(&mut self) -> io::Result<()> {
+        self.uring.submitter().unregister_eventfd()?;
+        self.eventfd = None;
+        Ok(())
+    }
+
+    /// Hands out a free slot of the table of direct descriptors.
+    pub(crate) fn alloc_direct(&mut self) -> io::Result<u32> {
+        self.files
+            .as_mut()
+            .ok_or_else(|| {
+                io::Error::other(
+                    "direct descriptors are not set up, see `Builder::direct_descriptors`",
+                )
+            })?
+            .alloc()
+    }
+
+    /// Installs `fd` in a free slot of the table of direct descriptors.
+    pub(crate) fn register_direct(&mut self, fd: RawFd) -> io::Result<u32> {
+        let slot = self.alloc_direct()?;
+
+        if let Err(e) = self.uring.submitter().register_files_update(slot, &[fd]) {
+            let _ = self.release_direct(slot, false);
+            return Err(e);
+        }
+        Ok(slot)
+    }
+
+    /// Releases the slot of a direct descriptor, closing it unless it was
+    /// closed already.
+    pub(crate) fn release_direct(&mut self, slot: u32, close: bool) -> io::Result<()> {
+        let res = if close {
+            self.uring
+                .submitter()
+                .register_files_update(slot, &[-1])
+                .map(drop)
+        } else {
+            Ok(())
+        };
+        if let Some(files) = &mut self.files {
+            files.release(slot);
+        }
+        res
+    }
+
+    // Push an entry to the submission queue, flushing the queue to the kernel
+    // if it is full.
+    //
+    // If the kernel can't accept entries until completions are processed,
+    // `block` keeps dispatching completions until it can, otherwise the queue
+    // is reported full.
+    fn push(&mut self, sqe: &squeue::Entry, block: bool) -> Result<(), SubmitError> {
+        // Safety: the caller keeps the resources of the entry alive until it
+        // completes.
+        self.push_with(|sq| unsafe { sq.push(sqe) }, block)
+    }
+
+    fn push_with(
+        &mut self,
+        mut push: impl FnMut(&mut ring::Sq<'_>) -> bool,
+        block: bool,
+    ) -> Result<(), SubmitError> {
+        let mut busy = false;
+
+        // The mock backend has recorded the entry.
+        if self.is_mock() {
+            return Ok(());
+        }
+
+        if push(&mut self.uring.submission()) {
+            self.submit_full_batch();
+            return Ok(());
+        }
+
+        self.sq_full += 1;
+        self.check_sizing();
+
+        while !push(&mut self.uring.submission()) {
+            match self.submit_and_wait(0) {
+                Ok(_) => {
+                    self.uring.submission().sync();
+                    if self.uring.params().is_setup_sqpoll() && self.uring.submission().is_full() {
+                        // The SQ thread consumes entries on its own schedule,
+                        // wait for it to make room rather than spin.
+                        self.uring
+                            .submitter()
+                            .squeue_wait()
+                            .map_err(SubmitError::Ring)?;
+                    }
+                }
+                Err(ref e)
+                    if matches!(e.raw_os_error(), Some(libc::EBUSY) | Some(libc::EAGAIN)) =>
+                {
+                    // Make room in the completion queue, once unless blocking,
+                    // if the kernel still can't accept entries the queue is
+                    // full.
+                    if busy && !block {
+                        return Err(SubmitError::QueueFull);
+                    }
+                    busy = true;
+                    self.dispatch_completions();
+                }
+                Err(ref e) if e.raw_os_error() == Some(libc::EINTR) => {}
+                Err(e) => return Err(SubmitError::Ring(e)),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Asks the kernel to cancel the in-flight operation at `index`.
+    ///
+    /// The operation still posts its final CQE, which is handled as usual.
+    pub(crate) fn cancel_op(&mut self, index: usize) -> io::Result<()> {
+        let sqe = AsyncCancel::new(index as u64).build().user_data(CANCEL);
+        trace::cancel(index, self.ops.submitted(index));
+
+        if let Some(polled) = &self.polled {
+            polled.notify_one();
+        }
+
+        #[cfg(feature = "mock")]
+        if let Some(mock) = &mut self.mock {
+            mock.cancel(index as u64);
+            return Ok(());
+        }
+
+        while unsafe { !self.uring.submission().push(&sqe) } {
+            // If the submission queue is full, flush it to the kernel
+            self.submit()?;
+        }
+
+        Ok(())
+    }
+
+    // Cancel an op whose future was dropped, so that it releases its data
+    // promptly rather than once it completes on its own.
+    fn cancel_dropped(&mut self, index: usize) {
+        // Ops dropped on shutdown are cancelled when the driver drains.
+        if self.shutting_down {
+            return;
+        }
+        if let Err(e) = self.cancel_op(index) {
+            self.report_error(DriverError::Submit(e));
+        }
+    }
+
+    /// Stops cancelling the ops dropped from here on, leaving them to the
+    /// drain.
+    pub(crate) fn shut_down(&mut self) {
+        self.shutting_down = true;
+    }
+
+    // Submit the pending entries once a batch of them is ready.
+    fn submit_full_batch(&mut self) {
+        if self.uring.submission().len() < self.submit_batch {
+            return;
+        }
+
+        match self.submit_and_wait(0) {
+            Ok(_) => {}
+            // The entries are submitted later on.
+            Err(ref e)
+                if matches!(
+                    e.raw_os_error(),
+                    Some(libc::EBUSY) | Some(libc::EAGAIN) | Some(libc::EINTR)
+                ) => {}
+            Err(e) => self.report_error(DriverError::Submit(e)),
+        }
+    }
+
+    // An operation skipping its completion on success
+    // (`IOSQE_CQE_SKIP_SUCCESS`) is known to have succeeded once a later
+    // operation in its chain completes, so chains with such operations are
+    // tracked. One ending its chain has no later operation, so it posts its
+    // completion as usual.
+    fn link_skipped(&mut self, index: usize, mut sqe: squeue::Entry) -> squeue::Entry {
+        let skip = squeue::Flags::SKIP_SUCCESS.bits();
+        let linked = (squeue::Flags::IO_LINK | squeue::Flags::IO_HARDLINK).bits();
+
+        // Safety: an SQE is an io_uring_sqe, whose flags are its second byte.
+        let flags = unsafe { &mut *(&mut sqe as *mut squeue::Entry as *mut u8).add(1) };
+
+        if *flags & linked == 0 {
+            *flags &= !skip;
+        }
+        let flags = *flags;
+
+        if flags & linked == 0 && self.chain.is_empty() {
+            return sqe;
+        }
 
-    // only used in tests rn
-    #[allow(unused)]
-    pub(super) fn num_operations(&self) -> usize {
-        self.ops.lifecycle.len()
+        self.chain.push(Linked::new(index, &sqe));
+        if flags & linked == 0 {
+            let chain = std::mem::take(&mut self.chain);
+            if chain.iter().any(Linked::skips_success) {
+                self.ops.chains.push(chain);
+            }
+        }
+        sqe
     }
 
-    pub(crate) fn submit(&mut self) -> io::Result<()> {
-        loop {
-            match self.uring.submit() {
-                Ok(_) => {
-                    self.uring.submission().sync();
-                    return Ok(());
-                }
-                Err(ref e) if e.raw_os_error() == Some(libc::EBUSY) => {
-                    self.dispatch_completions();
-                }
-                Err(e) if e.raw_os_error() != Some(libc::EINTR) => {
-                    return Err(e);
-                }
-                _ => continue,
-            }
+    // Returns how long the operation may take, if it gets a deadline.
+    //
+    // A timeout linked to an operation must directly follow it, so linked
+    // operations get none. Nor do operations waiting for events by design
+    // get the default.
+    fn deadline(&self, sqe: &squeue::Entry, timeout: OpTimeout) -> Option<Duration> {
+        let op = Submitted::new(sqe);
+        let linked = (squeue::Flags::IO_LINK | squeue::Flags::IO_HARDLINK).bits();
+        if op.flags & linked != 0 || !self.chain.is_empty() {
+            return None;
+        }
+
+        match timeout {
+            OpTimeout::Default if waits_for_events(op.opcode) => None,
+            OpTimeout::Default => self.op_timeout,
+            OpTimeout::Disabled => None,
+            OpTimeout::After(timeout) => Some(timeout),
         }
     }
 
-    pub(crate) fn dispatch_completions(&mut self) {
-        let mut cq = self.uring.completion();
-        cq.sync();
-
-        for cqe in cq {
-            if cqe.user_data() == u64::MAX {
-                // Result of the cancellation action. There isn't anything we
-                // need to do here. We must wait for the CQE for the operation
-                // that was canceled.
-                continue;
-            }
+    // Pushes the operation, followed by a timeout linked to it
+    // (`IORING_OP_LINK_TIMEOUT`) if it has a deadline.
+    fn push_op(
+        &mut self,
+        index: usize,
+        sqe: &squeue::Entry,
+        deadline: Option<Duration>,
+        block: bool,
+    ) -> Result<(), SubmitError> {
+        let timeout = match deadline {
+            Some(timeout) => timeout,
+            None => return self.push(sqe, block),
+        };
+
+        let ts = Box::new(Timespec::from(timeout));
+        let entries = [
+            sqe.clone().flags(squeue::Flags::IO_LINK),
+            opcode::LinkTimeout::new(&*ts)
+                .build()
+                .user_data(DEADLINE_TAG | index as u64),
+        ];
+        self.ops
+            .deadlines
+            .insert(index, (ts, Instant::now() + timeout));
+
+        // The two are pushed at once, so that the operation isn't submitted
+        // without its timeout.
+        // Safety: the caller keeps the resources of the operation alive until
+        // it completes, the driver keeps the timespec.
+        self.push_with(|sq| unsafe { sq.push_multiple(&entries) }, block)
+    }
 
-            let index = cqe.user_data() as _;
+    // Tracks a submitted operation, and records it with the mock backend.
+    fn record(&mut self, index: usize, sqe: &squeue::Entry) {
+        self.ops.submit(index, sqe);
 
-            self.ops.complete(index, cqe);
+        #[cfg(feature = "mock")]
+        if let Some(mock) = &mut self.mock {
+            mock.record(sqe);
         }
     }
 
-    pub(crate) fn register_buffers(
-        &mut self,
-        buffers: Rc<RefCell<dyn FixedBuffers>>,
-    ) -> io::Result<()> {
-        unsafe {
-            self.uring
-                .submitter()
-                .register_buffers(buffers.borrow().iovecs())
-        }?;
+    // Returns `true` if operations go to the mock backend rather than the
+    // kernel.
+    pub(crate) fn is_mock(&self) -> bool {
+        #[cfg(feature = "mock")]
+        return self.mock.is_some();
 
-        self.fixed_buffers = Some(buffers);
-        Ok(())
+        #[cfg(not(feature = "mock"))]
+        false
     }
 
-    pub(crate) fn unregister_buffers(
-        &mut self,
-        buffers: Rc<RefCell<dyn FixedBuffers>>,
-    ) -> io::Result<()> {
-        if let Some(currently_registered) = &self.fixed_buffers {
-            if Rc::ptr_eq(&buffers, currently_registered) {
-                self.uring.submitter().unregister_buffers()?;
-                self.fixed_buffers = None;
-                return Ok(());
+    pub(crate) fn submit_op_2(&mut self, sqe: squeue::Entry, timeout: OpTimeout) -> usize {
+        match self.push_op_2(sqe, timeout, true) {
+            Ok(index) => index,
+            Err(e) => {
+                let e = io::Error::from(e);
+                self.report_error(DriverError::Submit(e));
+                panic!("Internal error, failed to submit ops");
             }
         }
-        Err(io::Error::new(
-            io::ErrorKind::Other,
-            "fixed buffers are not currently registered",
-        ))
     }
 
-    pub(crate) fn register_files(&mut self, fds: &[RawFd]) -> io::Result<()> {
-        self.uring.submitter().register_files(fds)?;
-
-        Ok(())
+    pub(crate) fn try_submit_op_2(
+        &mut self,
+        sqe: squeue::Entry,
+        timeout: OpTimeout,
+    ) -> Result<usize, SubmitError> {
+        self.push_op_2(sqe, timeout, false)
     }
 
-    pub(crate) fn unregister_files(&mut self) -> io::Result<()> {
-        self.uring.submitter().unregister_files()?;
+    fn push_op_2(
+        &mut self,
+        sqe: squeue::Entry,
+        timeout: OpTimeout,
+        block: bool,
+    ) -> Result<usize, SubmitError> {
+        self.admit(1)?;
+        self.reserve(1);
+        let index = self.insert_op();
 
-        Ok(())
+        // Configure the SQE
+        let deadline = self.deadline(&sqe, timeout);
+        let sqe = self.link_skipped(index, sqe.user_data(index as _));
+        self.record(index, &sqe);
+
+        // Push the new operation
+        if let Err(e) = self.push_op(index, &sqe, deadline, block) {
+            self.ops.abort(index);
+            return Err(e);
+        }
+
+        Ok(index)
     }
 
-    pub(crate) fn submit_op_2(&mut self, sqe: squeue::Entry) -> usize {
-        let index = self.ops.insert();
+    pub(crate) fn try_submit_op_128(
+        &mut self,
+        mut sqe: squeue::Entry128,
+    ) -> Result<usize, SubmitError> {
+        if !self.uring.is_sqe128() {
+            return Err(SubmitError::Ring(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the ring has regular submission queue entries",
+            )));
+        }
+
+        self.admit(1)?;
+        self.reserve(1);
+        let index = self.insert_op();
+        self.ops.big.insert(index, None);
 
         // Configure the SQE
-        let sqe = sqe.user_data(index as _);
+        sqe = sqe.user_data(index as _);
+        // Safety: a big SQE starts with a regular one.
+        let head = unsafe { &mut *(&mut sqe as *mut squeue::Entry128 as *mut squeue::Entry) };
+        *head = self.link_skipped(index, head.clone());
+        self.record(index, head);
 
         // Push the new operation
-        while unsafe { self.uring.submission().push(&sqe).is_err() } {
-            // If the submission queue is full, flush it to the kernel
-            self.submit().expect("Internal error, failed to submit ops");
+        // Safety: the caller keeps the resources of the entry alive until it
+        // completes.
+        if let Err(e) = self.push_with(|sq| unsafe { sq.push128(&sqe) }, false) {
+            self.ops.abort(index);
+            return Err(e);
         }
 
-        index
+        Ok(index)
     }
 
     pub(crate) fn submit_ops(&mut self, sqes: impl Iterator<Item = squeue::Entry>) -> Vec<usize> {
+        let sqes: Vec<squeue::Entry> = sqes.collect();
+        if let Err(e) = self.admit(sqes.len()) {
+            self.report_error(DriverError::Submit(e.into()));
+            panic!("Internal error, failed to submit ops");
+        }
+        self.reserve(sqes.len());
+
         let mut indices = Vec::new();
         let mut entries: Vec<squeue::Entry> = Vec::new();
         for sqe in sqes {
-            let index = self.ops.insert();
+            let index = self.insert_op();
             indices.push(index);
 
             // Configure the SQE
-            let sqe = sqe.user_data(index as _);
+            let sqe = self.link_skipped(index, sqe.user_data(index as _));
+            self.record(index, &sqe);
             entries.push(sqe);
         }
 
-        while unsafe { self.uring.submission().push_multiple(&entries).is_err() } {
+        while unsafe { !self.is_mock() && !self.uring.submission().push_multiple(&entries) } {
             // If the submission queue is full, flush it to the kernel
-            self.submit().expect("Internal error, failed to submit ops");
+            if let Err(e) = self.submit() {
+                self.report_error(DriverError::Submit(e));
+                panic!("Internal error, failed to submit ops");
+            }
         }
+        self.submit_full_batch();
 
         indices
     }
@@ -181,21 +1401,79 @@ impl Driver {
         T: Completable,
         F: FnOnce(&mut T) -> squeue::Entry,
     {
-        let index = self.ops.insert();
+        self.admit(1)?;
+        self.reserve(1);
+        let index = self.insert_op();
 
         // Configure the SQE
-        let sqe = f(&mut data).user_data(index as _);
+        let sqe = f(&mut data);
+        let deadline = self.deadline(&sqe, OpTimeout::Default);
+        let sqe = self.link_skipped(index, sqe.user_data(index as _));
+        self.record(index, &sqe);
+
+        // Push the new operation
+        if let Err(e) = self.push_op(index, &sqe, deadline, true) {
+            self.ops.abort(index);
+            return Err(e.into());
+        }
 
         // Create the operation
-        let op = Op::new(handle, data, index);
+        Ok(Op::new(handle, data, index))
+    }
 
-        // Push the new operation
-        while unsafe { self.uring.submission().push(&sqe).is_err() } {
-            // If the submission queue is full, flush it to the kernel
-            self.submit()?;
+    // Submits operations linked into a chain by `f`, all of them or none, so
+    // that a failure never leaves part of the chain in the queue. On failure
+    // the data of the operations is handed back.
+    pub(crate) fn submit_linked_ops<T, S, F>(
+        &mut self,
+        mut data: Vec<T>,
+        mut f: F,
+        handle: WeakHandle,
+    ) -> Result<Vec<Op<T, S>>, crate::Error<Vec<T>>>
+    where
+        T: Completable,
+        F: FnMut(&mut T) -> squeue::Entry,
+    {
+        if data.len() > self.uring.params().sq_entries() as usize {
+            let e = io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the chain is longer than the submission queue",
+            );
+            return Err(crate::Error(e, data));
+        }
+        if let Err(e) = self.admit(data.len()) {
+            return Err(crate::Error(e.into(), data));
+        }
+        self.reserve(data.len());
+
+        let mut indices = Vec::with_capacity(data.len());
+        let mut entries = Vec::with_capacity(data.len());
+        for data in &mut data {
+            let index = self.insert_op();
+            indices.push(index);
+
+            // Configure the SQE
+            let sqe = self.link_skipped(index, f(data).user_data(index as _));
+            self.record(index, &sqe);
+            entries.push(sqe);
+        }
+
+        // Push the whole chain at once
+        // Safety: the operations keep the resources of the entries alive until
+        // they complete.
+        if let Err(e) = self.push_with(|sq| unsafe { sq.push_multiple(&entries) }, true) {
+            for index in indices {
+                self.ops.abort(index);
+            }
+            return Err(crate::Error(e.into(), data));
         }
 
-        Ok(op)
+        // Create the operations
+        Ok(data
+            .into_iter()
+            .zip(indices)
+            .map(|(data, index)| Op::new(handle.clone(), data, index))
+            .collect())
     }
 
     pub(crate) fn remove_op<T, CqeType>(&mut self, op: &mut Op<T, CqeType>) {
@@ -208,12 +1486,14 @@ impl Driver {
             }
         };
 
-        match mem::replace(lifecycle, Lifecycle::Submitted) {
+        let ignored = match mem::replace(lifecycle, Lifecycle::Submitted) {
             Lifecycle::Submitted | Lifecycle::Waiting(_) => {
                 *lifecycle = Lifecycle::Ignored(Box::new(op.take_data()));
+                true
             }
             Lifecycle::Completed(..) => {
                 self.ops.remove(op.index());
+                false
             }
             Lifecycle::CompletionList(indices) => {
                 // Deallocate list entries, recording if more CQE's are expected
@@ -228,8 +1508,13 @@ impl Driver {
                 } else {
                     self.ops.remove(op.index());
                 }
+                more
             }
             Lifecycle::Ignored(..) => unreachable!(),
+        };
+
+        if ignored {
+            self.cancel_dropped(op.index());
         }
     }
 
@@ -243,12 +1528,14 @@ impl Driver {
             }
         };
 
-        match mem::replace(lifecycle, Lifecycle::Submitted) {
+        let ignored = match mem::replace(lifecycle, Lifecycle::Submitted) {
             Lifecycle::Submitted | Lifecycle::Waiting(_) => {
                 *lifecycle = Lifecycle::Ignored(Box::new(data));
+                true
             }
             Lifecycle::Completed(..) => {
                 self.ops.remove(index);
+                false
             }
             Lifecycle::CompletionList(indices) => {
                 // Deallocate list entries, recording if more CQE's are expected
@@ -263,11 +1550,27 @@ impl Driver {
                 } else {
                     self.ops.remove(index);
                 }
+                more
             }
             Lifecycle::Ignored(..) => unreachable!(),
+        };
+
+        if ignored {
+            self.cancel_dropped(index);
         }
     }
 
+    pub(crate) fn poll_op_32(
+        &mut self,
+        index: usize,
+        cx: &mut Context<'_>,
+    ) -> Poll<cqueue::Entry32> {
+        // Rings with regular completion queue entries have no extra bytes.
+        let big = self.ops.big.get(&index).copied().flatten();
+        let cqe = ready!(self.poll_op_2(index, cx));
+        Poll::Ready(op::big_cqe(cqe, big.unwrap_or_default()))
+    }
+
     pub(crate) fn poll_op_2(&mut self, index: usize, cx: &mut Context<'_>) -> Poll<cqueue::Entry> {
         let (lifecycle, _) = self.ops.get_mut(index).expect("invalid internal state");
 
@@ -391,9 +1694,268 @@ impl Driver {
     }
 }
 
+const IORING_ENTER_GETEVENTS: u32 = 1 << 0;
+const IORING_ENTER_SQ_WAKEUP: u32 = 1 << 1;
+const IORING_ENTER_REGISTERED_RING: u32 = 1 << 4;
+
+// Sets up a ring as configured by `b`, with `cq_entries` completion queue
+// entries if given, returning it along with the operations it supports.
+fn build_ring(b: &crate::Builder, cq_entries: Option<u32>) -> io::Result<(Ring, Option<Probe>)> {
+    // Keep the fd of the ring to attach to open until the ring is set up.
+    let attach_wq = match &b.attach_wq {
+        Some(fd) => Some(
+            fd.upgrade()
+                .ok_or_else(|| io::Error::other("the runtime to attach to has shut down"))?,
+        ),
+        None => None,
+    };
+
+    let uring = Ring::build(b, |urb| {
+        if let Some(entries) = cq_entries {
+            urb.setup_cqsize(entries);
+        }
+        if b.iopoll {
+            urb.setup_iopoll();
+        }
+        if b.single_issuer || b.defer_taskrun {
+            urb.setup_single_issuer();
+        }
+        if b.defer_taskrun {
+            urb.setup_defer_taskrun();
+        }
+        if b.coop_taskrun {
+            urb.setup_coop_taskrun();
+            urb.setup_taskrun_flag();
+        }
+        if let Some(idle) = b.sqpoll {
+            urb.setup_sqpoll(idle);
+            if let Some(cpu) = b.sq_thread_cpu {
+                urb.setup_sqpoll_cpu(cpu);
+            }
+        }
+        if let Some(fd) = &attach_wq {
+            urb.setup_attach_wq(fd.as_raw_fd());
+        }
+
+        // Restrictions can only be installed while the ring is disabled.
+        if b.restrictions.is_some() {
+            urb.setup_r_disabled();
+        }
+    })?;
+    let submitter = uring.submitter();
+
+    // Probe while the ring is still disabled, before restrictions apply
+    // to register operations. Kernels before 5.6 can't tell.
+    let probe = Probe::new(&submitter, uring.params()).ok();
+
+    if let Some(affinity) = &b.iowq_affinity {
+        submitter.register_iowq_aff(&affinity.cpu_set()?)?;
+    }
+
+    if let Some(n) = b.direct_descriptors {
+        submitter.register_files_sparse(n)?;
+    }
+
+    if let Some(restrictions) = &b.restrictions {
+        submitter.register_restrictions(&mut restrictions.to_restrictions())?;
+        submitter.register_enable_rings()?;
+    }
+
+    if let Some((timeout, prefer_busy_poll)) = b.napi {
+        register_napi(&uring, timeout, prefer_busy_poll)?;
+    }
+
+    Ok((uring, probe))
+}
+
+fn register_eventfd(uring: &Ring, fd: RawFd, async_only: bool) -> io::Result<()> {
+    let submitter = uring.submitter();
+    if async_only {
+        submitter.register_eventfd_async(fd)
+    } else {
+        submitter.register_eventfd(fd)
+    }
+}
+
+// Registers the ring fd with the ring itself (`IORING_REGISTER_RING_FDS`),
+// returning its index. Kernels before 5.18 don't support this, in which case
+// the ring fd is used as is.
+fn register_ring_fd(uring: &Ring) -> Option<u32> {
+    const IORING_REGISTER_RING_FDS: libc::c_uint = 20;
+
+    #[repr(C)]
+    struct RsrcUpdate {
+        offset: u32,
+        resv: u32,
+        data: u64,
+    }
+
+    // An offset of `u32::MAX` lets the kernel pick a free index.
+    let mut update = RsrcUpdate {
+        offset: u32::MAX,
+        resv: 0,
+        data: uring.as_raw_fd() as u64,
+    };
+    // Safety: the update outlives the call, and describes a single entry.
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_io_uring_register,
+            uring.as_raw_fd(),
+            IORING_REGISTER_RING_FDS,
+            &mut update as *mut RsrcUpdate,
+            1,
+        )
+    };
+    (res == 1).then_some(update.offset)
+}
+
+// Unregisters the ring fd registered at `index` (`IORING_UNREGISTER_RING_FDS`).
+fn unregister_ring_fd(uring: &Ring, index: u32) {
+    const IORING_UNREGISTER_RING_FDS: libc::c_uint = 21;
+
+    #[repr(C)]
+    struct RsrcUpdate {
+        offset: u32,
+        resv: u32,
+        data: u64,
+    }
+
+    let mut update = RsrcUpdate {
+        offset: index,
+        resv: 0,
+        data: 0,
+    };
+    // Safety: the update outlives the call, and describes a single entry.
+    unsafe {
+        libc::syscall(
+            libc::SYS_io_uring_register,
+            uring.as_raw_fd(),
+            IORING_UNREGISTER_RING_FDS,
+            &mut update as *mut RsrcUpdate,
+            1,
+        );
+    }
+}
+
+// Enables NAPI busy polling on the ring (`IORING_REGISTER_NAPI`).
+fn register_napi(uring: &Ring, timeout: u32, prefer_busy_poll: bool) -> io::Result<()> {
+    const IORING_REGISTER_NAPI: libc::c_uint = 27;
+
+    #[repr(C)]
+    struct Napi {
+        busy_poll_to: u32,
+        prefer_busy_poll: u8,
+        pad: [u8; 3],
+        resv: u64,
+    }
+
+    let mut napi = Napi {
+        busy_poll_to: timeout,
+        prefer_busy_poll: prefer_busy_poll as u8,
+        pad: [0; 3],
+        resv: 0,
+    };
+    // Safety: the settings outlive the call.
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_io_uring_register,
+            uring.as_raw_fd(),
+            IORING_REGISTER_NAPI,
+            &mut napi as *mut Napi,
+            1,
+        )
+    };
+    if res < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+// The register opcode cancelling operations synchronously.
+pub(crate) const IORING_REGISTER_SYNC_CANCEL: u8 = 24;
+
+// Cancels every operation in flight on the ring, waiting for the kernel to
+// have cancelled them (`IORING_REGISTER_SYNC_CANCEL`).
+fn sync_cancel_all(uring: &Ring) -> io::Result<()> {
+    const IORING_ASYNC_CANCEL_ANY: u32 = 1 << 2;
+
+    #[repr(C)]
+    struct SyncCancel {
+        addr: u64,
+        fd: i32,
+        flags: u32,
+        timeout: libc::timespec,
+        opcode: u8,
+        pad: [u8; 7],
+        pad2: [u64; 3],
+    }
+
+    let mut cancel = SyncCancel {
+        addr: 0,
+        fd: -1,
+        flags: IORING_ASYNC_CANCEL_ANY,
+        // Wait for as long as it takes.
+        timeout: libc::timespec {
+            tv_sec: -1,
+            tv_nsec: -1,
+        },
+        opcode: 0,
+        pad: [0; 7],
+        pad2: [0; 3],
+    };
+    // Safety: the request outlives the call.
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_io_uring_register,
+            uring.as_raw_fd(),
+            IORING_REGISTER_SYNC_CANCEL as libc::c_uint,
+            &mut cancel as *mut SyncCancel,
+            1,
+        )
+    };
+    // Operations completing meanwhile leave nothing to cancel.
+    if res < 0 && io::Error::last_os_error().raw_os_error() != Some(libc::ENOENT) {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+// Whether operations with `opcode` wait for events, which may take as long
+// as it takes.
+fn waits_for_events(opcode: u8) -> bool {
+    opcode == opcode::PollAdd::CODE
+        || opcode == opcode::Timeout::CODE
+        || opcode == opcode::Accept::CODE
+}
+
+// Builds a completion queue entry.
+fn raw_cqe(user_data: u64, res: i32, flags: u32) -> cqueue::Entry {
+    #[repr(C)]
+    struct RawCqe {
+        user_data: u64,
+        res: i32,
+        flags: u32,
+    }
+
+    // Safety: a CQE is an io_uring_cqe, which has this layout.
+    unsafe {
+        mem::transmute::<RawCqe, cqueue::Entry>(RawCqe {
+            user_data,
+            res,
+            flags,
+        })
+    }
+}
+
+fn report_error(on_error: &Option<crate::ErrorCallback>, error: DriverError) {
+    if let Some(on_error) = on_error {
+        on_error(&error);
+    }
+}
+
 impl AsRawFd for Driver {
     fn as_raw_fd(&self) -> RawFd {
-        self.uring.as_raw_fd()
+        self.ring_fd.as_raw_fd()
     }
 }
 
@@ -404,11 +1966,29 @@ impl AsRawFd for Driver {
 /// It is possible for this to be run without previously dropping the runtime, but this should only
 /// be possible in the case of [`std::process::exit`].
 ///
-/// This depends on us knowing when ops are completed and done firing.
-/// When multishot ops are added (support exists but none are implemented), a way to know if such
-/// an op is finished MUST be added, otherwise our shutdown process is unsound.
+/// This depends on us knowing when ops are completed and done firing. A multishot op, such as
+/// the poll of [`EpollBridge`](crate::io::EpollBridge), is only done once it posts a CQE without
+/// `IORING_CQE_F_MORE`. One whose last CQE still has the flag set is cancelled like any other op
+/// in flight, and waited for until the kernel posts its final CQE.
 impl Drop for Driver {
     fn drop(&mut self) {
+        self.drain();
+
+        // The further rings are drained once their tasks let go of them.
+        for (_, task) in &self.rings {
+            task.abort();
+        }
+    }
+}
+
+impl Driver {
+    /// Cancel all operations in flight, and wait for the kernel to release
+    /// them. With a shutdown timeout, the operations are given that long to
+    /// complete before they are cancelled.
+    pub(crate) fn drain(&mut self) -> Drain {
+        self.draining = Some(Drain::default());
+        self.shutting_down = true;
+
         // get all ops in flight for cancellation
         while !self.uring.submission().is_empty() {
             self.submit().expect("Internal error when dropping driver");
@@ -423,6 +2003,15 @@ impl Drop for Driver {
                     *cycle = lc;
                 }
 
+                lc @ Lifecycle::Ignored(_) => {
+                    // Keep the data of dropped ops alive until the kernel is
+                    // done with it.
+                    *cycle = lc;
+                    if let Some(drain) = &mut self.draining {
+                        drain.in_flight += 1;
+                    }
+                }
+
                 Lifecycle::CompletionList(indices) => {
                     let mut list = indices.clone().into_list(&mut self.ops.completions);
                     if !io_uring::cqueue::more(list.peek_end().unwrap().flags) {
@@ -430,30 +2019,57 @@ impl Drop for Driver {
                         // safety: zeroed memory is entirely valid with this underlying
                         // representation
                         *cycle = Lifecycle::Completed(unsafe { mem::zeroed() });
+                    } else if let Some(drain) = &mut self.draining {
+                        drain.in_flight += 1;
                     }
                 }
 
                 _ => {
                     // All other states need cancelling.
                     // The mem::replace means these are now marked Ignored.
+                    if let Some(drain) = &mut self.draining {
+                        drain.in_flight += 1;
+                    }
                 }
             }
         }
 
+        // Nothing completes the ops left with the mock backend.
+        #[cfg(feature = "mock")]
+        if self.mock.is_some() {
+            self.mock_cancel_all();
+        }
+
+        // Give the ops a chance to complete on their own first.
+        if let Some(timeout) = self.shutdown_timeout {
+            self.wait_ignored(timeout);
+        }
+
         // Submit cancellation for all ops marked Ignored
-        for (id, cycle) in self.ops.lifecycle.iter_mut() {
-            if let Lifecycle::Ignored(..) = cycle {
-                unsafe {
-                    while self
-                        .uring
-                        .submission()
-                        .push(&AsyncCancel::new(id as u64).build().user_data(u64::MAX))
-                        .is_err()
-                    {
-                        self.uring
-                            .submit_and_wait(1)
-                            .expect("Internal error when dropping driver");
-                    }
+        let mut ignored: Vec<usize> = self
+            .ops
+            .lifecycle
+            .iter()
+            .filter(|(_, cycle)| matches!(cycle, Lifecycle::Ignored(..)))
+            .map(|(id, _)| id)
+            .collect();
+
+        // Cancel them all at once, returning once the kernel is done with
+        // them, rather than with an entry each. Kernels before 6.0, and rings
+        // restricted from it, don't support this.
+        if !ignored.is_empty() && sync_cancel_all(&self.uring).is_ok() {
+            ignored.clear();
+        }
+
+        for id in ignored {
+            unsafe {
+                while !self
+                    .uring
+                    .submission()
+                    .push(&AsyncCancel::new(id as u64).build().user_data(CANCEL))
+                {
+                    self.submit_and_wait(1)
+                        .expect("Internal error when dropping driver");
                 }
             }
         }
@@ -473,9 +2089,13 @@ impl Drop for Driver {
             // If there is at least one Ignored still to process, call wait
             match self.ops.lifecycle.get(id) {
                 Some(Lifecycle::Ignored(..)) => {
-                    // If waiting fails, ignore the error. The wait will be attempted
+                    // If waiting fails, report the error. The wait will be attempted
                     // again on the next loop.
-                    let _ = self.wait();
+                    if let Err(e) = self.wait() {
+                        if e.kind() != io::ErrorKind::Interrupted {
+                            self.report_error(DriverError::Submit(e));
+                        }
+                    }
                     self.dispatch_completions();
                 }
 
@@ -490,14 +2110,53 @@ impl Drop for Driver {
                 }
             }
         }
+
+        self.draining.take().unwrap_or_default()
+    }
+
+    // Wait for up to `timeout` for the ops marked Ignored to complete.
+    fn wait_ignored(&mut self, timeout: Duration) {
+        let deadline = Instant::now() + timeout;
+
+        while self.ops.has_ignored() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+
+            let ts = Timespec::from(remaining);
+            let args = SubmitArgs::new().timespec(&ts);
+            match self.uring.submitter().submit_with_args(1, &args) {
+                Ok(_) => {}
+                Err(ref e) if e.raw_os_error() == Some(libc::ETIME) => {}
+                Err(ref e) if e.raw_os_error() == Some(libc::EINTR) => {}
+                Err(e) => {
+                    self.report_error(DriverError::Submit(e));
+                    break;
+                }
+            }
+            self.dispatch_completions();
+        }
     }
 }
 
 impl Ops {
-    fn new() -> Ops {
+    fn has_ignored(&self) -> bool {
+        self.lifecycle
+            .iter()
+            .any(|(_, cycle)| matches!(cycle, Lifecycle::Ignored(..)))
+    }
+
+    fn new(capacity: usize) -> Ops {
         Ops {
-            lifecycle: Slab::with_capacity(64),
-            completions: Slab::with_capacity(64),
+            lifecycle: Slab::with_capacity(capacity),
+            completions: Slab::with_capacity(capacity),
+            peak: 0,
+            in_flight: 0,
+            chains: Vec::new(),
+            big: HashMap::new(),
+            submitted: Vec::with_capacity(capacity),
+            deadlines: HashMap::new(),
         }
     }
 
@@ -508,20 +2167,136 @@ impl Ops {
             .map(|lifecycle| (lifecycle, completions))
     }
 
+    // Whether the submitter of the operation lost interest in it
+    fn is_ignored(&self, index: usize) -> bool {
+        matches!(self.lifecycle.get(index), Some(Lifecycle::Ignored(..)))
+    }
+
     // Insert a new operation
     fn insert(&mut self) -> usize {
-        self.lifecycle.insert(op::Lifecycle::Submitted)
+        let index = self.lifecycle.insert(op::Lifecycle::Submitted);
+        self.peak = self.peak.max(self.lifecycle.len());
+        self.in_flight += 1;
+        index
+    }
+
+    // Record what the operation at index was submitted as
+    fn submit(&mut self, index: usize, sqe: &squeue::Entry) {
+        let op = Submitted::new(sqe);
+        trace::submit(index, &op);
+        if self.submitted.len() <= index {
+            self.submitted.resize(index + 1, None);
+        }
+        self.submitted[index] = Some(op);
+    }
+
+    fn submitted(&self, index: usize) -> Option<&Submitted> {
+        self.submitted.get(index)?.as_ref()
     }
 
     // Remove an operation
     fn remove(&mut self, index: usize) {
         self.lifecycle.remove(index);
+        self.big.remove(&index);
+    }
+
+    // Remove an operation which failed to submit
+    fn abort(&mut self, index: usize) {
+        self.lifecycle.remove(index);
+        self.big.remove(&index);
+        self.deadlines.remove(&index);
+        self.in_flight -= 1;
+        for chain in &mut self.chains {
+            chain.retain(|op| op.index != index);
+        }
     }
 
-    fn complete(&mut self, index: usize, cqe: cqueue::Entry) {
+    // Returns false if no operation is tracked at index
+    fn complete(&mut self, index: usize, mut cqe: cqueue::Entry, big: Option<[u64; 2]>) -> bool {
+        let done = !cqueue::more(cqe.flags());
+        if let Some(slot) = self.big.get_mut(&index) {
+            *slot = big;
+        }
+        if done {
+            // An operation cancelled past its deadline was cancelled by the
+            // timeout linked to it.
+            let expired = self
+                .deadlines
+                .remove(&index)
+                .is_some_and(|(_, at)| Instant::now() >= at);
+            if expired && cqe.result() == -libc::ECANCELED {
+                cqe = raw_cqe(cqe.user_data(), -libc::ETIMEDOUT, cqe.flags());
+            }
+        }
+        if !self.complete_op(index, cqe) {
+            return false;
+        }
+        if done && !self.chains.is_empty() {
+            self.complete_chain(index);
+        }
+        true
+    }
+
+    fn complete_op(&mut self, index: usize, cqe: cqueue::Entry) -> bool {
         let completions = &mut self.completions;
-        if self.lifecycle[index].complete(completions, cqe) {
+        let lifecycle = match self.lifecycle.get_mut(index) {
+            Some(lifecycle) => lifecycle,
+            None => return false,
+        };
+        let more = cqueue::more(cqe.flags());
+        if !more {
+            self.in_flight -= 1;
+        }
+        let submitted = self.submitted.get(index).and_then(Option::as_ref);
+        trace::complete(index, submitted, cqe.result(), more);
+        if lifecycle.complete(completions, cqe) {
             self.lifecycle.remove(index);
+            self.big.remove(&index);
+        }
+        true
+    }
+
+    // Complete the operations of the chain of the operation at index which
+    // the kernel posts no completion for. Those skipping it on success before
+    // the operation have succeeded. If the operation skips it on success, it
+    // failed instead, and the kernel cancels the rest of a soft-linked chain
+    // without posting completions either.
+    fn complete_chain(&mut self, index: usize) {
+        let hard = squeue::Flags::IO_HARDLINK.bits();
+
+        let i = match self
+            .chains
+            .iter()
+            .position(|chain| chain.iter().any(|op| op.index == index))
+        {
+            Some(i) => i,
+            None => return,
+        };
+
+        let mut synthetic = Vec::new();
+        let chain = &mut self.chains[i];
+        let pos = chain.iter().position(|op| op.index == index).unwrap();
+        chain[pos].done = true;
+
+        for op in chain[..pos].iter_mut() {
+            if !op.done && op.skips_success() {
+                op.done = true;
+                synthetic.push((op.index, op.skipped));
+            }
+        }
+        if chain[pos].skips_success() && chain[pos].flags & hard == 0 {
+            for op in chain[pos + 1..].iter_mut().filter(|op| !op.done) {
+                op.done = true;
+                synthetic.push((op.index, -libc::ECANCELED));
+            }
+        }
+
+        if chain.iter().all(|op| op.done) {
+            self.chains.swap_remove(i);
+        }
+
+        for (index, result) in synthetic {
+            self.complete_op(index, op::skipped_cqe(index as u64, result));
         }
     }
 }
@@ -675,12 +2450,11 @@ mod test {
         assert_eq!(1, num_operations());
 
         CONTEXT.with(|cx| {
-            cx.handle()
-                .unwrap()
-                .inner
-                .borrow_mut()
-                .ops
-                .complete(index, unsafe { mem::zeroed() })
+            cx.handle().unwrap().inner.borrow_mut().ops.complete(
+                index,
+                unsafe { mem::zeroed() },
+                None,
+            )
         });
 
         assert_eq!(1, Rc::strong_count(&data));
@@ -689,6 +2463,30 @@ mod test {
         release();
     }
 
+    #[test]
+    fn unexpected_completion_is_reported() {
+        use std::sync::{Arc, Mutex};
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut builder = crate::builder();
+        {
+            let seen = seen.clone();
+            builder.on_driver_error(move |e| seen.lock().unwrap().push(e.to_string()));
+        }
+
+        let mut driver = Driver::new(&builder).unwrap();
+        let nop = io_uring::opcode::Nop::new().build().user_data(1234);
+        assert!(unsafe { driver.uring.submission().push(&nop) });
+        driver.uring.submit_and_wait(1).unwrap();
+
+        driver.dispatch_completions();
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            ["unexpected completion (user_data: 1234, result: 0)"]
+        );
+    }
+
     fn init() -> (Op<Rc<()>>, Rc<()>) {
         let driver = Driver::new(&crate::builder()).unwrap();
         let data = Rc::new(());
@@ -716,7 +2514,11 @@ mod test {
         CONTEXT.with(|cx| {
             let driver = cx.handle().unwrap();
 
-            driver.inner.borrow_mut().ops.complete(op.index(), cqe);
+            driver
+                .inner
+                .borrow_mut()
+                .ops
+                .complete(op.index(), cqe, None);
         });
     }
 
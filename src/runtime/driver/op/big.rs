@@ -0,0 +1,165 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use io_uring::squeue::Flags;
+use io_uring::{cqueue, squeue};
+
+use super::Submit;
+use crate::runtime::{driver, CONTEXT};
+
+/// An unsubmitted oneshot operation with a big submission queue entry, whose
+/// completion is read with its extra 16 bytes.
+///
+/// This carries `IORING_OP_URING_CMD` passthrough commands, such as NVMe's.
+/// It can only be submitted on a ring set up with
+/// [`Builder::sqe128`](crate::Builder::sqe128). The extra bytes of the
+/// completion are zero unless the ring is also set up with
+/// [`Builder::cqe32`](crate::Builder::cqe32).
+///
+/// # Examples
+///
+/// ```no_run
+/// use io_uring::{cqueue, opcode, squeue};
+/// use tokio_uring::{BigOutputTransform, Submit, UnsubmittedBig};
+///
+/// struct Extra;
+///
+/// impl BigOutputTransform for Extra {
+///     type Output = (i32, [u64; 2]);
+///     type StoredData = ();
+///
+///     fn transform_big_output(self, _: (), cqe: cqueue::Entry32) -> Self::Output {
+///         (cqe.result(), *cqe.big_cqe())
+///     }
+/// }
+///
+/// tokio_uring::builder().sqe128().cqe32().start(async {
+///     let sqe = squeue::Entry128::from(opcode::Nop::new().build());
+///     let (res, extra) = UnsubmittedBig::new((), Extra, sqe).submit().await;
+///     assert_eq!(res, 0);
+///     println!("extra completion bytes: {:?}", extra);
+/// });
+/// ```
+pub struct UnsubmittedBig<D: 'static, T: BigOutputTransform<StoredData = D>> {
+    stable_data: D,
+    post_op: T,
+    pub sqe: squeue::Entry128,
+}
+
+impl<D, T: BigOutputTransform<StoredData = D>> UnsubmittedBig<D, T> {
+    /// Construct a new operation for later submission.
+    pub fn new(stable_data: D, post_op: T, sqe: squeue::Entry128) -> Self {
+        Self {
+            stable_data,
+            post_op,
+            sqe,
+        }
+    }
+
+    /// Set the SQE's flags.
+    pub fn set_flags(mut self, flags: Flags) -> Self {
+        self.sqe = self.sqe.flags(flags);
+        self
+    }
+
+    /// Submit the operation to the driver, returning an error instead of
+    /// panicking if the submission fails.
+    ///
+    /// Submitting on a ring with regular submission queue entries fails with
+    /// `InvalidInput`. See [`SubmitError`](crate::SubmitError) for the other
+    /// failures reported.
+    pub fn try_submit(self) -> Result<InFlightBig<D, T>, crate::SubmitError> {
+        let handle = CONTEXT
+            .with(|x| x.handle())
+            .expect("Could not submit op; not in runtime context");
+
+        let index = handle.try_submit_op_128(self.sqe)?;
+
+        let inner = InFlightBigInner {
+            index,
+            driver: (&handle).into(),
+            stable_data: self.stable_data,
+            post_op: self.post_op,
+        };
+
+        Ok(InFlightBig { inner: Some(inner) })
+    }
+}
+
+impl<D, T: BigOutputTransform<StoredData = D>> Submit for UnsubmittedBig<D, T> {
+    type Output = InFlightBig<D, T>;
+
+    /// Submit an operation to the driver for batched entry to the kernel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the submission fails, e.g. as the ring has regular
+    /// submission queue entries.
+    fn submit(self) -> Self::Output {
+        match self.try_submit() {
+            Ok(op) => op,
+            Err(e) => panic!("Failed to submit op: {}", e),
+        }
+    }
+}
+
+/// An in-progress operation with a big submission queue entry which can be
+/// polled for completion.
+pub struct InFlightBig<D: 'static, T: BigOutputTransform<StoredData = D>> {
+    inner: Option<InFlightBigInner<D, T>>,
+}
+
+struct InFlightBigInner<D, T: BigOutputTransform<StoredData = D>> {
+    driver: driver::WeakHandle,
+    index: usize,
+    stable_data: D,
+    post_op: T,
+}
+
+impl<D: Unpin, T: BigOutputTransform<StoredData = D> + Unpin> Future for InFlightBig<D, T> {
+    type Output = T::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let inner = this
+            .inner
+            .as_mut()
+            .expect("Cannot poll already-completed operation");
+
+        let index = inner.index;
+
+        let upgraded = inner
+            .driver
+            .upgrade()
+            .expect("Failed to poll op: driver no longer exists");
+
+        let cqe = ready!(upgraded.poll_op_32(index, cx));
+
+        let inner = this.inner.take().unwrap();
+
+        Poll::Ready(inner.post_op.transform_big_output(inner.stable_data, cqe))
+    }
+}
+
+impl<D: 'static, T: BigOutputTransform<StoredData = D>> Drop for InFlightBig<D, T> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            if let Some(driver) = inner.driver.upgrade() {
+                driver.remove_op_2(inner.index, inner.stable_data)
+            }
+        }
+    }
+}
+
+/// Transforms the big completion of an operation into a more user-friendly
+/// format.
+pub trait BigOutputTransform {
+    /// The final output after the transformation.
+    type Output;
+    /// The stored data within the op.
+    type StoredData;
+    /// Transform the stored data and the big cqe into the final output.
+    fn transform_big_output(self, data: Self::StoredData, cqe: cqueue::Entry32) -> Self::Output;
+}
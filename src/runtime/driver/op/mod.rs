@@ -7,9 +7,11 @@ use std::task::{Context, Poll, Waker};
 use io_uring::squeue::Flags;
 use io_uring::{cqueue, squeue};
 
+mod big;
 mod link;
 mod slab_list;
 
+pub use big::{BigOutputTransform, InFlightBig, UnsubmittedBig};
 pub use link::{Link, LinkedInFlightOneshot};
 use slab::Slab;
 use slab_list::{SlabListEntry, SlabListIndices};
@@ -28,6 +30,7 @@ pub struct UnsubmittedOneshot<D: 'static, T: OneshotOutputTransform<StoredData =
     stable_data: D,
     post_op: T,
     pub sqe: squeue::Entry,
+    timeout: driver::OpTimeout,
 }
 
 impl<D, T: OneshotOutputTransform<StoredData = D>> UnsubmittedOneshot<D, T> {
@@ -37,6 +40,7 @@ impl<D, T: OneshotOutputTransform<StoredData = D>> UnsubmittedOneshot<D, T> {
             stable_data,
             post_op,
             sqe,
+            timeout: driver::OpTimeout::Default,
         }
     }
 
@@ -62,6 +66,118 @@ impl<D, T: OneshotOutputTransform<StoredData = D>> UnsubmittedOneshot<D, T> {
         self
     }
 
+    /// Post no completion if the operation succeeds
+    /// (`IOSQE_CQE_SKIP_SUCCESS`), saving a completion queue entry.
+    ///
+    /// This is for operations whose result is irrelevant when they succeed,
+    /// such as a barrier linked before other operations. The completion of
+    /// a later operation in its chain tells it succeeded, and it then
+    /// resolves with the requested length for a read or write, which only
+    /// skips its completion once it transferred everything, and with a
+    /// result of 0 for other operations. An operation which fails posts its
+    /// completion, as does one not linked to another, which ends its chain.
+    /// Requires Linux 5.17 or later.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    /// use tokio_uring::Submit;
+    ///
+    /// tokio_uring::start(async {
+    ///     let file = File::create("hello.txt").await.unwrap();
+    ///
+    ///     let header = file.write_at(b"header".to_vec(), 0).skip_success();
+    ///     let body = file.write_at(b"body".to_vec(), 6);
+    ///
+    ///     let (header, body) = header.link(body).submit().await;
+    ///     header.unwrap();
+    ///     body.await.unwrap();
+    /// });
+    /// ```
+    pub fn skip_success(self) -> Self {
+        self.set_flags(Flags::SKIP_SUCCESS)
+    }
+
+    /// Fail the operation with `ETIMEDOUT` if it takes longer than `timeout`,
+    /// overriding the deadline set with
+    /// [`Builder::op_timeout`](crate::Builder::op_timeout).
+    ///
+    /// The operation is cancelled by a timeout linked to it
+    /// (`IORING_OP_LINK_TIMEOUT`). Operations linked to others can't have a
+    /// timeout linked to them as well, so they get none.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use tokio_uring::net::TcpStream;
+    /// use tokio_uring::Submit;
+    ///
+    /// tokio_uring::start(async {
+    ///     let stream = TcpStream::connect("127.0.0.1:8080".parse().unwrap())
+    ///         .await
+    ///         .unwrap();
+    ///     let res = stream
+    ///         .write(b"hello".to_vec())
+    ///         .timeout(Duration::from_secs(30))
+    ///         .submit()
+    ///         .await;
+    /// });
+    /// ```
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = driver::OpTimeout::After(timeout);
+        self
+    }
+
+    /// Let the operation take as long as it takes, overriding the deadline
+    /// set with [`Builder::op_timeout`](crate::Builder::op_timeout).
+    pub fn no_timeout(mut self) -> Self {
+        self.timeout = driver::OpTimeout::Disabled;
+        self
+    }
+
+    /// Perform the operation with the credentials of a registered
+    /// [`Personality`](crate::runtime::Personality).
+    pub fn personality(mut self, personality: crate::runtime::Personality) -> Self {
+        self.sqe = self.sqe.personality(personality.id());
+        self
+    }
+
+    /// Submit the operation to the driver, returning an error instead of
+    /// panicking if the submission fails.
+    ///
+    /// See [`SubmitError`](crate::SubmitError) for the failures reported.
+    pub fn try_submit(self) -> Result<InFlightOneshot<D, T>, crate::SubmitError> {
+        let handle = CONTEXT
+            .with(|x| x.handle())
+            .expect("Could not submit op; not in runtime context")
+            .routed(&self.sqe);
+
+        let index = handle.try_submit_op_2(self.sqe, self.timeout)?;
+
+        let inner = InFlightOneshotInner {
+            index,
+            driver: (&handle).into(),
+            stable_data: self.stable_data,
+            post_op: self.post_op,
+        };
+
+        Ok(InFlightOneshot { inner: Some(inner) })
+    }
+
+    /// Submit the operation to the driver once the submission queue has room,
+    /// and the runtime is under its cap on operations in flight, letting
+    /// other tasks run meanwhile.
+    ///
+    /// See [`sq_space_left`](crate::runtime::sq_space_left) and
+    /// [`op_permit`](crate::runtime::op_permit).
+    pub async fn submit_when_ready(self) -> InFlightOneshot<D, T> {
+        let permit = crate::runtime::op_permit().await;
+        crate::runtime::sq_space_left().await;
+        permit.submit(self)
+    }
+
     // Create inflight from submitted index.
     pub fn inflight(self, index: usize) -> InFlightOneshot<D, T> {
         let handle = CONTEXT
@@ -86,9 +202,10 @@ impl<D, T: OneshotOutputTransform<StoredData = D>> Submit for UnsubmittedOneshot
     fn submit(self) -> Self::Output {
         let handle = CONTEXT
             .with(|x| x.handle())
-            .expect("Could not submit op; not in runtime context");
+            .expect("Could not submit op; not in runtime context")
+            .routed(&self.sqe);
 
-        let index = handle.submit_op_2(self.sqe);
+        let index = handle.submit_op_2(self.sqe, self.timeout);
 
         let inner = InFlightOneshotInner {
             index,
@@ -159,6 +276,12 @@ pub trait Submit {
     type Output;
 
     /// Submit an operation or linked operations.
+    ///
+    /// This doesn't wait for room in the submission queue: when it is full,
+    /// the queue is flushed to the kernel, blocking the runtime thread until
+    /// the kernel takes the entries. Use
+    /// [`UnsubmittedOneshot::submit_when_ready`] to let other tasks run
+    /// meanwhile instead.
     fn submit(self) -> Self::Output;
 }
 
@@ -230,6 +353,38 @@ pub(crate) struct CqeResult {
     pub(crate) flags: u32,
 }
 
+/// The completion of an operation the kernel posted none for, as it
+/// succeeded with `IOSQE_CQE_SKIP_SUCCESS`, or was cancelled along with it.
+pub(crate) fn skipped_cqe(user_data: u64, res: i32) -> cqueue::Entry {
+    #[repr(C)]
+    struct RawCqe {
+        user_data: u64,
+        res: i32,
+        flags: u32,
+    }
+
+    let cqe = RawCqe {
+        user_data,
+        res,
+        flags: 0,
+    };
+    // Safety: a CQE is an io_uring_cqe, which has the same layout.
+    unsafe { std::mem::transmute::<RawCqe, cqueue::Entry>(cqe) }
+}
+
+/// A big completion from a regular one and its extra 16 bytes.
+pub(crate) fn big_cqe(cqe: cqueue::Entry, big: [u64; 2]) -> cqueue::Entry32 {
+    #[repr(C)]
+    struct RawCqe32 {
+        cqe: cqueue::Entry,
+        big: [u64; 2],
+    }
+
+    // Safety: a big CQE is a CQE followed by 16 bytes, which has the same
+    // layout.
+    unsafe { std::mem::transmute::<RawCqe32, cqueue::Entry32>(RawCqe32 { cqe, big }) }
+}
+
 impl From<cqueue::Entry> for CqeResult {
     fn from(cqe: cqueue::Entry) -> Self {
         let res = cqe.result();
@@ -254,7 +409,7 @@ impl<T, CqeType> Op<T, CqeType> {
         }
     }
 
-    pub(super) fn index(&self) -> usize {
+    pub(crate) fn index(&self) -> usize {
         self.index
     }
 
@@ -265,6 +420,16 @@ impl<T, CqeType> Op<T, CqeType> {
     pub(super) fn insert_data(&mut self, data: T) {
         self.data = Some(data);
     }
+
+    /// Request cancellation of the operation. The Op resolves once the
+    /// kernel posts its final completion.
+    pub(crate) fn cancel(&self) -> io::Result<()> {
+        match self.driver.upgrade() {
+            Some(driver) => driver.cancel_op(self),
+            // The driver is gone, and cancelled everything on its way out.
+            None => Ok(()),
+        }
+    }
 }
 
 impl<T> Future for Op<T, SingleCQE>
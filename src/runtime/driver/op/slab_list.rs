@@ -53,6 +53,11 @@ impl SlabListIndices {
     pub(crate) fn into_list<T>(self, slab: &mut Slab<SlabListEntry<T>>) -> SlabList<'_, T> {
         SlabList::from_indices(self, slab)
     }
+
+    /// Peek at the end of the list (most recently pushed) without taking it
+    pub(crate) fn peek_end<'a, T>(&self, slab: &'a Slab<SlabListEntry<T>>) -> Option<&'a T> {
+        slab.get(self.end).map(|entry| &entry.entry)
+    }
 }
 
 impl<'a, T> SlabList<'a, T> {
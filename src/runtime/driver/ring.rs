@@ -0,0 +1,313 @@
+//! The ring of the driver, whatever the size of its queue entries.
+//!
+//! Rings set up with `IORING_SETUP_SQE128` or `IORING_SETUP_CQE32` have
+//! entries of another size, and so another type. The driver deals in regular
+//! entries, which are widened or narrowed as they go through a ring with big
+//! entries, and in big entries for the operations which need them.
+
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+use io_uring::cqueue::{self, CompletionQueue};
+use io_uring::squeue::{self, SubmissionQueue};
+use io_uring::{IoUring, Parameters, Submitter};
+
+pub(crate) enum Ring {
+    Default(IoUring),
+    Sqe128(IoUring<squeue::Entry128, cqueue::Entry>),
+    Cqe32(IoUring<squeue::Entry, cqueue::Entry32>),
+    Big(IoUring<squeue::Entry128, cqueue::Entry32>),
+}
+
+macro_rules! each_ring {
+    ($ring:expr, $r:ident => $e:expr) => {
+        match $ring {
+            Ring::Default($r) => $e,
+            Ring::Sqe128($r) => $e,
+            Ring::Cqe32($r) => $e,
+            Ring::Big($r) => $e,
+        }
+    };
+}
+
+impl Ring {
+    /// Builds a ring with big entries as requested, applying `configure` to
+    /// the ring builder of the matching type.
+    pub(crate) fn build(
+        b: &crate::Builder,
+        configure: impl Fn(&mut dyn Configure),
+    ) -> io::Result<Ring> {
+        if b.custom_urb && (b.sqe128 || b.cqe32) {
+            // The custom builder has regular entries, so it can't set up
+            // this ring.
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "big ring entries can't be combined with a custom uring builder",
+            ));
+        }
+
+        Ok(match (b.sqe128, b.cqe32) {
+            (false, false) => {
+                let mut urb = b.urb.clone();
+                configure(&mut urb);
+                Ring::Default(urb.build(b.entries)?)
+            }
+            (true, false) => {
+                let mut urb = IoUring::<squeue::Entry128, cqueue::Entry>::builder();
+                configure(&mut urb);
+                Ring::Sqe128(urb.build(b.entries)?)
+            }
+            (false, true) => {
+                let mut urb = IoUring::<squeue::Entry, cqueue::Entry32>::builder();
+                configure(&mut urb);
+                Ring::Cqe32(urb.build(b.entries)?)
+            }
+            (true, true) => {
+                let mut urb = IoUring::<squeue::Entry128, cqueue::Entry32>::builder();
+                configure(&mut urb);
+                Ring::Big(urb.build(b.entries)?)
+            }
+        })
+    }
+
+    /// Returns the ring with regular entries, if it has them.
+    pub(crate) fn as_default(&self) -> Option<&IoUring> {
+        match self {
+            Ring::Default(ring) => Some(ring),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_sqe128(&self) -> bool {
+        matches!(self, Ring::Sqe128(_) | Ring::Big(_))
+    }
+
+    pub(crate) fn params(&self) -> &Parameters {
+        each_ring!(self, r => r.params())
+    }
+
+    pub(crate) fn submitter(&self) -> Submitter<'_> {
+        each_ring!(self, r => r.submitter())
+    }
+
+    #[cfg(test)]
+    pub(crate) fn submit_and_wait(&self, want: usize) -> io::Result<usize> {
+        each_ring!(self, r => r.submit_and_wait(want))
+    }
+
+    pub(crate) fn submission(&mut self) -> Sq<'_> {
+        match self {
+            Ring::Default(r) => Sq::Regular(r.submission()),
+            Ring::Cqe32(r) => Sq::Regular(r.submission()),
+            Ring::Sqe128(r) => Sq::Big(r.submission()),
+            Ring::Big(r) => Sq::Big(r.submission()),
+        }
+    }
+
+    pub(crate) fn completion(&mut self) -> Cq<'_> {
+        match self {
+            Ring::Default(r) => Cq::Regular(r.completion()),
+            Ring::Sqe128(r) => Cq::Regular(r.completion()),
+            Ring::Cqe32(r) => Cq::Big(r.completion()),
+            Ring::Big(r) => Cq::Big(r.completion()),
+        }
+    }
+}
+
+impl AsRawFd for Ring {
+    fn as_raw_fd(&self) -> RawFd {
+        each_ring!(self, r => r.as_raw_fd())
+    }
+}
+
+/// The setup options the driver applies to a ring builder, whatever the
+/// type of its entries.
+pub(crate) trait Configure {
+    fn setup_iopoll(&mut self);
+    fn setup_single_issuer(&mut self);
+    fn setup_defer_taskrun(&mut self);
+    fn setup_coop_taskrun(&mut self);
+    fn setup_taskrun_flag(&mut self);
+    fn setup_sqpoll(&mut self, idle: u32);
+    fn setup_sqpoll_cpu(&mut self, cpu: u32);
+    fn setup_attach_wq(&mut self, fd: RawFd);
+    fn setup_r_disabled(&mut self);
+    fn setup_cqsize(&mut self, entries: u32);
+}
+
+impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> Configure for io_uring::Builder<S, C> {
+    fn setup_iopoll(&mut self) {
+        io_uring::Builder::setup_iopoll(self);
+    }
+
+    fn setup_single_issuer(&mut self) {
+        io_uring::Builder::setup_single_issuer(self);
+    }
+
+    fn setup_defer_taskrun(&mut self) {
+        io_uring::Builder::setup_defer_taskrun(self);
+    }
+
+    fn setup_coop_taskrun(&mut self) {
+        io_uring::Builder::setup_coop_taskrun(self);
+    }
+
+    fn setup_taskrun_flag(&mut self) {
+        io_uring::Builder::setup_taskrun_flag(self);
+    }
+
+    fn setup_sqpoll(&mut self, idle: u32) {
+        io_uring::Builder::setup_sqpoll(self, idle);
+    }
+
+    fn setup_sqpoll_cpu(&mut self, cpu: u32) {
+        io_uring::Builder::setup_sqpoll_cpu(self, cpu);
+    }
+
+    fn setup_attach_wq(&mut self, fd: RawFd) {
+        io_uring::Builder::setup_attach_wq(self, fd);
+    }
+
+    fn setup_r_disabled(&mut self) {
+        io_uring::Builder::setup_r_disabled(self);
+    }
+
+    fn setup_cqsize(&mut self, entries: u32) {
+        io_uring::Builder::setup_cqsize(self, entries);
+    }
+}
+
+/// The submission queue of a ring.
+pub(crate) enum Sq<'a> {
+    Regular(SubmissionQueue<'a, squeue::Entry>),
+    Big(SubmissionQueue<'a, squeue::Entry128>),
+}
+
+macro_rules! each_sq {
+    ($sq:expr, $q:ident => $e:expr) => {
+        match $sq {
+            Sq::Regular($q) => $e,
+            Sq::Big($q) => $e,
+        }
+    };
+}
+
+impl Sq<'_> {
+    pub(crate) fn sync(&mut self) {
+        each_sq!(self, q => q.sync())
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        each_sq!(self, q => q.len())
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        each_sq!(self, q => q.is_empty())
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        each_sq!(self, q => q.is_full())
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        each_sq!(self, q => q.capacity())
+    }
+
+    pub(crate) fn need_wakeup(&self) -> bool {
+        each_sq!(self, q => q.need_wakeup())
+    }
+
+    pub(crate) fn cq_overflow(&self) -> bool {
+        each_sq!(self, q => q.cq_overflow())
+    }
+
+    pub(crate) fn taskrun(&self) -> bool {
+        each_sq!(self, q => q.taskrun())
+    }
+
+    /// Pushes an entry, widened on a ring with big entries. Returns `false`
+    /// if the queue is full.
+    ///
+    /// # Safety
+    ///
+    /// As [`SubmissionQueue::push`].
+    pub(crate) unsafe fn push(&mut self, sqe: &squeue::Entry) -> bool {
+        match self {
+            Sq::Regular(q) => q.push(sqe).is_ok(),
+            Sq::Big(q) => q.push(&squeue::Entry128::from(sqe.clone())).is_ok(),
+        }
+    }
+
+    /// Pushes entries, all or none of them. Returns `false` if the queue
+    /// doesn't have room for all of them.
+    ///
+    /// # Safety
+    ///
+    /// As [`SubmissionQueue::push_multiple`].
+    pub(crate) unsafe fn push_multiple(&mut self, sqes: &[squeue::Entry]) -> bool {
+        match self {
+            Sq::Regular(q) => q.push_multiple(sqes).is_ok(),
+            Sq::Big(q) => {
+                let sqes: Vec<squeue::Entry128> = sqes.iter().cloned().map(Into::into).collect();
+                q.push_multiple(&sqes).is_ok()
+            }
+        }
+    }
+
+    /// Pushes a big entry. Returns `false` if the queue is full.
+    ///
+    /// # Safety
+    ///
+    /// As [`SubmissionQueue::push`]. The ring must have big entries.
+    pub(crate) unsafe fn push128(&mut self, sqe: &squeue::Entry128) -> bool {
+        match self {
+            Sq::Regular(_) => unreachable!("ring has regular submission queue entries"),
+            Sq::Big(q) => q.push(sqe).is_ok(),
+        }
+    }
+}
+
+/// The completion queue of a ring.
+pub(crate) enum Cq<'a> {
+    Regular(CompletionQueue<'a, cqueue::Entry>),
+    Big(CompletionQueue<'a, cqueue::Entry32>),
+}
+
+impl Cq<'_> {
+    pub(crate) fn sync(&mut self) {
+        match self {
+            Cq::Regular(q) => q.sync(),
+            Cq::Big(q) => q.sync(),
+        }
+    }
+
+    pub(crate) fn overflow(&self) -> u32 {
+        match self {
+            Cq::Regular(q) => q.overflow(),
+            Cq::Big(q) => q.overflow(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        match self {
+            Cq::Regular(q) => q.is_empty(),
+            Cq::Big(q) => q.is_empty(),
+        }
+    }
+}
+
+/// Yields completions narrowed to regular entries, with the extra 16 bytes
+/// of big entries.
+impl Iterator for Cq<'_> {
+    type Item = (cqueue::Entry, Option<[u64; 2]>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Cq::Regular(q) => q.next().map(|cqe| (cqe, None)),
+            Cq::Big(q) => q.next().map(|cqe| {
+                let big = *cqe.big_cqe();
+                (cqe.into(), Some(big))
+            }),
+        }
+    }
+}
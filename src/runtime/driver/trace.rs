@@ -0,0 +1,107 @@
+//! Instrumentation of the driver with `tracing`, behind the `tracing`
+//! feature. Without it, the hooks do nothing and compile away.
+//!
+//! Operations are traced as they are submitted, cancelled and complete,
+//! with their opcode, file descriptor, result and latency. The driver traces
+//! each time it enters the kernel and dispatches completions.
+
+#[cfg(feature = "tracing")]
+pub(super) use imp::*;
+
+#[cfg(not(feature = "tracing"))]
+pub(super) use noop::*;
+
+#[cfg(feature = "tracing")]
+mod imp {
+    use std::io;
+
+    use super::super::Submitted;
+
+    pub(in crate::runtime::driver) fn submit(index: usize, op: &Submitted) {
+        tracing::trace!(index, opcode = op.opcode, fd = op.fd, "submit op");
+    }
+
+    pub(in crate::runtime::driver) fn cancel(index: usize, op: Option<&Submitted>) {
+        match op {
+            Some(op) => tracing::debug!(index, opcode = op.opcode, fd = op.fd, "cancel op"),
+            None => tracing::debug!(index, "cancel op"),
+        }
+    }
+
+    pub(in crate::runtime::driver) fn complete(
+        index: usize,
+        op: Option<&Submitted>,
+        result: i32,
+        more: bool,
+    ) {
+        let op = match op {
+            Some(op) => op,
+            None => return tracing::trace!(index, result, more, "complete op"),
+        };
+
+        let latency_us = op.at.elapsed().as_micros() as u64;
+        if result < 0 {
+            tracing::debug!(
+                index,
+                opcode = op.opcode,
+                fd = op.fd,
+                result,
+                more,
+                latency_us,
+                "complete op"
+            );
+        } else {
+            tracing::trace!(
+                index,
+                opcode = op.opcode,
+                fd = op.fd,
+                result,
+                more,
+                latency_us,
+                "complete op"
+            );
+        }
+    }
+
+    pub(in crate::runtime::driver) fn enter(to_submit: u32, want: u32, res: &io::Result<usize>) {
+        match res {
+            Ok(submitted) => tracing::trace!(to_submit, want, submitted, "enter kernel"),
+            Err(e) => tracing::debug!(to_submit, want, error = %e, "enter kernel"),
+        }
+    }
+
+    pub(in crate::runtime::driver) fn dispatch(reaped: usize, in_flight: usize) {
+        if reaped > 0 {
+            tracing::trace!(reaped, in_flight, "dispatch completions");
+        }
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+mod noop {
+    use std::io;
+
+    use super::super::Submitted;
+
+    #[inline]
+    pub(in crate::runtime::driver) fn submit(_index: usize, _op: &Submitted) {}
+
+    #[inline]
+    pub(in crate::runtime::driver) fn cancel(_index: usize, _op: Option<&Submitted>) {}
+
+    #[inline]
+    pub(in crate::runtime::driver) fn complete(
+        _index: usize,
+        _op: Option<&Submitted>,
+        _result: i32,
+        _more: bool,
+    ) {
+    }
+
+    #[inline]
+    pub(in crate::runtime::driver) fn enter(_to_submit: u32, _want: u32, _res: &io::Result<usize>) {
+    }
+
+    #[inline]
+    pub(in crate::runtime::driver) fn dispatch(_reaped: usize, _in_flight: usize) {}
+}
@@ -0,0 +1,64 @@
+use std::fmt;
+use std::io;
+
+use super::RingStats;
+
+/// An internal error of the `io_uring` driver.
+///
+/// These conditions are not tied to a particular operation, so they cannot
+/// be reported through an operation's result. Install a callback with
+/// [`Builder::on_driver_error`] to observe them.
+///
+/// [`Builder::on_driver_error`]: crate::Builder::on_driver_error
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DriverError {
+    /// Submitting queued entries to the kernel failed.
+    Submit(io::Error),
+
+    /// A completion was posted for an operation the driver does not track.
+    UnexpectedCompletion {
+        /// The `user_data` of the completion.
+        user_data: u64,
+        /// The raw result of the completion.
+        result: i32,
+    },
+
+    /// The kernel failed to cancel an operation.
+    Cancel(io::Error),
+
+    /// The ring is too small for the load it sees: the submission queue
+    /// filled up, or the completion queue overflowed.
+    ///
+    /// This is reported each time the recommended number of entries grows.
+    Undersized(RingStats),
+}
+
+impl fmt::Display for DriverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DriverError::Submit(e) => write!(f, "failed to submit operations: {}", e),
+            DriverError::UnexpectedCompletion { user_data, result } => write!(
+                f,
+                "unexpected completion (user_data: {}, result: {})",
+                user_data, result
+            ),
+            DriverError::Cancel(e) => write!(f, "failed to cancel operation: {}", e),
+            DriverError::Undersized(stats) => write!(
+                f,
+                "ring of {} entries is undersized, {} entries recommended",
+                stats.sq_entries,
+                stats.recommended_entries().unwrap_or(stats.sq_entries)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DriverError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DriverError::Submit(e) | DriverError::Cancel(e) => Some(e),
+            DriverError::UnexpectedCompletion { .. } | DriverError::Undersized(_) => None,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::time::Instant;
+
+/// An operation in flight on the ring of a runtime, as returned by
+/// [`dump_inflight`](crate::runtime::dump_inflight).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct InflightOp {
+    /// The `user_data` the operation was submitted with, which identifies it
+    /// in its completions and to `IORING_OP_ASYNC_CANCEL`.
+    pub user_data: u64,
+
+    /// The opcode of the operation, e.g. [`io_uring::opcode::Read::CODE`].
+    pub opcode: u8,
+
+    /// The file descriptor the operation was submitted on, -1 for those
+    /// without one. This is the slot of a direct descriptor if
+    /// [`fixed_file`](InflightOp::fixed_file) is set.
+    pub fd: i32,
+
+    /// Whether the operation refers to a direct descriptor
+    /// (`IOSQE_FIXED_FILE`).
+    pub fixed_file: bool,
+
+    /// When the operation was submitted to the driver.
+    pub submitted: Instant,
+
+    /// Whether the future of the operation was dropped. The operation is then
+    /// left to complete or be cancelled, and nothing awaits its result.
+    pub abandoned: bool,
+}
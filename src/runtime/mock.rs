@@ -0,0 +1,160 @@
+use crate::runtime::{driver, CONTEXT};
+
+const IORING_CQE_F_MORE: u32 = 1 << 1;
+
+/// Completes the operations of a runtime built with
+/// [`Builder::mock`](crate::Builder::mock), in place of the kernel.
+///
+/// Operations are recorded as they are submitted, and complete once a test
+/// completes them by their user data. This simulates short reads, errors
+/// such as `EAGAIN`, and completion queue overflows deterministically.
+///
+/// Operations still in flight when the runtime shuts down complete with
+/// `ECANCELED`.
+///
+/// Requires the `mock` feature.
+///
+/// # Examples
+///
+/// A read which only fills part of its buffer:
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use tokio_uring::fs::File;
+/// use tokio_uring::runtime::MockDriver;
+/// use tokio_uring::Submit;
+///
+/// tokio_uring::builder().mock(true).start(async {
+///     let mock = MockDriver::current();
+///     let file = File::from_std(std::fs::File::open("/dev/null").unwrap());
+///
+///     let read = tokio_uring::spawn(async move { file.read_at(vec![0; 16], 0).submit().await });
+///     tokio::time::sleep(Duration::from_millis(1)).await;
+///
+///     let op = mock.submitted().pop().unwrap();
+///     mock.complete_read(op.user_data, b"hello");
+///
+///     let (n, buf) = read.await.unwrap().unwrap();
+///     assert_eq!(&buf[..n], b"hello");
+/// });
+/// ```
+#[derive(Clone)]
+pub struct MockDriver {
+    handle: driver::Handle,
+}
+
+impl MockDriver {
+    /// Returns the mock driver of the current runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a runtime context, or if the runtime was
+    /// not built with [`Builder::mock`](crate::Builder::mock).
+    pub fn current() -> MockDriver {
+        let handle = CONTEXT
+            .with(|x| x.handle())
+            .expect("Not in a runtime context");
+        assert!(handle.is_mock(), "the runtime has no mock driver");
+        MockDriver { handle }
+    }
+
+    /// Returns the operations submitted since this was last called, in the
+    /// order they were submitted.
+    pub fn submitted(&self) -> Vec<MockOp> {
+        self.handle.mock_submitted()
+    }
+
+    /// Returns the operations in flight, by user data.
+    pub fn pending(&self) -> Vec<MockOp> {
+        self.handle.mock_pending()
+    }
+
+    /// Returns the user data of the operations the runtime asked to cancel
+    /// since this was last called, e.g. as their futures were dropped.
+    ///
+    /// Cancelling an operation does not complete it: complete it with
+    /// `-ECANCELED`, or with a result it could have completed with first.
+    pub fn cancelled(&self) -> Vec<u64> {
+        self.handle.mock_cancelled()
+    }
+
+    /// Completes the operation with `user_data` with `result`, a value or a
+    /// negated `errno` as the kernel posts it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no operation is in flight with `user_data`.
+    pub fn complete(&self, user_data: u64, result: i32) {
+        self.handle.mock_complete(user_data, result, 0)
+    }
+
+    /// Posts a completion with `result` for the multishot operation with
+    /// `user_data`, which stays in flight (`IORING_CQE_F_MORE`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if no operation is in flight with `user_data`.
+    pub fn complete_more(&self, user_data: u64, result: i32) {
+        self.handle
+            .mock_complete(user_data, result, IORING_CQE_F_MORE)
+    }
+
+    /// Completes the read with `user_data`, copying `data` to its buffer.
+    ///
+    /// At most as many bytes as the buffer holds are copied, and the read
+    /// completes with the number copied. A `data` shorter than the buffer
+    /// simulates a short read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no read is in flight with `user_data`.
+    pub fn complete_read(&self, user_data: u64, data: &[u8]) {
+        self.handle.mock_complete_read(user_data, data)
+    }
+
+    /// Reports the completion queue overflowed.
+    ///
+    /// The runtime handles it as configured with
+    /// [`Builder::cq_overflow`](crate::Builder::cq_overflow), reporting the
+    /// ring as undersized through
+    /// [`Builder::on_driver_error`](crate::Builder::on_driver_error).
+    pub fn overflow(&self) {
+        self.handle.mock_overflow()
+    }
+}
+
+impl std::fmt::Debug for MockDriver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockDriver").finish()
+    }
+}
+
+/// An operation submitted to a [`MockDriver`], as its submission queue
+/// entry.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct MockOp {
+    /// The user data, which identifies the operation to complete.
+    pub user_data: u64,
+
+    /// The opcode, e.g. `io_uring::opcode::Read::CODE`.
+    pub opcode: u8,
+
+    /// The `IOSQE_*` flags.
+    pub flags: u8,
+
+    /// The file descriptor, or direct descriptor.
+    pub fd: i32,
+
+    /// The offset.
+    pub offset: u64,
+
+    /// The address, e.g. of the buffer.
+    pub addr: u64,
+
+    /// The length, e.g. of the buffer.
+    pub len: u32,
+
+    /// The flags of the operation, e.g. the `RWF_*` flags of a read.
+    pub op_flags: u32,
+}
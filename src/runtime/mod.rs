@@ -1,13 +1,54 @@
 use std::future::Future;
 use std::io;
 use std::mem::ManuallyDrop;
-use tokio::io::unix::AsyncFd;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+use std::time::Instant;
+use tokio::io::unix::{AsyncFd, AsyncFdReadyGuard};
+use tokio::sync::Notify;
 use tokio::task::LocalSet;
 
+mod attach;
+mod busy_poll;
 mod context;
 pub(crate) mod driver;
+mod error;
+mod inflight;
+#[cfg(feature = "mock")]
+mod mock;
+mod msg_ring;
+mod multi_thread;
+mod overflow;
+mod permit;
+mod personality;
+mod probe;
+mod remote;
+mod restrictions;
+mod rings;
+mod shutdown;
+mod stats;
+mod task;
 
+pub use attach::Driver;
+pub use busy_poll::BusyPoll;
 pub(crate) use context::RuntimeContext;
+pub use error::DriverError;
+pub use inflight::InflightOp;
+#[cfg(feature = "mock")]
+pub use mock::{MockDriver, MockOp};
+pub use msg_ring::{recv_msg, Message};
+pub use multi_thread::MultiThreadRuntime;
+pub use overflow::CqOverflow;
+pub use permit::{op_permit, OpPermit};
+pub use personality::Personality;
+pub use probe::{probe, Probe};
+pub use remote::{JoinError, RemoteJoinHandle, RuntimeHandle};
+pub use restrictions::Restrictions;
+pub use rings::{add_ring, on_ring, route, OnRing, RingId};
+pub use shutdown::ShutdownReport;
+pub use stats::RingStats;
+pub use task::{current_task_name, TaskBuilder, TaskHandle};
 
 thread_local! {
     pub static CONTEXT: RuntimeContext = const { RuntimeContext::new() };
@@ -30,6 +71,12 @@ pub struct Runtime {
 
     /// Strong reference to the driver.
     pub driver: driver::Handle,
+
+    /// Handle other threads use to send work to this runtime.
+    remote: RuntimeHandle,
+
+    /// Whether the tasks and the Tokio runtime have already been dropped.
+    tasks_dropped: bool,
 }
 
 /// Spawns a new asynchronous task, returning a [`JoinHandle`] for it.
@@ -40,6 +87,8 @@ pub struct Runtime {
 /// lifecycle of that task.
 ///
 /// This function must be called from the context of a `tokio-uring` runtime.
+/// To name the task, and account for the time spent polling it, spawn it with
+/// a [`TaskBuilder`] instead.
 ///
 /// [`JoinHandle`]: tokio::task::JoinHandle
 ///
@@ -62,36 +111,361 @@ pub fn spawn<T: Future + 'static>(task: T) -> tokio::task::JoinHandle<T::Output>
     tokio::task::spawn_local(task)
 }
 
+/// Calls `f` with the `io_uring` instance of the current runtime.
+///
+/// This is an escape hatch for functionality the crate does not wrap yet,
+/// e.g. inspecting the ring's parameters, probing for supported operations,
+/// or registering resources through its [`Submitter`].
+///
+/// The runtime relies on a few invariants which `f` must uphold:
+///
+/// - Entries must not be pushed to the submission queue. The runtime owns the
+///   `user_data` space, and would take foreign completions for its own.
+/// - Resources registered by the runtime, such as fixed buffers, must not be
+///   unregistered or replaced behind its back.
+/// - The ring must not be disabled, resized or otherwise reconfigured.
+///
+/// `f` runs while the driver is borrowed, so it must not use the runtime
+/// itself, e.g. by submitting operations. Doing so panics.
+///
+/// [`Submitter`]: io_uring::Submitter
+///
+/// # Panics
+///
+/// Panics if called outside of a runtime context, or if the ring has big
+/// entries (see [`Builder::sqe128`] and [`Builder::cqe32`]).
+///
+/// [`Builder::sqe128`]: crate::Builder::sqe128
+/// [`Builder::cqe32`]: crate::Builder::cqe32
+///
+/// # Examples
+///
+/// ```no_run
+/// tokio_uring::start(async {
+///     let sq_entries = tokio_uring::with_ring(|ring| ring.params().sq_entries());
+///     println!("submission queue holds {} entries", sq_entries);
+/// });
+/// ```
+pub fn with_ring<F, R>(f: F) -> R
+where
+    F: FnOnce(&io_uring::IoUring) -> R,
+{
+    CONTEXT
+        .with(|x| x.handle())
+        .expect("Not in a runtime context")
+        .with_ring(f)
+}
+
+/// Returns `false` if the ring of the current runtime can't run the
+/// operation `opcode`, in which case operations fall back to older ones.
+pub(crate) fn supports(opcode: u8) -> bool {
+    CONTEXT
+        .with(|x| x.handle())
+        .expect("Not in a runtime context")
+        .supports(opcode)
+}
+
+/// Returns the sizing statistics of the ring of the current runtime.
+///
+/// # Panics
+///
+/// Panics if called outside of a runtime context.
+///
+/// # Examples
+///
+/// ```no_run
+/// tokio_uring::start(async {
+///     // ...
+///     if let Some(entries) = tokio_uring::runtime::ring_stats().recommended_entries() {
+///         eprintln!("ring too small, consider {} entries", entries);
+///     }
+/// });
+/// ```
+pub fn ring_stats() -> RingStats {
+    CONTEXT
+        .with(|x| x.handle())
+        .expect("Not in a runtime context")
+        .ring_stats()
+}
+
+/// Returns the operations in flight on the ring of the current runtime,
+/// oldest first.
+///
+/// Operations a runtime stops making progress on show up here, with the
+/// time they have been waiting for. Call this from a watchdog task to log
+/// them. The runtime's own cancellations are left out.
+///
+/// # Panics
+///
+/// Panics if called outside of a runtime context.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// tokio_uring::start(async {
+///     tokio_uring::spawn(async {
+///         loop {
+///             tokio::time::sleep(Duration::from_secs(10)).await;
+///             for op in tokio_uring::runtime::dump_inflight() {
+///                 if op.submitted.elapsed() > Duration::from_secs(60) {
+///                     eprintln!("stuck: {:?}", op);
+///                 }
+///             }
+///         }
+///     });
+///     // ...
+/// });
+/// ```
+pub fn dump_inflight() -> Vec<InflightOp> {
+    CONTEXT
+        .with(|x| x.handle())
+        .expect("Not in a runtime context")
+        .dump_inflight()
+}
+
+/// Waits for room in the submission queue of the current runtime, returning
+/// the number of free entries.
+///
+/// Submitting to a full queue enters the kernel to make room, blocking the
+/// runtime thread when the kernel can't take the entries yet. Awaiting this
+/// first lets other tasks run meanwhile, which paces tasks submitting at a
+/// high rate. The entries free when it resolves are not reserved: another
+/// task may take them before this one submits.
+///
+/// [`Submit::submit`](crate::Submit::submit) doesn't await room by itself, as
+/// it submits synchronously. Await this before submitting, or use
+/// [`UnsubmittedOneshot::submit_when_ready`](crate::UnsubmittedOneshot::submit_when_ready)
+/// which does both.
+///
+/// # Panics
+///
+/// Panics if called outside of a runtime context.
+///
+/// # Examples
+///
+/// ```no_run
+/// tokio_uring::start(async {
+///     for _ in 0..1024 {
+///         tokio_uring::runtime::sq_space_left().await;
+///         tokio_uring::spawn(tokio_uring::no_op());
+///     }
+/// });
+/// ```
+pub async fn sq_space_left() -> usize {
+    let handle = CONTEXT
+        .with(|x| x.handle())
+        .expect("Not in a runtime context");
+
+    std::future::poll_fn(|cx| handle.poll_sq_space(cx)).await
+}
+
+/// Submits the operations pending in the submission queue of the current
+/// runtime to the kernel, returning the number submitted.
+///
+/// Operations are otherwise submitted once the runtime has no more tasks to
+/// run, when the submission queue fills up, or once
+/// [`Builder::submit_batch`](crate::Builder::submit_batch) of them are
+/// pending. Flushing starts them right away, such as at the end of a burst of
+/// writes.
+///
+/// # Panics
+///
+/// Panics if called outside of a runtime context.
+///
+/// # Examples
+///
+/// ```no_run
+/// tokio_uring::start(async {
+///     let task = tokio_uring::spawn(tokio_uring::no_op());
+///
+///     // Let the task queue its operation, then start it without waiting
+///     // for the runtime to run out of tasks.
+///     tokio::task::yield_now().await;
+///     tokio_uring::runtime::flush()?;
+///
+///     task.await.unwrap()
+/// })
+/// .unwrap();
+/// ```
+pub fn flush() -> io::Result<usize> {
+    CONTEXT
+        .with(|x| x.handle())
+        .expect("Not in a runtime context")
+        .flush()
+}
+
+/// Re-creates the ring of the current runtime with `sq_entries` submission
+/// queue and `cq_entries` completion queue entries.
+///
+/// The size of a ring is otherwise fixed when the runtime is created. A
+/// long-running service which finds its ring too small, see [`ring_stats`],
+/// can grow it this way without restarting. The new ring is set up as the
+/// [`Builder`](crate::Builder) of the runtime configured the old one, and
+/// takes over the fixed buffers and the eventfd registered with it. The file
+/// descriptor of the runtime stays the same, and refers to the new ring.
+///
+/// The kernel can resize a ring in place (`IORING_REGISTER_RESIZE_RINGS`),
+/// but not without mapping it anew, which the `io-uring` crate does not
+/// support.
+///
+/// # Errors
+///
+/// Fails with `EBUSY` while operations are in flight, as they belong to the
+/// old ring, and while files, direct descriptors or personalities are
+/// registered, as they can't be carried over. Call it at a quiet point, e.g.
+/// before accepting connections again. The kernel requires `cq_entries` to
+/// be at least `sq_entries`, and rounds both up to a power of two.
+///
+/// # Panics
+///
+/// Panics if called outside of a runtime context.
+///
+/// # Examples
+///
+/// ```no_run
+/// tokio_uring::start(async {
+///     // ...
+///     if let Some(entries) = tokio_uring::runtime::ring_stats().recommended_entries() {
+///         tokio_uring::runtime::resize(entries, entries * 2)?;
+///     }
+///     Ok::<(), std::io::Error>(())
+/// })
+/// .unwrap();
+/// ```
+pub fn resize(sq_entries: u32, cq_entries: u32) -> io::Result<()> {
+    CONTEXT
+        .with(|x| x.handle())
+        .expect("Not in a runtime context")
+        .resize(sq_entries, cq_entries)
+}
+
+/// Registers an eventfd which the ring of the current runtime signals when
+/// operations complete (`IORING_REGISTER_EVENTFD`).
+///
+/// This notifies an external event loop, or another thread, that the
+/// runtime has completions to process, without polling the ring itself.
+/// With `async_only`, the eventfd is only signalled for operations which
+/// could not complete inline, but were handed to a kernel worker or waited
+/// for readiness (`IORING_REGISTER_EVENTFD_ASYNC`).
+///
+/// A ring has at most one eventfd: registering another fails with `EBUSY`
+/// until [`unregister_eventfd`] is called. The eventfd is still owned by the
+/// caller, and must stay open while registered.
+///
+/// # Panics
+///
+/// Panics if called outside of a runtime context.
+///
+/// # Examples
+///
+/// ```no_run
+/// tokio_uring::start(async {
+///     let efd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+///     tokio_uring::runtime::register_eventfd(efd, false)?;
+///
+///     // Hand `efd` to the event loop to watch.
+///
+///     tokio_uring::runtime::unregister_eventfd()?;
+///     unsafe { libc::close(efd) };
+///     Ok::<(), std::io::Error>(())
+/// })
+/// .unwrap();
+/// ```
+pub fn register_eventfd(fd: RawFd, async_only: bool) -> io::Result<()> {
+    CONTEXT
+        .with(|x| x.handle())
+        .expect("Not in a runtime context")
+        .register_eventfd(fd, async_only)
+}
+
+/// Unregisters the eventfd registered with [`register_eventfd`].
+///
+/// Fails with `ENXIO` if no eventfd is registered.
+///
+/// # Panics
+///
+/// Panics if called outside of a runtime context.
+pub fn unregister_eventfd() -> io::Result<()> {
+    CONTEXT
+        .with(|x| x.handle())
+        .expect("Not in a runtime context")
+        .unregister_eventfd()
+}
+
 impl Runtime {
     /// Creates a new tokio_uring runtime on the current thread.
     ///
     /// This takes the tokio-uring [`Builder`](crate::Builder) as a parameter.
     pub fn new(b: &crate::Builder) -> io::Result<Runtime> {
-        let rt = tokio::runtime::Builder::new_current_thread()
-            .on_thread_park(|| {
-                CONTEXT.with(|x| {
-                    let _ = x
-                        .handle()
-                        .expect("Internal error, driver context not present when invoking hooks")
-                        .flush();
-                });
-            })
-            .enable_all()
-            .build()?;
+        if let Some(cpu) = b.pin_to_cpu {
+            crate::pin_thread(cpu)?;
+        }
+
+        let on_park = b.on_park.clone();
+        let mut rt = tokio::runtime::Builder::new_current_thread();
+        rt.on_thread_park(move || {
+            CONTEXT.with(|x| {
+                let handle = x
+                    .handle()
+                    .expect("Internal error, driver context not present when invoking hooks");
+                if let Err(e) = handle.flush() {
+                    if e.kind() != io::ErrorKind::Interrupted {
+                        handle.report_error(DriverError::Submit(e));
+                    }
+                }
+            });
+            if let Some(f) = &on_park {
+                f();
+            }
+        });
+        if let Some(f) = b.on_unpark.clone() {
+            rt.on_thread_unpark(move || f());
+        }
+        let rt = rt.enable_all().build()?;
 
         let tokio_rt = ManuallyDrop::new(rt);
         let local = ManuallyDrop::new(LocalSet::new());
         let driver = driver::Handle::new(b)?;
 
-        start_uring_wakes_task(&tokio_rt, &local, driver.clone());
+        start_uring_wakes_task(&tokio_rt, &local, driver.clone(), b.busy_poll);
+
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        local.spawn_local(remote::run_remote_tasks(rx));
+
+        let remote = RuntimeHandle::new(tx, driver.ring_fd());
 
         Ok(Runtime {
             local,
             tokio_rt,
             driver,
+            remote,
+            tasks_dropped: false,
         })
     }
 
+    /// Starts a runtime with `workers` worker threads, each driving its own
+    /// ring.
+    ///
+    /// This is [`MultiThreadRuntime::new`] with the default configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let rt = tokio_uring::Runtime::new_multi_thread(4).unwrap();
+    /// let id = rt.block_on(|| async { std::thread::current().id() });
+    /// assert_ne!(id, std::thread::current().id());
+    /// ```
+    pub fn new_multi_thread(workers: usize) -> io::Result<MultiThreadRuntime> {
+        MultiThreadRuntime::new(workers, &crate::builder())
+    }
+
+    /// Returns a handle other threads can use to send work to this runtime.
+    pub fn handle(&self) -> RuntimeHandle {
+        self.remote.clone()
+    }
+
     /// Runs a future to completion on the tokio-uring runtime. This is the
     /// runtime's entry point.
     ///
@@ -106,7 +480,31 @@ impl Runtime {
     ///
     /// This function panics if the provided future panics, or if called within an
     /// asynchronous execution context.
-    /// Runs a future to completion on the current runtime.
+    ///
+    /// # Examples
+    ///
+    /// A runtime kept in a struct, running an entry future per request:
+    ///
+    /// ```no_run
+    /// struct App {
+    ///     rt: tokio_uring::Runtime,
+    /// }
+    ///
+    /// impl App {
+    ///     fn handle(&self, path: &str) -> std::io::Result<()> {
+    ///         self.rt.block_on(async {
+    ///             let file = tokio_uring::fs::File::open(path).await?;
+    ///             file.close().await
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// let app = App {
+    ///     rt: tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap(),
+    /// };
+    /// app.handle("hello.txt").unwrap();
+    /// app.handle("world.txt").unwrap();
+    /// ```
     pub fn block_on<F>(&self, future: F) -> F::Output
     where
         F: Future,
@@ -134,10 +532,80 @@ impl Runtime {
 
         res
     }
-}
 
-impl Drop for Runtime {
-    fn drop(&mut self) {
+    /// Spawns a task on the runtime, returning a [`JoinHandle`] for it.
+    ///
+    /// This is [`spawn`] for use outside of the runtime, e.g. to start
+    /// background tasks before the first call to
+    /// [`block_on`](Runtime::block_on). The task runs while a future is
+    /// being run by `block_on`, and is suspended in between.
+    ///
+    /// [`JoinHandle`]: tokio::task::JoinHandle
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let rt = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    /// let task = rt.spawn(async {
+    ///     tokio_uring::no_op().await.unwrap();
+    /// });
+    ///
+    /// rt.block_on(task).unwrap();
+    /// ```
+    pub fn spawn<T: Future + 'static>(&self, task: T) -> tokio::task::JoinHandle<T::Output> {
+        self.local.spawn_local(task)
+    }
+
+    /// Shuts the runtime down, reporting what was left in flight.
+    ///
+    /// All tasks still on the runtime are dropped, in-flight operations are
+    /// cancelled, and the runtime waits for the kernel to release them, as
+    /// when the runtime is dropped. The returned [`ShutdownReport`] accounts
+    /// for the operations and for errors closing resources which were not
+    /// closed explicitly, e.g. for a clean-shutdown audit log.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let rt = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    /// rt.block_on(async {
+    ///     // ...
+    /// });
+    ///
+    /// let report = rt.shutdown();
+    /// if !report.is_clean() {
+    ///     eprintln!("unclean shutdown: {:?}", report);
+    /// }
+    /// ```
+    pub fn shutdown(mut self) -> ShutdownReport {
+        CONTEXT.with(|cx| {
+            cx.set_handle(self.driver.clone());
+            cx.collect_close_errors();
+        });
+
+        self.drop_tasks();
+        let drain = self.driver.drain();
+
+        let close_errors = CONTEXT.with(|cx| {
+            cx.unset_driver();
+            cx.take_close_errors()
+        });
+
+        ShutdownReport {
+            in_flight: drain.in_flight,
+            completed: drain.completed,
+            cancelled: drain.cancelled,
+            close_errors,
+        }
+    }
+
+    fn drop_tasks(&mut self) {
+        if self.tasks_dropped {
+            return;
+        }
+        self.tasks_dropped = true;
+        self.driver.shut_down();
+
         // drop tasks in correct order
         unsafe {
             ManuallyDrop::drop(&mut self.local);
@@ -146,28 +614,151 @@ impl Drop for Runtime {
     }
 }
 
+impl Drop for Runtime {
+    fn drop(&mut self) {
+        self.drop_tasks();
+    }
+}
+
+/// The file descriptor of the runtime's ring, which polls readable while
+/// completions are waiting to be processed.
+///
+/// An external event loop can watch it to learn when to drive the runtime.
+/// To be notified through an eventfd instead, see [`register_eventfd`].
+impl AsRawFd for Runtime {
+    fn as_raw_fd(&self) -> RawFd {
+        self.driver.as_raw_fd()
+    }
+}
+
+/// The fd of the ring of a runtime, registered with the Tokio reactor.
+///
+/// Re-creating the ring points the fd at the new ring, which the reactor
+/// knows nothing about, so it is registered anew.
+struct RingFd {
+    driver: driver::Handle,
+    fd: Option<AsyncFd<driver::Handle>>,
+    generation: u64,
+}
+
+impl RingFd {
+    fn new(driver: driver::Handle) -> io::Result<RingFd> {
+        let generation = driver.poll_generation(&Context::from_waker(Waker::noop()));
+        Ok(RingFd {
+            fd: Some(AsyncFd::new(driver.clone())?),
+            driver,
+            generation,
+        })
+    }
+
+    fn get_ref(&self) -> &driver::Handle {
+        &self.driver
+    }
+
+    fn poll_read_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<AsyncFdReadyGuard<'_, driver::Handle>>> {
+        let generation = self.driver.poll_generation(cx);
+        if generation != self.generation {
+            // Deregistering goes by fd, which refers to the new ring already,
+            // so deregister before registering it.
+            self.fd = None;
+            self.generation = generation;
+        }
+        if self.fd.is_none() {
+            self.fd = Some(AsyncFd::new(self.driver.clone())?);
+        }
+        self.fd.as_ref().unwrap().poll_read_ready(cx)
+    }
+
+    async fn readable(&mut self) -> io::Result<AsyncFdReadyGuard<'_, driver::Handle>> {
+        std::future::poll_fn(|cx| self.poll_read_ready(cx).map_ok(drop)).await?;
+        // Readiness is kept until cleared, so this resolves right away.
+        self.fd.as_ref().unwrap().readable().await
+    }
+}
+
 fn start_uring_wakes_task(
     tokio_rt: &tokio::runtime::Runtime,
     local: &LocalSet,
     driver: driver::Handle,
+    busy_poll: Option<(BusyPoll, bool)>,
 ) {
     let _guard = tokio_rt.enter();
-    let async_driver_handle = AsyncFd::new(driver).unwrap();
 
-    local.spawn_local(drive_uring_wakes(async_driver_handle));
+    if let Some(submitted) = driver.polled() {
+        local.spawn_local(drive_uring_polled(driver, submitted));
+        return;
+    }
+
+    let async_driver_handle = RingFd::new(driver).unwrap();
+
+    match busy_poll {
+        Some((mode, pause)) => {
+            local.spawn_local(drive_uring_spinning(async_driver_handle, mode, pause))
+        }
+        None => local.spawn_local(drive_uring_wakes(async_driver_handle)),
+    };
 }
 
-async fn drive_uring_wakes(driver: AsyncFd<driver::Handle>) {
+async fn drive_uring_wakes(mut driver: RingFd) {
     loop {
         // Wait for read-readiness
         let mut guard = driver.readable().await.unwrap();
 
-        guard.get_inner().dispatch_completions();
+        if guard.get_inner().dispatch_budgeted() {
+            // Completions are left over, the ring stays readable. Let other
+            // tasks run before processing them.
+            drop(guard);
+            tokio::task::yield_now().await;
+            continue;
+        }
 
         guard.clear_ready();
     }
 }
 
+// Checks the completion queue every time the runtime runs out of tasks, by
+// yielding rather than waiting for the ring to become readable. Yielding
+// keeps the runtime from parking, which is where submissions are otherwise
+// flushed, so flush them here.
+async fn drive_uring_spinning(mut driver: RingFd, mode: BusyPoll, pause: bool) {
+    let mut last_completion = Instant::now();
+
+    loop {
+        if driver.get_ref().spin() {
+            last_completion = Instant::now();
+        } else if let BusyPoll::Timeout(timeout) = mode {
+            if last_completion.elapsed() >= timeout {
+                let mut guard = driver.readable().await.unwrap();
+                guard.get_inner().dispatch_budgeted();
+                guard.clear_ready();
+                last_completion = Instant::now();
+                continue;
+            }
+        }
+
+        if pause {
+            std::hint::spin_loop();
+        }
+        tokio::task::yield_now().await;
+    }
+}
+
+// An I/O polling ring posts no completion events until asked to poll, so
+// poll it for as long as operations are in flight, yielding to other tasks
+// in between.
+async fn drive_uring_polled(driver: driver::Handle, submitted: Rc<Notify>) {
+    loop {
+        submitted.notified().await;
+
+        while driver.poll_completions() {
+            tokio::task::yield_now().await;
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -177,13 +768,13 @@ mod test {
     #[test]
     fn block_on() {
         let rt = Runtime::new(&builder()).unwrap();
-        rt.block_on(async move { () });
+        rt.block_on(async move {});
     }
 
     #[test]
     fn block_on_twice() {
         let rt = Runtime::new(&builder()).unwrap();
-        rt.block_on(async move { () });
-        rt.block_on(async move { () });
+        rt.block_on(async move {});
+        rt.block_on(async move {});
     }
 }
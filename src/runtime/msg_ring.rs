@@ -0,0 +1,103 @@
+use std::future::poll_fn;
+use std::io;
+
+use crate::runtime::driver::op::Op;
+use crate::runtime::{RuntimeHandle, CONTEXT};
+
+/// Tags the user data of message completions, telling them apart from those
+/// of operations.
+pub(crate) const MSG_TAG: u64 = 1 << 63;
+
+/// A message posted to the ring of a runtime by another runtime, as returned
+/// by [`recv_msg`].
+///
+/// See [`RuntimeHandle::send_msg`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Message {
+    value: i32,
+    data: u64,
+}
+
+impl Message {
+    pub(crate) fn new(value: i32, data: u64) -> Message {
+        Message { value, data }
+    }
+
+    /// The value the message was sent with.
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    /// The data the message was sent with.
+    pub fn data(&self) -> u64 {
+        self.data
+    }
+}
+
+impl RuntimeHandle {
+    /// Posts a message to the ring of this handle's runtime, from the ring
+    /// of the current runtime (`IORING_OP_MSG_RING`).
+    ///
+    /// The message is a completion event carrying `value` and `data`, which
+    /// the receiving runtime picks up with [`recv_msg`]. Unlike
+    /// [`execute`](RuntimeHandle::execute), this goes through the kernel
+    /// without allocating or waking the receiving thread through an eventfd,
+    /// e.g. to build work stealing between per-core runtimes.
+    ///
+    /// `data` must leave the most significant bit clear, otherwise an
+    /// `InvalidInput` error is returned. An error is also returned if the
+    /// receiving runtime has shut down. Requires Linux 5.18 or later.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a runtime context.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let rt = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    /// let handle = rt.handle();
+    ///
+    /// std::thread::spawn(move || {
+    ///     tokio_uring::start(async move {
+    ///         handle.send_msg(1, 42).await.unwrap();
+    ///     });
+    /// });
+    ///
+    /// rt.block_on(async {
+    ///     let msg = tokio_uring::runtime::recv_msg().await;
+    ///     assert_eq!((msg.value(), msg.data()), (1, 42));
+    /// });
+    /// ```
+    pub async fn send_msg(&self, value: i32, data: u64) -> io::Result<()> {
+        if data & MSG_TAG != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "message data must leave the most significant bit clear",
+            ));
+        }
+
+        let ring_fd = self
+            .ring_fd()
+            .upgrade()
+            .ok_or_else(|| io::Error::other("runtime has shut down"))?;
+        Op::msg_ring(ring_fd, value, data | MSG_TAG)?.await
+    }
+}
+
+/// Waits for a message posted to the ring of the current runtime.
+///
+/// Messages are received in the order they were posted. They are meant for a
+/// single receiving task: when several tasks wait, only the last one to poll
+/// is woken. See [`RuntimeHandle::send_msg`].
+///
+/// # Panics
+///
+/// Panics if called outside of a runtime context.
+pub async fn recv_msg() -> Message {
+    let handle = CONTEXT
+        .with(|x| x.handle())
+        .expect("Not in a runtime context");
+
+    poll_fn(|cx| handle.poll_msg(cx)).await
+}
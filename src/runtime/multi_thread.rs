@@ -0,0 +1,208 @@
+use std::future::Future;
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use tokio::sync::oneshot;
+
+use crate::runtime::{JoinError, RemoteJoinHandle, Runtime, RuntimeHandle};
+
+/// A runtime driving one ring per worker thread, as returned by
+/// [`Runtime::new_multi_thread`].
+///
+/// Each worker thread runs its own [`Runtime`], with its own ring and its
+/// own local task queue, so resources never cross threads. Tasks are
+/// spawned onto the workers in turn with [`spawn`], or onto a given worker
+/// with [`spawn_on`]. A task stays on the worker it was spawned on.
+///
+/// Dropping the runtime stops the workers, dropping the tasks still on them,
+/// and waits for the worker threads to exit.
+///
+/// [`spawn`]: MultiThreadRuntime::spawn
+/// [`spawn_on`]: MultiThreadRuntime::spawn_on
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::File;
+///
+/// let rt = tokio_uring::Runtime::new_multi_thread(4).unwrap();
+///
+/// let tasks: Vec<_> = (0..8)
+///     .map(|i| {
+///         rt.spawn(move || async move {
+///             let file = File::create(format!("{}.txt", i)).await.unwrap();
+///             file.close().await.unwrap();
+///         })
+///     })
+///     .collect();
+///
+/// rt.block_on(|| async {
+///     // Runs on one of the workers.
+/// });
+/// ```
+pub struct MultiThreadRuntime {
+    workers: Vec<Worker>,
+    next: AtomicUsize,
+}
+
+struct Worker {
+    handle: RuntimeHandle,
+    stop: Option<oneshot::Sender<()>>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl MultiThreadRuntime {
+    /// Starts `workers` worker threads, each with a runtime configured by
+    /// `b`.
+    ///
+    /// The rings are created on the worker threads, which keeps options such
+    /// as [`Builder::single_issuer`](crate::Builder::single_issuer) valid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `workers` is zero.
+    pub fn new(workers: usize, b: &crate::Builder) -> io::Result<MultiThreadRuntime> {
+        assert!(
+            workers > 0,
+            "a multi-thread runtime needs at least one worker"
+        );
+
+        let mut rt = MultiThreadRuntime {
+            workers: Vec::with_capacity(workers),
+            next: AtomicUsize::new(0),
+        };
+
+        for i in 0..workers {
+            // Dropping `rt` on error stops the workers started so far.
+            rt.workers.push(Worker::start(i, b.clone())?);
+        }
+
+        Ok(rt)
+    }
+
+    /// Returns the number of worker threads.
+    pub fn workers(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Returns a handle to the runtime of worker `worker`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `worker` is out of range.
+    pub fn handle(&self, worker: usize) -> RuntimeHandle {
+        self.workers[worker].handle.clone()
+    }
+
+    /// Calls `f` on the next worker, in turn, and spawns the future it
+    /// returns as a task there.
+    ///
+    /// See [`RuntimeHandle::spawn_with`].
+    pub fn spawn<F, Fut>(&self, f: F) -> RemoteJoinHandle<Fut::Output>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future + 'static,
+        Fut::Output: Send + 'static,
+    {
+        let worker = self.next.fetch_add(1, Ordering::Relaxed) % self.workers.len();
+        self.spawn_on(worker, f)
+    }
+
+    /// Calls `f` on worker `worker` and spawns the future it returns as a
+    /// task there.
+    ///
+    /// See [`RuntimeHandle::spawn_with`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `worker` is out of range.
+    pub fn spawn_on<F, Fut>(&self, worker: usize, f: F) -> RemoteJoinHandle<Fut::Output>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future + 'static,
+        Fut::Output: Send + 'static,
+    {
+        self.workers[worker].handle.spawn_with(f)
+    }
+
+    /// Spawns a task as [`spawn`](MultiThreadRuntime::spawn) does, and
+    /// blocks the current thread until it completes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the task panics, resuming the panic, or if called within an
+    /// asynchronous execution context.
+    pub fn block_on<F, Fut>(&self, f: F) -> Fut::Output
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future + 'static,
+        Fut::Output: Send + 'static,
+    {
+        match self.spawn(f).blocking_join() {
+            Ok(output) => output,
+            Err(JoinError::Panic(panic)) => std::panic::resume_unwind(panic),
+            Err(JoinError::Cancelled) => panic!("task cancelled"),
+        }
+    }
+}
+
+impl Drop for MultiThreadRuntime {
+    fn drop(&mut self) {
+        // Stop all workers before waiting for any of them.
+        for worker in &mut self.workers {
+            worker.stop.take();
+        }
+        for worker in &mut self.workers {
+            if let Some(thread) = worker.thread.take() {
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+impl Worker {
+    fn start(i: usize, mut b: crate::Builder) -> io::Result<Worker> {
+        b.pin_to_cpu = b.pin_to_cpu.map(|cpu| cpu + i);
+
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let (stop, stop_rx) = oneshot::channel::<()>();
+
+        let thread = thread::Builder::new()
+            .name(format!("tokio-uring-worker-{}", i))
+            .spawn(move || {
+                let rt = match Runtime::new(&b) {
+                    Ok(rt) => rt,
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                        return;
+                    }
+                };
+                let _ = ready_tx.send(Ok(rt.handle()));
+
+                // Run the tasks until asked to stop.
+                rt.block_on(async {
+                    let _ = stop_rx.await;
+                });
+            })?;
+
+        let handle = match ready_rx.recv() {
+            Ok(Ok(handle)) => handle,
+            Ok(Err(e)) => {
+                let _ = thread.join();
+                return Err(e);
+            }
+            Err(_) => {
+                let _ = thread.join();
+                return Err(io::Error::other("worker thread exited during startup"));
+            }
+        };
+
+        Ok(Worker {
+            handle,
+            stop: Some(stop),
+            thread: Some(thread),
+        })
+    }
+}
@@ -0,0 +1,30 @@
+/// What the runtime does when its completion queue overflows, set with
+/// [`Builder::cq_overflow`](crate::Builder::cq_overflow).
+///
+/// The completion queue overflows when more operations complete than it has
+/// room for before the runtime reaps them. The kernel then holds the extra
+/// completions aside (`IORING_FEAT_NODROP`, Linux 5.5 and later) until the
+/// queue has room again, at the cost of memory and of slower completions.
+/// The number of times this happened is reported by
+/// [`RingStats::cq_overflow`](crate::runtime::RingStats::cq_overflow).
+///
+/// A ring cannot grow once created. To size it for the load, see
+/// [`RingStats::recommended_entries`](crate::runtime::RingStats::recommended_entries).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CqOverflow {
+    /// Let the kernel hold overflowed completions aside, and flush them to
+    /// the queue once it has room.
+    #[default]
+    Buffer,
+
+    /// Keep the operations in flight within the size of the completion
+    /// queue, so that it cannot overflow.
+    ///
+    /// Submitting an operation while the queue could be filled waits for
+    /// completions first, blocking the runtime thread.
+    Backpressure,
+
+    /// Panic when the completion queue is found overflowed.
+    Panic,
+}
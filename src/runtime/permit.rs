@@ -0,0 +1,94 @@
+use std::future::Future;
+
+use crate::runtime::{driver, CONTEXT};
+use crate::Submit;
+
+/// Waits for room under the cap on operations in flight of the current
+/// runtime, set with [`Builder::max_in_flight`](crate::Builder::max_in_flight),
+/// returning a permit to submit an operation with.
+///
+/// The permit holds a slot aside until it is used or dropped, so the
+/// operation submitted with it is admitted even if other tasks filled the
+/// remaining slots meanwhile. Without a cap, this resolves immediately.
+///
+/// # Panics
+///
+/// Panics if called outside of a runtime context.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::File;
+/// use tokio_uring::Submit;
+///
+/// tokio_uring::builder().max_in_flight(64).start(async {
+///     let file = File::open("hello.txt").await.unwrap();
+///
+///     for i in 0..1024 {
+///         let permit = tokio_uring::runtime::op_permit().await;
+///         let read = permit.submit(file.read_at(vec![0; 4096], i * 4096));
+///         tokio_uring::spawn(read);
+///     }
+/// });
+/// ```
+pub async fn op_permit() -> OpPermit {
+    let handle = CONTEXT
+        .with(|x| x.handle())
+        .expect("Not in a runtime context");
+
+    std::future::poll_fn(|cx| handle.poll_permit(cx)).await;
+    OpPermit { handle }
+}
+
+/// A slot for one operation under the cap on operations in flight of a
+/// runtime, as returned by [`op_permit`].
+///
+/// Dropping the permit gives the slot back.
+#[must_use = "a permit holds a slot until it is used or dropped"]
+pub struct OpPermit {
+    handle: driver::Handle,
+}
+
+impl OpPermit {
+    /// Submits `op` in the slot of the permit.
+    ///
+    /// The permit covers a single operation: submitting linked operations
+    /// needs a slot for each of the others.
+    pub fn submit<S: Submit>(self, op: S) -> S::Output {
+        drop(self);
+        op.submit()
+    }
+
+    /// Runs `fut`, which submits an operation when first polled, in the slot
+    /// of the permit.
+    ///
+    /// This suits the operations of the crate which are `async fn`, and
+    /// submit when first polled rather than when called.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// tokio_uring::builder().max_in_flight(64).start(async {
+    ///     let permit = tokio_uring::runtime::op_permit().await;
+    ///     permit.run(tokio_uring::no_op()).await.unwrap();
+    /// });
+    /// ```
+    pub async fn run<F: Future>(self, fut: F) -> F::Output {
+        // The slot is only given back once `fut` is about to be polled,
+        // which then submits before anything else runs.
+        drop(self);
+        fut.await
+    }
+}
+
+impl std::fmt::Debug for OpPermit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpPermit").finish()
+    }
+}
+
+impl Drop for OpPermit {
+    fn drop(&mut self) {
+        self.handle.release_permit();
+    }
+}
@@ -0,0 +1,77 @@
+use std::io;
+
+use crate::runtime::CONTEXT;
+
+/// A set of credentials registered with the ring.
+///
+/// Registering a personality snapshots the credentials of the calling thread.
+/// Operations tagged with the personality, e.g. through
+/// [`OpenOptions::personality`] or [`UnsubmittedOneshot::personality`], are
+/// then performed with these credentials, whatever the credentials of the
+/// process at the time of submission. This lets a privileged broker assume
+/// another identity, register it, and switch back, to later perform
+/// individual operations on behalf of that identity.
+///
+/// Personalities are tied to the ring of the runtime they were registered on,
+/// and remain registered until [`unregister`](Personality::unregister) is
+/// called or the runtime shuts down.
+///
+/// [`OpenOptions::personality`]: crate::fs::OpenOptions::personality
+/// [`UnsubmittedOneshot::personality`]: crate::UnsubmittedOneshot::personality
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::OpenOptions;
+/// use tokio_uring::runtime::Personality;
+///
+/// tokio_uring::start(async {
+///     // Switch to the credentials of the client, e.g. with setresuid, then:
+///     let client = Personality::register().unwrap();
+///     // Switch back to the credentials of the broker.
+///
+///     let file = OpenOptions::new()
+///         .read(true)
+///         .personality(client)
+///         .open("/home/client/data.txt")
+///         .await
+///         .unwrap();
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Personality(u16);
+
+impl Personality {
+    /// Registers the credentials of the current thread with the ring of the
+    /// current runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a runtime context.
+    pub fn register() -> io::Result<Personality> {
+        CONTEXT
+            .with(|x| x.handle())
+            .expect("Not in a runtime context")
+            .register_personality()
+            .map(Personality)
+    }
+
+    /// Unregisters the personality from the ring of the current runtime.
+    ///
+    /// Operations already submitted with the personality are unaffected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a runtime context.
+    pub fn unregister(self) -> io::Result<()> {
+        CONTEXT
+            .with(|x| x.handle())
+            .expect("Not in a runtime context")
+            .unregister_personality(self.0)
+    }
+
+    /// Returns the id the kernel assigned to the personality.
+    pub fn id(&self) -> u16 {
+        self.0
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+use std::io;
+
+use io_uring::IoUring;
+
+/// The operations and features supported by the running kernel, as
+/// returned by [`probe`].
+///
+/// Operations are identified by their opcode, such as
+/// [`SendZc::CODE`](io_uring::opcode::SendZc::CODE).
+pub struct Probe {
+    probe: io_uring::Probe,
+    params: io_uring::Parameters,
+}
+
+impl Probe {
+    pub(crate) fn new(
+        submitter: &io_uring::Submitter<'_>,
+        params: &io_uring::Parameters,
+    ) -> io::Result<Probe> {
+        let mut probe = io_uring::Probe::new();
+        submitter.register_probe(&mut probe)?;
+
+        Ok(Probe {
+            probe,
+            params: params.clone(),
+        })
+    }
+
+    /// Returns `true` if the kernel supports the operation `opcode`.
+    pub fn is_supported(&self, opcode: u8) -> bool {
+        self.probe.is_supported(opcode)
+    }
+
+    /// Returns the parameters of a ring set up with the default
+    /// configuration, telling which ring features the kernel supports.
+    pub fn params(&self) -> &io_uring::Parameters {
+        &self.params
+    }
+}
+
+impl fmt::Debug for Probe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Probe").field("probe", &self.probe).finish()
+    }
+}
+
+/// Probes the operations and features supported by the running kernel
+/// (`IORING_REGISTER_PROBE`).
+///
+/// This sets up a small ring of its own, so it may be called before starting
+/// a runtime, e.g. to choose between operations at startup. Probing requires
+/// Linux 5.6 or later, and fails with `EINVAL` on older kernels.
+///
+/// # Examples
+///
+/// ```no_run
+/// use io_uring::opcode;
+///
+/// let probe = tokio_uring::probe().unwrap();
+/// if probe.is_supported(opcode::SendZc::CODE) {
+///     println!("zero-copy sends are supported");
+/// }
+/// if probe.params().is_feature_fast_poll() {
+///     println!("sockets are polled internally");
+/// }
+/// ```
+pub fn probe() -> io::Result<Probe> {
+    let ring = IoUring::new(2)?;
+    Probe::new(&ring.submitter(), ring.params())
+}
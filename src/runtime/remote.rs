@@ -0,0 +1,335 @@
+use std::any::Any;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::os::unix::io::OwnedFd;
+use std::pin::Pin;
+use std::sync::Weak;
+use std::task::{Context, Poll};
+use tokio::sync::{mpsc, oneshot};
+
+/// Work sent to a runtime from another thread.
+pub(crate) type Task = Box<dyn FnOnce() + Send>;
+
+/// A `Send + Sync` handle used to hand work to a [`Runtime`] from other
+/// threads.
+///
+/// The `tokio-uring` resource types are bound to the thread owning the ring,
+/// so other threads cannot use them directly. Instead, they can ask the ring
+/// thread to run a closure on their behalf with [`execute`]. The closure runs
+/// within the runtime context, so it may open files, submit operations and
+/// [`spawn`] local tasks.
+///
+/// Enqueued work only makes progress while the runtime is being driven by
+/// [`Runtime::block_on`]. Cloning a handle is cheap.
+///
+/// [`Runtime`]: crate::Runtime
+/// [`Runtime::block_on`]: crate::Runtime::block_on
+/// [`execute`]: RuntimeHandle::execute
+/// [`spawn`]: crate::spawn
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::fs::File;
+///
+/// let rt = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+/// let handle = rt.handle();
+///
+/// std::thread::spawn(move || {
+///     handle
+///         .execute(|| {
+///             tokio_uring::spawn(async {
+///                 let file = File::create("hello.txt").await.unwrap();
+///                 file.close().await.unwrap();
+///             });
+///         })
+///         .unwrap();
+/// });
+///
+/// rt.block_on(async {
+///     // Drive the runtime, running the remote work.
+/// });
+/// ```
+#[derive(Clone)]
+pub struct RuntimeHandle {
+    tx: mpsc::UnboundedSender<Task>,
+    ring_fd: Weak<OwnedFd>,
+}
+
+impl RuntimeHandle {
+    pub(crate) fn new(tx: mpsc::UnboundedSender<Task>, ring_fd: Weak<OwnedFd>) -> RuntimeHandle {
+        RuntimeHandle { tx, ring_fd }
+    }
+
+    // The file descriptor of the runtime's ring, which can only be upgraded
+    // while the runtime is alive.
+    pub(crate) fn ring_fd(&self) -> &Weak<OwnedFd> {
+        &self.ring_fd
+    }
+
+    /// Enqueues `f` to run on the runtime's thread.
+    ///
+    /// The ring thread is woken if it is parked. Closures run in the order
+    /// they were enqueued.
+    ///
+    /// Returns an error if the runtime has been dropped, in which case `f`
+    /// is dropped without being run.
+    pub fn execute<F>(&self, f: F) -> io::Result<()>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.tx
+            .send(Box::new(f))
+            .map_err(|_| io::Error::other("runtime has shut down"))
+    }
+
+    /// Spawns `future` as a task on the runtime's thread, returning a
+    /// [`RemoteJoinHandle`] that can be awaited from any thread.
+    ///
+    /// This lets threads outside the runtime, e.g. workers of a multi-thread
+    /// Tokio runtime, schedule tasks onto a `tokio-uring` runtime. The future
+    /// must be `Send` to reach the ring thread; use [`spawn_with`] for tasks
+    /// which hold `tokio-uring` resources across await points.
+    ///
+    /// If the runtime has shut down, or shuts down before the task completes,
+    /// the handle resolves to a cancelled [`JoinError`].
+    ///
+    /// [`spawn_with`]: RuntimeHandle::spawn_with
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let rt = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    /// let handle = rt.handle();
+    ///
+    /// std::thread::spawn(move || {
+    ///     let task = handle.spawn(async move {
+    ///         // Runs on the ring thread.
+    ///         std::thread::current().id()
+    ///     });
+    /// });
+    ///
+    /// rt.block_on(async {
+    ///     // Drive the runtime, running the remote task.
+    /// });
+    /// ```
+    pub fn spawn<F>(&self, future: F) -> RemoteJoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.spawn_with(move || future)
+    }
+
+    /// Calls `f` on the runtime's thread and spawns the future it returns as
+    /// a task, returning a [`RemoteJoinHandle`] that can be awaited from any
+    /// thread.
+    ///
+    /// Only the closure and the task's output cross threads, so the future
+    /// may hold `!Send` resources such as a [`File`](crate::fs::File).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    /// use tokio_uring::Submit;
+    ///
+    /// let rt = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    /// let handle = rt.handle();
+    ///
+    /// let task = handle.spawn_with(|| async {
+    ///     let file = File::open("hello.txt").await?;
+    ///     let (n, buf) = file.read_at(vec![0; 4096], 0).submit().await.map_err(|e| e.0)?;
+    ///     file.close().await?;
+    ///     Ok::<_, std::io::Error>(buf[..n].to_vec())
+    /// });
+    ///
+    /// // Await the task from a thread running a regular Tokio runtime.
+    /// std::thread::spawn(move || {
+    ///     let tokio_rt = tokio::runtime::Builder::new_current_thread()
+    ///         .build()
+    ///         .unwrap();
+    ///     let contents = tokio_rt.block_on(task).unwrap().unwrap();
+    ///     println!("{:?}", contents);
+    /// });
+    ///
+    /// rt.block_on(async {
+    ///     // Drive the runtime, running the remote task.
+    /// });
+    /// ```
+    pub fn spawn_with<F, Fut>(&self, f: F) -> RemoteJoinHandle<Fut::Output>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future + 'static,
+        Fut::Output: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+
+        // If the runtime is gone, `tx` is dropped along with the closure and
+        // the handle resolves as cancelled.
+        let _ = self.execute(move || {
+            let _ = tx.send(crate::spawn(f()));
+        });
+
+        RemoteJoinHandle {
+            state: JoinState::Spawning(rx),
+        }
+    }
+
+    /// Returns `true` if the runtime has been dropped.
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+}
+
+impl fmt::Debug for RuntimeHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RuntimeHandle")
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+/// An owned permission to join on a task spawned with
+/// [`RuntimeHandle::spawn`] or [`RuntimeHandle::spawn_with`].
+///
+/// Unlike the handles returned by [`spawn`](crate::spawn), this handle is
+/// `Send` and may be awaited from any thread. Dropping it detaches the task.
+pub struct RemoteJoinHandle<T> {
+    state: JoinState<T>,
+}
+
+enum JoinState<T> {
+    /// Waiting for the ring thread to spawn the task.
+    Spawning(oneshot::Receiver<tokio::task::JoinHandle<T>>),
+
+    /// The task has been spawned.
+    Spawned(tokio::task::JoinHandle<T>),
+}
+
+impl<T> RemoteJoinHandle<T> {
+    /// Blocks the current thread until the task completes.
+    ///
+    /// This lets threads not running an asynchronous runtime, e.g. the
+    /// workers of a thread pool, wait for work done by the ring thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called within an asynchronous execution context.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    ///
+    /// let rt = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    /// let handle = rt.handle();
+    ///
+    /// std::thread::spawn(move || {
+    ///     let len = handle
+    ///         .spawn_with(|| async {
+    ///             let file = File::open("hello.txt").await?;
+    ///             let len = file.metadata().await?.len();
+    ///             file.close().await?;
+    ///             Ok::<_, std::io::Error>(len)
+    ///         })
+    ///         .blocking_join()
+    ///         .unwrap();
+    /// });
+    ///
+    /// rt.block_on(async {
+    ///     // Drive the runtime, running the remote task.
+    /// });
+    /// ```
+    pub fn blocking_join(self) -> Result<T, JoinError> {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("failed to build a runtime to wait on")
+            .block_on(self)
+    }
+}
+
+impl<T> Future for RemoteJoinHandle<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            match &mut self.state {
+                JoinState::Spawning(rx) => match ready!(Pin::new(rx).poll(cx)) {
+                    Ok(handle) => self.state = JoinState::Spawned(handle),
+                    Err(_) => return Poll::Ready(Err(JoinError::Cancelled)),
+                },
+                JoinState::Spawned(handle) => {
+                    return Pin::new(handle).poll(cx).map_err(JoinError::from);
+                }
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for RemoteJoinHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let spawned = matches!(self.state, JoinState::Spawned(_));
+        f.debug_struct("RemoteJoinHandle")
+            .field("spawned", &spawned)
+            .finish()
+    }
+}
+
+/// Task failed to execute to completion.
+pub enum JoinError {
+    /// The task was cancelled, or never ran because the runtime shut down.
+    Cancelled,
+
+    /// The task panicked.
+    Panic(Box<dyn Any + Send + 'static>),
+}
+
+impl JoinError {
+    /// Returns `true` if the task was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, JoinError::Cancelled)
+    }
+
+    /// Returns `true` if the task panicked.
+    pub fn is_panic(&self) -> bool {
+        matches!(self, JoinError::Panic(_))
+    }
+}
+
+impl From<tokio::task::JoinError> for JoinError {
+    fn from(e: tokio::task::JoinError) -> JoinError {
+        match e.try_into_panic() {
+            Ok(payload) => JoinError::Panic(payload),
+            Err(_) => JoinError::Cancelled,
+        }
+    }
+}
+
+impl fmt::Debug for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JoinError::Cancelled => f.write_str("JoinError::Cancelled"),
+            JoinError::Panic(_) => f.write_str("JoinError::Panic(..)"),
+        }
+    }
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JoinError::Cancelled => f.write_str("task was cancelled"),
+            JoinError::Panic(_) => f.write_str("task panicked"),
+        }
+    }
+}
+
+impl std::error::Error for JoinError {}
+
+/// Runs the work sent through the runtime's handles.
+pub(crate) async fn run_remote_tasks(mut rx: mpsc::UnboundedReceiver<Task>) {
+    while let Some(task) = rx.recv().await {
+        task();
+    }
+}
@@ -0,0 +1,145 @@
+use crate::runtime::driver::IORING_REGISTER_SYNC_CANCEL;
+use io_uring::register::Restriction;
+use io_uring::{opcode, squeue};
+
+/// An allow-list of `io_uring` operations, installed on the ring with
+/// [`Builder::restrictions`].
+///
+/// Once installed, submitting an operation or calling a register operation
+/// which is not on the list fails with `EACCES`, and the list can never be
+/// lifted for the lifetime of the ring. This lets a service set up its ring,
+/// then drop privileges knowing that the ring cannot be used for anything
+/// beyond what it was built for.
+///
+/// Besides the operations issued by the application, the runtime itself
+/// submits `IORING_OP_ASYNC_CANCEL` to cancel operations,
+/// `IORING_OP_CLOSE` to close resources on `close()`, and linked
+/// `IORING_OP_LINK_TIMEOUT` entries to enforce
+/// [`Builder::op_timeout`](crate::Builder::op_timeout), so these should
+/// generally be allowed, see [`allow_runtime_ops`].
+///
+/// [`Builder::restrictions`]: crate::Builder::restrictions
+/// [`allow_runtime_ops`]: Restrictions::allow_runtime_ops
+///
+/// # Examples
+///
+/// ```no_run
+/// use io_uring::opcode;
+/// use tokio_uring::runtime::Restrictions;
+///
+/// let mut restrictions = Restrictions::new();
+/// restrictions
+///     .allow_op(opcode::Read::CODE)
+///     .allow_op(opcode::Write::CODE)
+///     .allow_runtime_ops();
+///
+/// tokio_uring::builder()
+///     .restrictions(&restrictions)
+///     .start(async {
+///         // Only reads, writes and the operations of the runtime are accepted.
+///     });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Restrictions {
+    entries: Vec<Entry>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Entry {
+    SqeOp(u8),
+    RegisterOp(u8),
+    SqeFlagsAllowed(u8),
+    SqeFlagsRequired(u8),
+}
+
+impl Restrictions {
+    /// Creates an empty allow-list, which permits nothing.
+    pub fn new() -> Restrictions {
+        Restrictions::default()
+    }
+
+    /// Allows submitting the operation with the given opcode, e.g.
+    /// [`io_uring::opcode::Read::CODE`].
+    pub fn allow_op(&mut self, opcode: u8) -> &mut Self {
+        self.entries.push(Entry::SqeOp(opcode));
+        self
+    }
+
+    /// Allows the operations the runtime submits on its own: to cancel
+    /// operations (`IORING_OP_ASYNC_CANCEL`, and
+    /// `IORING_REGISTER_SYNC_CANCEL` on shutdown), to close resources
+    /// (`IORING_OP_CLOSE`), and to time out operations
+    /// (`IORING_OP_LINK_TIMEOUT`, linked with the
+    /// [`IO_LINK`](squeue::Flags::IO_LINK) flag).
+    ///
+    /// Other register operations of the runtime, such as registering
+    /// buffers, files or an eventfd, are only issued when asked for, and need
+    /// to be allowed separately.
+    pub fn allow_runtime_ops(&mut self) -> &mut Self {
+        self.allow_op(opcode::AsyncCancel::CODE)
+            .allow_op(opcode::Close::CODE)
+            .allow_op(opcode::LinkTimeout::CODE)
+            .allow_sqe_flags(squeue::Flags::IO_LINK)
+            .allow_register_op(IORING_REGISTER_SYNC_CANCEL)
+    }
+
+    /// Allows the register operation with the given `IORING_REGISTER_*`
+    /// opcode, e.g. `0` for `IORING_REGISTER_BUFFERS`.
+    ///
+    /// Registering buffers or files after the runtime started, e.g. through
+    /// [`FixedBufRegistry`], requires the matching register and unregister
+    /// opcodes to be allowed.
+    ///
+    /// [`FixedBufRegistry`]: crate::buf::fixed::FixedBufRegistry
+    pub fn allow_register_op(&mut self, opcode: u8) -> &mut Self {
+        self.entries.push(Entry::RegisterOp(opcode));
+        self
+    }
+
+    /// Allows setting the given flags on submitted operations, in addition to
+    /// those allowed already.
+    ///
+    /// Without this, submissions carrying any flag, such as the
+    /// [`IO_LINK`](squeue::Flags::IO_LINK) flag used by linked operations,
+    /// are rejected.
+    pub fn allow_sqe_flags(&mut self, flags: squeue::Flags) -> &mut Self {
+        self.entries.push(Entry::SqeFlagsAllowed(flags.bits()));
+        self
+    }
+
+    /// Requires the given flags to be set on every submitted operation.
+    pub fn require_sqe_flags(&mut self, flags: squeue::Flags) -> &mut Self {
+        self.entries.push(Entry::SqeFlagsRequired(flags.bits()));
+        self
+    }
+
+    /// Returns `true` if submitting the operation `opcode` is allowed.
+    pub(crate) fn allows_op(&self, opcode: u8) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| matches!(*entry, Entry::SqeOp(op) if op == opcode))
+    }
+
+    pub(crate) fn to_restrictions(&self) -> Vec<Restriction> {
+        // The kernel keeps the last set of allowed flags it is given, so they
+        // are merged into one.
+        let mut allowed = None;
+        let mut restrictions: Vec<Restriction> = self
+            .entries
+            .iter()
+            .filter_map(|entry| match *entry {
+                Entry::SqeOp(op) => Some(Restriction::sqe_op(op)),
+                Entry::RegisterOp(op) => Some(Restriction::register_op(op)),
+                Entry::SqeFlagsAllowed(flags) => {
+                    *allowed.get_or_insert(0) |= flags;
+                    None
+                }
+                Entry::SqeFlagsRequired(flags) => Some(Restriction::sqe_flags_required(flags)),
+            })
+            .collect();
+        if let Some(flags) = allowed {
+            restrictions.push(Restriction::sqe_flags_allowed(flags));
+        }
+        restrictions
+    }
+}
@@ -0,0 +1,185 @@
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use pin_project_lite::pin_project;
+
+use crate::runtime::{attach, drive_uring_polled, driver, RingFd, CONTEXT};
+
+/// Identifies a ring of a runtime, as returned by [`add_ring`].
+///
+/// A runtime starts with a single ring, its primary ring. Further rings are
+/// set up differently, e.g. for I/O polling, and operations are routed to
+/// them explicitly with [`on_ring`], or by opcode with [`route`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RingId(Option<usize>);
+
+impl RingId {
+    /// The primary ring of the runtime, set up by the
+    /// [`Builder`](crate::Builder) the runtime was created with.
+    pub const PRIMARY: RingId = RingId(None);
+}
+
+/// Adds a ring set up by `b` to the current runtime.
+///
+/// The ring is driven by a task of its own on the runtime thread. Operations
+/// are submitted to it while polling a future passed to [`on_ring`], and
+/// routed to it by opcode with [`route`]. Files, direct descriptors, fixed
+/// buffers and other resources registered with a ring belong to it, and
+/// operations using them must be submitted to the same ring.
+///
+/// Only the ring-level settings of `b` apply, such as its entries, I/O or
+/// submission polling and restrictions; thread settings such as
+/// [`Builder::pin_to_cpu`](crate::Builder::pin_to_cpu) and busy polling do
+/// not. The ring lives as long as the runtime.
+///
+/// # Panics
+///
+/// Panics if called outside of a runtime context.
+///
+/// # Examples
+///
+/// An I/O polling ring for `O_DIRECT` disk I/O, beside the interrupt-driven
+/// primary ring for sockets:
+///
+/// ```no_run
+/// use std::os::unix::fs::OpenOptionsExt;
+/// use tokio_uring::fs::OpenOptions;
+/// use tokio_uring::runtime::{add_ring, on_ring};
+/// use tokio_uring::Submit;
+///
+/// tokio_uring::start(async {
+///     let polled = add_ring(tokio_uring::builder().iopoll(true))?;
+///
+///     let file = OpenOptions::new()
+///         .read(true)
+///         .custom_flags(libc::O_DIRECT)
+///         .open("/dev/nvme0n1")
+///         .await?;
+///
+///     let buf = Vec::with_capacity(4096);
+///     let (n, _) = on_ring(polled, file.read_at(buf, 0).submit()).await?;
+///     println!("read {} bytes", n);
+///     Ok::<(), Box<dyn std::error::Error>>(())
+/// })
+/// .unwrap();
+/// ```
+pub fn add_ring(b: &crate::Builder) -> io::Result<RingId> {
+    let primary = CONTEXT
+        .with(|x| x.primary())
+        .expect("Not in a runtime context");
+
+    let handle = driver::Handle::new(b)?;
+
+    // Rings other than the primary one are not flushed as the runtime parks,
+    // but by their task, which submissions wake.
+    let submitted = handle.notify_submissions();
+    let task = if b.iopoll {
+        tokio::task::spawn_local(drive_uring_polled(handle.clone(), submitted))
+    } else {
+        let fd = RingFd::new(handle.clone())?;
+        tokio::task::spawn_local(attach::drive_uring_attached(fd, submitted))
+    };
+
+    Ok(RingId(Some(primary.add_ring(handle, task))))
+}
+
+/// Submits the operations `future` starts to `ring`, rather than to the
+/// primary ring of the runtime.
+///
+/// The ring is selected each time `future` is polled, so it applies to
+/// everything the future submits, but not to tasks it spawns. Selecting a
+/// further ring takes precedence over routes set with [`route`], while
+/// selecting [`RingId::PRIMARY`] leaves them in place.
+///
+/// # Panics
+///
+/// Polling the returned future panics if `ring` is not a ring of the current
+/// runtime.
+pub fn on_ring<F: Future>(ring: RingId, future: F) -> OnRing<F> {
+    OnRing { ring, future }
+}
+
+pin_project! {
+    /// Future returned by [`on_ring`].
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct OnRing<F> {
+        ring: RingId,
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F: Future> Future for OnRing<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let this = self.project();
+
+        // Restores the ring selected before, even if the future panics.
+        struct Selected(Option<usize>);
+
+        impl Drop for Selected {
+            fn drop(&mut self) {
+                let _ = CONTEXT.try_with(|x| x.select(self.0));
+            }
+        }
+
+        let _selected = CONTEXT.with(|x| {
+            let primary = x.primary().expect("Not in a runtime context");
+            if let RingId(Some(index)) = *this.ring {
+                assert!(
+                    primary.ring(index).is_some(),
+                    "{:?} is not a ring of the current runtime",
+                    this.ring
+                );
+            }
+            Selected(x.select(this.ring.0))
+        });
+
+        this.future.poll(cx)
+    }
+}
+
+/// Routes the operations with `opcode` to `ring`, or back to the primary
+/// ring with [`RingId::PRIMARY`].
+///
+/// Routes apply to operations submitted to the primary ring, unless a ring
+/// is selected with [`on_ring`]. Operations on direct descriptors and fixed
+/// buffers are registered with the primary ring, so they are not routed.
+/// Nor are linked chains of operations, which are submitted to a single
+/// ring.
+///
+/// # Panics
+///
+/// Panics if called outside of a runtime context.
+///
+/// # Examples
+///
+/// Keep file system operations, which the kernel may punt to its workers,
+/// apart from the sockets of the primary ring:
+///
+/// ```no_run
+/// use io_uring::opcode;
+/// use tokio_uring::runtime::{add_ring, route};
+///
+/// tokio_uring::start(async {
+///     let files = add_ring(&tokio_uring::builder())?;
+///     for code in [opcode::OpenAt::CODE, opcode::Statx::CODE, opcode::Fsync::CODE] {
+///         route(code, files);
+///     }
+///
+///     // Opens the file on the second ring.
+///     let file = tokio_uring::fs::File::open("hello.txt").await?;
+///     file.close().await
+/// })
+/// .unwrap();
+/// ```
+pub fn route(opcode: u8, ring: RingId) {
+    CONTEXT
+        .with(|x| x.primary())
+        .expect("Not in a runtime context")
+        .route(opcode, ring.0)
+}
@@ -0,0 +1,34 @@
+use std::io;
+
+/// What was left to do when a runtime was shut down, as returned by
+/// [`Runtime::shutdown`](crate::Runtime::shutdown).
+///
+/// Shutting down drops all tasks still on the runtime. Operations they left
+/// in flight are cancelled, after the
+/// [shutdown timeout](crate::Builder::shutdown_timeout) if one is set, and the
+/// runtime waits for the kernel to release them. Each of these operations
+/// either completed before the cancellation took effect, or was cancelled.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct ShutdownReport {
+    /// Operations still in flight when the runtime shut down.
+    pub in_flight: usize,
+
+    /// In-flight operations which completed while the runtime drained.
+    pub completed: usize,
+
+    /// In-flight operations which were cancelled.
+    pub cancelled: usize,
+
+    /// Errors closing resources which were dropped without an explicit
+    /// `close()` during shutdown.
+    pub close_errors: Vec<io::Error>,
+}
+
+impl ShutdownReport {
+    /// Returns `true` if nothing was in flight and all resources closed
+    /// without error.
+    pub fn is_clean(&self) -> bool {
+        self.in_flight == 0 && self.close_errors.is_empty()
+    }
+}
@@ -0,0 +1,94 @@
+use std::convert::TryFrom;
+
+/// The largest number of entries the kernel accepts for a ring.
+const MAX_ENTRIES: u32 = 32768;
+
+/// Sizing statistics of the ring of a runtime, as returned by
+/// [`ring_stats`](crate::runtime::ring_stats).
+///
+/// The number of ring entries is fixed when the runtime is created. A ring
+/// too small for the traffic it sees shows up as a full submission queue,
+/// which forces early flushes, and as completion queue overflows, which make
+/// the kernel buffer completions on the side. These statistics track both,
+/// and [`recommended_entries`](RingStats::recommended_entries) turns them
+/// into a value for [`Builder::entries`](crate::Builder::entries).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RingStats {
+    /// Entries of the submission queue.
+    pub sq_entries: u32,
+
+    /// Entries of the completion queue.
+    pub cq_entries: u32,
+
+    /// Times an operation found the submission queue full.
+    pub sq_full: u64,
+
+    /// Times the completion queue was found overflowed.
+    pub cq_overflow: u64,
+
+    /// Completions the kernel dropped as the completion queue overflowed.
+    /// Only kernels without `IORING_FEAT_NODROP`, before Linux 5.5, drop
+    /// completions.
+    pub cq_dropped: u32,
+
+    /// Whether the kernel holds overflowed completions aside rather than
+    /// dropping them (`IORING_FEAT_NODROP`).
+    pub nodrop: bool,
+
+    /// The largest number of operations in flight at once.
+    pub peak_in_flight: usize,
+
+    /// Whether the ring fd is registered with the ring, which saves a file
+    /// descriptor lookup each time the runtime enters the kernel.
+    pub registered_ring: bool,
+}
+
+impl RingStats {
+    /// Returns the number of submission queue entries the runtime should be
+    /// created with, or `None` if the ring has not been found too small.
+    ///
+    /// The recommendation covers the peak number of operations in flight, and
+    /// at least doubles the current size.
+    pub fn recommended_entries(&self) -> Option<u32> {
+        if (self.sq_full == 0 && self.cq_overflow == 0) || self.sq_entries >= MAX_ENTRIES {
+            return None;
+        }
+
+        let peak = u32::try_from(self.peak_in_flight).unwrap_or(u32::MAX);
+        let entries = peak
+            .max(self.sq_entries.saturating_mul(2))
+            .checked_next_power_of_two()
+            .unwrap_or(MAX_ENTRIES);
+        Some(entries.min(MAX_ENTRIES))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn stats(sq_full: u64, cq_overflow: u64, peak_in_flight: usize) -> RingStats {
+        RingStats {
+            sq_entries: 64,
+            cq_entries: 128,
+            sq_full,
+            cq_overflow,
+            peak_in_flight,
+            cq_dropped: 0,
+            nodrop: true,
+            registered_ring: true,
+        }
+    }
+
+    #[test]
+    fn recommended_entries() {
+        assert_eq!(stats(0, 0, 1000).recommended_entries(), None);
+        assert_eq!(stats(1, 0, 10).recommended_entries(), Some(128));
+        assert_eq!(stats(0, 1, 300).recommended_entries(), Some(512));
+        assert_eq!(
+            stats(1, 1, 1 << 20).recommended_entries(),
+            Some(MAX_ENTRIES)
+        );
+    }
+}
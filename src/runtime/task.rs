@@ -0,0 +1,223 @@
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use pin_project_lite::pin_project;
+use tokio::task::JoinError;
+
+thread_local! {
+    // The task being polled on this thread, if it was spawned by a
+    // `TaskBuilder`.
+    static CURRENT: RefCell<Option<Rc<TaskState>>> = const { RefCell::new(None) };
+}
+
+/// Configures a task before spawning it, as tokio's `task::Builder` does.
+///
+/// A task spawned this way carries a name, and keeps count of the time spent
+/// polling it. A thread-per-core service can attribute the CPU time of its
+/// runtime, and stalls of it, to the tasks responsible with
+/// [`TaskHandle::busy_duration`] and [`current_task_name`]. With the
+/// `tracing` feature, each poll of the task is also traced within a `task`
+/// span carrying its name.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::runtime::TaskBuilder;
+///
+/// tokio_uring::start(async {
+///     let handle = TaskBuilder::new()
+///         .name("flush")
+///         .spawn(async {
+///             // ...
+///         })
+///         .unwrap();
+///
+///     handle.await.unwrap();
+/// });
+/// ```
+#[derive(Debug, Default)]
+pub struct TaskBuilder<'a> {
+    name: Option<&'a str>,
+}
+
+impl<'a> TaskBuilder<'a> {
+    /// Creates a builder for a task without a name.
+    pub fn new() -> TaskBuilder<'a> {
+        TaskBuilder::default()
+    }
+
+    /// Sets the name of the task.
+    pub fn name(self, name: &'a str) -> TaskBuilder<'a> {
+        TaskBuilder { name: Some(name) }
+    }
+
+    /// Spawns `task` on the current runtime, as [`spawn`](crate::spawn)
+    /// does, returning a [`TaskHandle`] for it.
+    ///
+    /// This returns a `Result` as tokio's `Builder::spawn_local` does, but
+    /// does not fail as of now.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a `tokio-uring` runtime.
+    pub fn spawn<T>(self, task: T) -> io::Result<TaskHandle<T::Output>>
+    where
+        T: Future + 'static,
+    {
+        let state = Rc::new(TaskState {
+            name: self.name.map(Rc::from),
+            finished: Cell::new(false),
+            busy: Cell::new(Duration::ZERO),
+            polls: Cell::new(0),
+        });
+
+        let inner = tokio::task::spawn_local(Tracked {
+            future: task,
+            _finish: Finish(state.clone()),
+            state: state.clone(),
+        });
+
+        Ok(TaskHandle { inner, state })
+    }
+}
+
+/// An owned permission to join on a task spawned by a [`TaskBuilder`].
+///
+/// This is tokio's `JoinHandle`, along with the name of the task and the
+/// time spent polling it. Awaiting the handle waits for the task to finish.
+/// Dropping it detaches the task.
+pub struct TaskHandle<T> {
+    inner: tokio::task::JoinHandle<T>,
+    state: Rc<TaskState>,
+}
+
+impl<T> TaskHandle<T> {
+    /// Returns the name of the task, if it was given one.
+    pub fn name(&self) -> Option<&str> {
+        self.state.name.as_deref()
+    }
+
+    /// Returns `true` once the task has finished, whether it completed,
+    /// panicked or was aborted.
+    pub fn is_finished(&self) -> bool {
+        self.state.finished.get()
+    }
+
+    /// Returns the time spent polling the task so far.
+    pub fn busy_duration(&self) -> Duration {
+        self.state.busy.get()
+    }
+
+    /// Returns the number of times the task was polled so far.
+    pub fn polls(&self) -> u64 {
+        self.state.polls.get()
+    }
+
+    /// Aborts the task, as tokio's `JoinHandle::abort` does.
+    pub fn abort(&self) {
+        self.inner.abort();
+    }
+}
+
+impl<T> Future for TaskHandle<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+impl<T> fmt::Debug for TaskHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskHandle")
+            .field("name", &self.name())
+            .field("finished", &self.is_finished())
+            .field("busy", &self.busy_duration())
+            .finish()
+    }
+}
+
+/// Returns the name of the task being polled, if it was spawned by a
+/// [`TaskBuilder`] with a name.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio_uring::runtime::{current_task_name, TaskBuilder};
+///
+/// tokio_uring::start(async {
+///     TaskBuilder::new()
+///         .name("worker")
+///         .spawn(async {
+///             assert_eq!(current_task_name().as_deref(), Some("worker"));
+///         })
+///         .unwrap()
+///         .await
+///         .unwrap();
+/// });
+/// ```
+pub fn current_task_name() -> Option<Rc<str>> {
+    CURRENT.with(|current| current.borrow().as_ref()?.name.clone())
+}
+
+struct TaskState {
+    name: Option<Rc<str>>,
+    finished: Cell<bool>,
+    busy: Cell<Duration>,
+    polls: Cell<u64>,
+}
+
+// Marks the task finished once its future is dropped, which tokio does when
+// it completes, panics or is aborted.
+struct Finish(Rc<TaskState>);
+
+impl Drop for Finish {
+    fn drop(&mut self) {
+        self.0.finished.set(true);
+    }
+}
+
+pin_project! {
+    struct Tracked<F> {
+        #[pin]
+        future: F,
+        state: Rc<TaskState>,
+        _finish: Finish,
+    }
+}
+
+impl<F: Future> Future for Tracked<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let this = self.project();
+
+        // Restores the task polled before, even if the future panics.
+        struct Current(Option<Rc<TaskState>>);
+
+        impl Drop for Current {
+            fn drop(&mut self) {
+                let prev = self.0.take();
+                CURRENT.with(|current| *current.borrow_mut() = prev);
+            }
+        }
+
+        let _current =
+            Current(CURRENT.with(|current| current.borrow_mut().replace(this.state.clone())));
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("task", name = this.state.name.as_deref()).entered();
+
+        let start = Instant::now();
+        let res = this.future.poll(cx);
+        this.state.busy.set(this.state.busy.get() + start.elapsed());
+        this.state.polls.set(this.state.polls.get() + 1);
+        res
+    }
+}
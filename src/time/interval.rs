@@ -0,0 +1,75 @@
+use std::time::{Duration, Instant};
+
+use crate::time::{sleep_until, Sleep};
+
+/// Creates an [`Interval`] ticking every `period`, the first tick completing
+/// immediately.
+///
+/// # Panics
+///
+/// Panics if `period` is zero.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// tokio_uring::start(async {
+///     let mut interval = tokio_uring::time::interval(Duration::from_secs(1));
+///     for _ in 0..3 {
+///         interval.tick().await;
+///         println!("tick");
+///     }
+/// });
+/// ```
+pub fn interval(period: Duration) -> Interval {
+    interval_at(Instant::now(), period)
+}
+
+/// Creates an [`Interval`] ticking every `period`, the first tick completing
+/// at `start`.
+///
+/// # Panics
+///
+/// Panics if `period` is zero.
+pub fn interval_at(start: Instant, period: Duration) -> Interval {
+    assert!(!period.is_zero(), "`period` must be non-zero");
+    Interval {
+        sleep: sleep_until(start),
+        period,
+    }
+}
+
+/// Ticks at a fixed period, as returned by [`interval`] and [`interval_at`].
+///
+/// Ticks are scheduled from the deadline of the previous tick rather than
+/// from when it was awaited, so the ticks don't drift. Ticks missed while
+/// not awaited complete immediately, one after the other, until the
+/// interval catches up.
+#[derive(Debug)]
+pub struct Interval {
+    sleep: Sleep,
+    period: Duration,
+}
+
+impl Interval {
+    /// Completes at the next tick, returning the instant it was scheduled
+    /// for.
+    pub async fn tick(&mut self) -> Instant {
+        (&mut self.sleep).await;
+
+        let tick = self.sleep.deadline();
+        self.sleep.reset(tick + self.period);
+        tick
+    }
+
+    /// Returns the period of the interval.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Restarts the interval, its next tick completing a period from now.
+    pub fn reset(&mut self) {
+        self.sleep.reset(Instant::now() + self.period);
+    }
+}
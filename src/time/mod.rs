@@ -0,0 +1,23 @@
+//! Timers driven by the ring.
+//!
+//! [`sleep`] and [`interval`] wait with timeout operations
+//! (`IORING_OP_TIMEOUT`), whose completions arrive through the completion
+//! queue along with those of I/O. They need no timer driver besides the ring,
+//! and work within any `tokio-uring` runtime.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! tokio_uring::start(async {
+//!     tokio_uring::time::sleep(Duration::from_millis(100)).await;
+//!     println!("100 ms have elapsed");
+//! });
+//! ```
+
+mod interval;
+mod sleep;
+
+pub use interval::{interval, interval_at, Interval};
+pub use sleep::{sleep, sleep_until, Sleep};
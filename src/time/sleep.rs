@@ -0,0 +1,227 @@
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use crate::io::timeout::{Timeout, TimeoutUpdate};
+use crate::runtime::driver::op::Op;
+use crate::runtime::{DriverError, CONTEXT};
+use crate::SubmitError;
+
+/// Waits until `duration` has elapsed.
+///
+/// The timeout is submitted to the ring when the returned future is first
+/// polled, and cancelled if the future is dropped before it elapses.
+///
+/// The timeout waits for room under the cap on operations in flight of the
+/// runtime, if any, to be submitted. Should the ring fail it, the failure is
+/// reported to the handler set with
+/// [`Builder::on_driver_error`](crate::Builder::on_driver_error), and the
+/// sleep completes early rather than never.
+///
+/// # Panics
+///
+/// The returned future panics if polled outside of a runtime context.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// tokio_uring::start(async {
+///     tokio_uring::time::sleep(Duration::from_millis(100)).await;
+/// });
+/// ```
+pub fn sleep(duration: Duration) -> Sleep {
+    sleep_until(Instant::now() + duration)
+}
+
+/// Waits until `deadline` is reached.
+///
+/// See [`sleep`].
+pub fn sleep_until(deadline: Instant) -> Sleep {
+    Sleep {
+        deadline,
+        op: None,
+        update: None,
+        elapsed: false,
+    }
+}
+
+/// A future which completes once its deadline is reached, as returned by
+/// [`sleep`] and [`sleep_until`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Sleep {
+    deadline: Instant,
+    op: Option<Op<Timeout>>,
+    update: Option<Op<TimeoutUpdate>>,
+    elapsed: bool,
+}
+
+impl Sleep {
+    /// Returns the instant the future completes at.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Returns `true` once the deadline was reached.
+    pub fn is_elapsed(&self) -> bool {
+        self.elapsed
+    }
+
+    /// Moves the deadline to `deadline`, which may be earlier or later.
+    ///
+    /// The future completes at the new deadline, even if it had completed
+    /// already. A timeout in flight is updated in place
+    /// (`IORING_TIMEOUT_UPDATE`) rather than cancelled and submitted anew,
+    /// which keeps rescheduling cheap, e.g. for the idle timers of many
+    /// connections.
+    pub fn reset(&mut self, deadline: Instant) {
+        self.deadline = deadline;
+        self.elapsed = false;
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let update = match &self.op {
+            Some(op) if !remaining.is_zero() => Op::timeout_update(op, remaining),
+            _ => return self.op = None,
+        };
+        match update {
+            Ok(update) => self.update = Some(update),
+            // Dropping the timeout in flight cancels it, another one is
+            // submitted once polled.
+            Err(_) => self.op = None,
+        }
+    }
+
+    /// Stops the sleep, removing its timeout from the ring
+    /// (`IORING_OP_TIMEOUT_REMOVE`).
+    ///
+    /// Returns `true` if the sleep was stopped before its deadline, or
+    /// `false` if it had elapsed already.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// tokio_uring::start(async {
+    ///     let idle = tokio_uring::time::sleep(Duration::from_secs(60));
+    ///     // ...
+    ///     if idle.cancel().await {
+    ///         println!("the connection was active");
+    ///     }
+    /// });
+    /// ```
+    pub async fn cancel(mut self) -> bool {
+        let op = match self.op.take() {
+            Some(op) => op,
+            None => return !self.elapsed && Instant::now() < self.deadline,
+        };
+
+        match Op::timeout_remove(&op) {
+            Ok(remove) => {
+                let _ = remove.await;
+            }
+            // Dropping the timeout cancels it.
+            Err(_) => return Instant::now() < self.deadline,
+        }
+        match op.await {
+            // A timeout which expired before it was moved to a later
+            // deadline leaves the sleep pending.
+            Ok(()) => Instant::now() < self.deadline,
+            Err(e) if e.raw_os_error() == Some(libc::ECANCELED) => true,
+            Err(_) => Instant::now() < self.deadline,
+        }
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+
+        loop {
+            if this.elapsed {
+                return Poll::Ready(());
+            }
+
+            if let Some(update) = &mut this.update {
+                // A timeout which expired before being updated completes,
+                // and the deadline is checked again below.
+                if Pin::new(update).poll(cx).is_ready() {
+                    this.update = None;
+                }
+            }
+
+            let op = match &mut this.op {
+                Some(op) => op,
+                None => {
+                    let remaining = this.deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        this.elapsed = true;
+                        continue;
+                    }
+                    match Op::timeout(remaining) {
+                        Ok(op) => this.op.insert(op),
+                        Err(e) => {
+                            this.elapsed = !ready!(poll_room(cx, e));
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            match Pin::new(op).poll(cx) {
+                Poll::Ready(res) => {
+                    this.op = None;
+                    // The timeout elapses on the same clock as `Instant`.
+                    // One which expired before it was moved to a later
+                    // deadline, or was cancelled, is submitted anew. One
+                    // which failed otherwise completes the sleep.
+                    this.elapsed = match res {
+                        Err(e) if e.raw_os_error() != Some(libc::ECANCELED) => true,
+                        _ => Instant::now() >= this.deadline,
+                    };
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+// Waits for room to submit a timeout which failed to submit with `e`,
+// returning `true` to try again. Failures of the ring are reported instead,
+// returning `false`.
+fn poll_room(cx: &mut Context<'_>, e: io::Error) -> Poll<bool> {
+    let handle = CONTEXT
+        .with(|x| x.handle())
+        .expect("Not in a runtime context");
+
+    match SubmitError::from_io_error(&e) {
+        Some(SubmitError::InFlightLimit) => {
+            ready!(handle.poll_permit(cx));
+            // Give the slot to the timeout submitted next.
+            handle.release_permit();
+            Poll::Ready(true)
+        }
+        Some(SubmitError::QueueFull) => {
+            ready!(handle.poll_sq_space(cx));
+            Poll::Ready(true)
+        }
+        _ => {
+            handle.report_error(DriverError::Submit(e));
+            Poll::Ready(false)
+        }
+    }
+}
+
+impl std::fmt::Debug for Sleep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sleep")
+            .field("deadline", &self.deadline)
+            .field("elapsed", &self.elapsed)
+            .finish()
+    }
+}
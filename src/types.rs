@@ -1,4 +1,5 @@
 use std::fmt::{Debug, Display};
+use std::io;
 
 /// A specialized `Result` type for `io-uring` operations with buffers.
 ///
@@ -10,6 +11,7 @@ use std::fmt::{Debug, Display};
 ///
 /// ```no_run
 /// use tokio_uring::fs::File;
+/// use tokio_uring::Submit;
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
 ///     tokio_uring::start(async {
@@ -20,7 +22,7 @@ use std::fmt::{Debug, Display};
 ///         // Read some data, the buffer is passed by ownership and
 ///         // submitted to the kernel. When the operation completes,
 ///         // we get the buffer back.
-///         let (n, buf) = file.read_at(buf, 0).await?;
+///         let (n, buf) = file.read_at(buf, 0).submit().await?;
 ///
 ///         // Display the contents
 ///         println!("{:?}", &buf[..n]);
@@ -102,3 +104,86 @@ where
         }
     }
 }
+
+/// An error raised while submitting an operation to the kernel.
+///
+/// Submission errors mean the operation never ran, unlike the I/O errors
+/// reported in an operation's result. Callers can react to them separately,
+/// e.g. by applying backpressure when the queue is full.
+///
+/// Operations returning [`std::io::Error`] wrap submission errors in it, use
+/// [`SubmitError::from_io_error`] to recover them.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SubmitError {
+    /// The submission queue is full and the kernel cannot accept more entries
+    /// until pending completions have been processed.
+    ///
+    /// Only `try_submit` reports it, other submissions process completions
+    /// until the kernel accepts the entries.
+    QueueFull,
+
+    /// The kernel rejected the submission, e.g. with `ENOMEM` or `EBADF`.
+    Ring(io::Error),
+
+    /// The runtime already has as many operations in flight as it was
+    /// capped to with [`Builder::max_in_flight`](crate::Builder::max_in_flight).
+    /// Wait for an [`OpPermit`](crate::runtime::OpPermit) to submit once one
+    /// completes.
+    InFlightLimit,
+}
+
+impl SubmitError {
+    /// Returns the submission error carried by `e`, if `e` was raised while
+    /// submitting an operation.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_uring::fs::File;
+    /// use tokio_uring::SubmitError;
+    ///
+    /// tokio_uring::start(async {
+    ///     match File::open("hello.txt").await {
+    ///         Ok(file) => { /* ... */ }
+    ///         Err(e) => match SubmitError::from_io_error(&e) {
+    ///             Some(SubmitError::InFlightLimit) => { /* wait for a permit */ }
+    ///             Some(_) => { /* the ring failed */ }
+    ///             None => { /* the open itself failed */ }
+    ///         },
+    ///     }
+    /// });
+    /// ```
+    pub fn from_io_error(e: &io::Error) -> Option<&SubmitError> {
+        e.get_ref()?.downcast_ref()
+    }
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::QueueFull => f.write_str("submission queue is full"),
+            SubmitError::InFlightLimit => f.write_str("too many operations in flight"),
+            SubmitError::Ring(e) => write!(f, "failed to submit operation: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SubmitError::QueueFull | SubmitError::InFlightLimit => None,
+            SubmitError::Ring(e) => Some(e),
+        }
+    }
+}
+
+impl From<SubmitError> for io::Error {
+    fn from(e: SubmitError) -> io::Error {
+        let kind = match &e {
+            SubmitError::QueueFull | SubmitError::InFlightLimit => io::ErrorKind::WouldBlock,
+            SubmitError::Ring(e) => e.kind(),
+        };
+        io::Error::new(kind, e)
+    }
+}
@@ -0,0 +1,113 @@
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+
+use tokio_uring::io::DirectFd;
+use tokio_uring::Submit;
+
+#[test]
+fn read_and_write_through_slots() {
+    tokio_uring::builder().direct_descriptors(2).start(async {
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        let file = std::fs::File::options()
+            .read(true)
+            .write(true)
+            .open(tmp.path())
+            .unwrap();
+        let direct = DirectFd::register(&file).unwrap();
+        drop(file);
+
+        let (n, _) = direct
+            .write_at(b"hello".to_vec(), 0)
+            .submit()
+            .await
+            .unwrap();
+        assert_eq!(n, 5);
+        let (n, buf) = direct.read_at(vec![0; 8], 0).submit().await.unwrap();
+        assert_eq!(&buf[..n], b"hello");
+
+        let (mut a, b) = UnixStream::pair().unwrap();
+        let socket = DirectFd::register(&b).unwrap();
+        drop(b);
+        assert_ne!(socket.slot(), direct.slot());
+
+        a.write_all(b"ping").unwrap();
+        let (n, buf) = socket.read_at(vec![0; 8], 0).submit().await.unwrap();
+        assert_eq!(&buf[..n], b"ping");
+        socket.write_at(b"pong".to_vec(), 0).submit().await.unwrap();
+        let mut buf = [0; 4];
+        a.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"pong");
+
+        // The table is full until a slot is handed back.
+        let err = DirectFd::register(&a).err().unwrap();
+        assert_eq!(err.raw_os_error(), Some(libc::ENFILE));
+
+        let slot = socket.slot();
+        socket.close().await.unwrap();
+        let mut buf = [0; 1];
+        assert_eq!(a.read(&mut buf).unwrap(), 0);
+
+        let again = DirectFd::register(&a).unwrap();
+        assert_eq!(again.slot(), slot);
+        drop(again);
+        DirectFd::register(&a).unwrap();
+    });
+
+    tokio_uring::start(async {
+        let file = std::fs::File::open("/dev/null").unwrap();
+        assert!(DirectFd::register(&file).is_err());
+    });
+}
+
+#[test]
+fn install_and_replace_from_the_ring() {
+    tokio_uring::builder().direct_descriptors(1).start(async {
+        let (mut a, b) = UnixStream::pair().unwrap();
+        let direct = DirectFd::install(&b).await.unwrap();
+        drop(b);
+
+        direct.write_at(b"one".to_vec(), 0).submit().await.unwrap();
+        let mut buf = [0; 3];
+        a.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"one");
+
+        // Replacing the socket closes it.
+        let (mut c, d) = UnixStream::pair().unwrap();
+        direct.replace(&d).await.unwrap();
+        drop(d);
+        assert_eq!(a.read(&mut buf).unwrap(), 0);
+
+        direct.write_at(b"two".to_vec(), 0).submit().await.unwrap();
+        c.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"two");
+
+        // A failed install hands its slot back.
+        drop(direct);
+        let err = DirectFd::install(&i32::MAX).await.err().unwrap();
+        assert_eq!(err.raw_os_error(), Some(libc::EBADF));
+        DirectFd::install(&c).await.unwrap();
+    });
+}
+
+#[test]
+fn install_a_process_fd() {
+    use std::os::unix::io::FromRawFd;
+
+    tokio_uring::builder().direct_descriptors(1).start(async {
+        let (mut a, b) = UnixStream::pair().unwrap();
+        let direct = DirectFd::register(&b).unwrap();
+        drop(b);
+
+        let fd = direct.install_fd().await.unwrap();
+        let flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+        assert_eq!(flags & libc::FD_CLOEXEC, libc::FD_CLOEXEC);
+
+        // Both refer to the same socket.
+        let mut b = unsafe { UnixStream::from_raw_fd(fd) };
+        b.write_all(b"one").unwrap();
+        direct.write_at(b"two".to_vec(), 0).submit().await.unwrap();
+        let mut buf = [0; 6];
+        a.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"onetwo");
+    });
+}
@@ -125,6 +125,271 @@ fn completion_overflow() {
         });
 }
 
+#[test]
+fn completion_backpressure() {
+    use tokio::task::JoinSet;
+    use tokio_uring::runtime::CqOverflow;
+
+    tokio_uring::builder()
+        .entries(2)
+        .uring_builder(tokio_uring::uring_builder().setup_cqsize(4))
+        .cq_overflow(CqOverflow::Backpressure)
+        .start(async move {
+            let mut js = JoinSet::new();
+
+            for _ in 0..50 {
+                js.spawn_local(tokio_uring::no_op());
+            }
+
+            while let Some(res) = js.join_next().await {
+                res.unwrap().unwrap();
+            }
+
+            let stats = tokio_uring::runtime::ring_stats();
+            assert!(stats.nodrop);
+            assert_eq!(stats.cq_overflow, 0);
+            assert_eq!(stats.cq_dropped, 0);
+        });
+}
+
+#[test]
+fn submission_backpressure() {
+    use tokio::task::JoinSet;
+
+    tokio_uring::builder().entries(2).start(async move {
+        let mut js = JoinSet::new();
+
+        for _ in 0..64 {
+            let space = tokio_uring::runtime::sq_space_left().await;
+            assert!(space > 0 && space <= 2);
+            js.spawn_local(tokio_uring::no_op());
+            tokio::task::yield_now().await;
+        }
+
+        while let Some(res) = js.join_next().await {
+            res.unwrap().unwrap();
+        }
+
+        assert_eq!(tokio_uring::runtime::ring_stats().sq_full, 0);
+    });
+}
+
+#[test]
+fn manual_flush() {
+    tokio_uring::start(async {
+        let task = tokio_uring::spawn(tokio_uring::no_op());
+        tokio::task::yield_now().await;
+
+        assert_eq!(tokio_uring::runtime::flush().unwrap(), 1);
+        assert_eq!(tokio_uring::runtime::flush().unwrap(), 0);
+        task.await.unwrap().unwrap();
+    });
+}
+
+#[test]
+fn small_submit_batch() {
+    fn pending() -> usize {
+        tokio_uring::with_ring(|ring| unsafe { ring.submission_shared() }.len())
+    }
+
+    tokio_uring::builder().submit_batch(2).start(async {
+        let first = tokio_uring::spawn(tokio_uring::no_op());
+        tokio::task::yield_now().await;
+        assert_eq!(pending(), 1);
+
+        // The second operation completes the batch, submitting both.
+        let second = tokio_uring::spawn(tokio_uring::no_op());
+        tokio::task::yield_now().await;
+        assert_eq!(pending(), 0);
+
+        first.await.unwrap().unwrap();
+        second.await.unwrap().unwrap();
+    });
+}
+
+#[test]
+fn small_completion_budget() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use tokio::task::JoinSet;
+
+    tokio_uring::builder()
+        .entries(64)
+        .completion_budget(1)
+        // The ticker keeps the runtime busy, submit without waiting for it to
+        // run out of tasks.
+        .submit_batch(1)
+        .start(async move {
+            let done = Rc::new(Cell::new(false));
+            let ticks = Rc::new(Cell::new(0));
+
+            // Counts the times it gets to run while completions are processed.
+            let ticker = tokio_uring::spawn({
+                let (done, ticks) = (done.clone(), ticks.clone());
+                async move {
+                    while !done.get() {
+                        ticks.set(ticks.get() + 1);
+                        tokio::task::yield_now().await;
+                    }
+                }
+            });
+
+            let mut js = JoinSet::new();
+            for _ in 0..64 {
+                js.spawn_local(tokio_uring::no_op());
+            }
+            while let Some(res) = js.join_next().await {
+                res.unwrap().unwrap();
+            }
+
+            done.set(true);
+            ticker.await.unwrap();
+            assert!(ticks.get() > 1);
+        });
+}
+
+#[test]
+fn small_cqe_batch() {
+    use tokio::task::JoinSet;
+
+    tokio_uring::builder()
+        .entries(16)
+        .cqe_batch(1)
+        .cqe_repeek(true)
+        .start(async move {
+            let mut js = JoinSet::new();
+
+            for _ in 0..100 {
+                js.spawn_local(tokio_uring::no_op());
+            }
+
+            while let Some(res) = js.join_next().await {
+                res.unwrap().unwrap();
+            }
+        });
+}
+
+#[test]
+fn submit_errors() {
+    use std::io;
+    use tokio_uring::SubmitError;
+
+    // Submission errors survive the conversion to io::Error.
+    let e = io::Error::from(SubmitError::QueueFull);
+    assert_eq!(e.kind(), io::ErrorKind::WouldBlock);
+    assert!(matches!(
+        SubmitError::from_io_error(&e),
+        Some(SubmitError::QueueFull)
+    ));
+
+    let e = io::Error::from(SubmitError::Ring(io::Error::from_raw_os_error(
+        libc::ENOMEM,
+    )));
+    assert_eq!(e.kind(), io::ErrorKind::OutOfMemory);
+    assert!(matches!(
+        SubmitError::from_io_error(&e),
+        Some(SubmitError::Ring(_))
+    ));
+
+    // Completion errors are not submission errors.
+    let e = io::Error::from_raw_os_error(libc::ENOENT);
+    assert!(SubmitError::from_io_error(&e).is_none());
+
+    tokio_uring::start(async {
+        let tempfile = tempfile();
+        let file = File::open(tempfile.path()).await.unwrap();
+
+        let (n, _) = file
+            .read_at(vec![0; 16], 0)
+            .try_submit()
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(n, 0);
+    });
+}
+
+#[test]
+fn undersized_ring_is_reported() {
+    use std::sync::{Arc, Mutex};
+    use tokio_uring::runtime::DriverError;
+
+    let recommended = Arc::new(Mutex::new(Vec::new()));
+
+    tokio_uring::builder()
+        .entries(4)
+        .on_driver_error({
+            let recommended = recommended.clone();
+            move |e| {
+                if let DriverError::Undersized(stats) = e {
+                    recommended
+                        .lock()
+                        .unwrap()
+                        .push(stats.recommended_entries().unwrap());
+                }
+            }
+        })
+        .start(async {
+            let tempfile = tempfile();
+            let file = File::open(tempfile.path()).await.unwrap();
+
+            // More operations in flight than the submission queue holds.
+            let reads: Vec<_> = (0..16)
+                .map(|_| file.read_at(vec![0; 16], 0).submit())
+                .collect();
+            for read in reads {
+                read.await.unwrap();
+            }
+
+            let stats = tokio_uring::runtime::ring_stats();
+            assert_eq!(stats.sq_entries, 4);
+            assert!(stats.sq_full > 0);
+            assert!(stats.peak_in_flight >= 16);
+            assert_eq!(stats.recommended_entries(), Some(16));
+        });
+
+    // Reported as the recommendation grew, up to the final one.
+    let recommended = recommended.lock().unwrap();
+    assert_eq!(recommended.first(), Some(&8));
+    assert_eq!(recommended.last(), Some(&16));
+}
+
+#[test]
+fn registered_ring_fd() {
+    tokio_uring::start(async {
+        // Supported since Linux 5.18.
+        assert!(tokio_uring::runtime::ring_stats().registered_ring);
+
+        let tempfile = tempfile();
+        let file = File::create(tempfile.path()).await.unwrap();
+        let (n, _) = file.write_at(b"hello".to_vec(), 0).submit().await.unwrap();
+        assert_eq!(n, 5);
+
+        let file = File::open(tempfile.path()).await.unwrap();
+        let (n, buf) = file.read_at(vec![0; 5], 0).submit().await.unwrap();
+        assert_eq!(&buf[..n], b"hello");
+    });
+}
+
+#[test]
+fn cancel_dropped_ops() {
+    let mut fds = [0; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+
+    let (_, report) = tokio_uring::builder().start_with_report(async {
+        use std::os::unix::io::FromRawFd;
+
+        // Nothing is ever written to the pipe, the read is only cancelled.
+        let rx = unsafe { File::from_raw_fd(fds[0]) };
+        poll_once(rx.read_at(vec![0; 16], 0).submit()).await;
+
+        tokio_uring::no_op().await.unwrap();
+    });
+
+    assert_eq!(report.in_flight, 0);
+    unsafe { libc::close(fds[1]) };
+}
+
 fn tempfile() -> NamedTempFile {
     NamedTempFile::new().unwrap()
 }
@@ -0,0 +1,35 @@
+use std::io::{Read, Write};
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::UnixStream;
+
+use tokio_uring::io::EpollBridge;
+
+#[test]
+fn readiness_through_bridge() {
+    tokio_uring::start(async {
+        let (mut a, mut b) = UnixStream::pair().unwrap();
+        let mut bridge = EpollBridge::new().unwrap();
+        bridge.add(b.as_raw_fd(), libc::EPOLLIN as u32, 7).unwrap();
+
+        for round in 0..3u8 {
+            let writer = tokio_uring::spawn(async move {
+                tokio::task::yield_now().await;
+                a.write_all(&[round]).unwrap();
+                a
+            });
+
+            let events = bridge.wait().await.unwrap();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].token(), 7);
+            assert!(events[0].is_readable());
+
+            let mut buf = [0];
+            b.read_exact(&mut buf).unwrap();
+            assert_eq!(buf[0], round);
+
+            a = writer.await.unwrap();
+        }
+
+        bridge.delete(b.as_raw_fd()).unwrap();
+    });
+}
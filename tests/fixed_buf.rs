@@ -1,5 +1,7 @@
 use tokio_test::assert_err;
-use tokio_uring::buf::fixed::{FixedBufPool, FixedBufRegistry};
+use tokio_uring::buf::fixed::{
+    memlock_usage, FixedBufPool, FixedBufRegistry, NumaPolicy, RegionOptions, SharedFixedBufs,
+};
 use tokio_uring::buf::{BoundedBuf, BoundedBufMut};
 use tokio_uring::fs::File;
 
@@ -185,6 +187,136 @@ fn pool_next_as_concurrency_limit() {
     })
 }
 
+#[test]
+fn readv_fixed_spanning_buffers() {
+    tokio_uring::start(async {
+        let data = HELLO.repeat(3);
+
+        let mut tempfile = tempfile();
+        tempfile.write_all(&data).unwrap();
+
+        let file = File::open(tempfile.path()).await.unwrap();
+
+        let buffers = FixedBufRegistry::new([16, 16, 16].iter().map(|&n| Vec::with_capacity(n)));
+        buffers.register().unwrap();
+
+        let bufs = (0..3).map(|i| buffers.check_out(i).unwrap()).collect();
+
+        // The file ends in the last buffer.
+        let (n, bufs) = file.readv_fixed_at(bufs, 0).await.unwrap();
+        assert_eq!(n, data.len());
+        assert_eq!(bufs.len(), 3);
+
+        let read: Vec<u8> = bufs.iter().flat_map(|b| b[..].to_vec()).collect();
+        assert_eq!(&read[..n], &data[..]);
+        assert_eq!(bufs[2].bytes_init(), data.len() - 32);
+    });
+}
+
+#[test]
+fn readv_fixed_over_in_flight_cap() {
+    tokio_uring::builder().max_in_flight(1).start(async {
+        let mut tempfile = tempfile();
+        tempfile.write_all(HELLO).unwrap();
+
+        let file = File::open(tempfile.path()).await.unwrap();
+
+        let buffers = FixedBufRegistry::new([16, 16].iter().map(|&n| Vec::with_capacity(n)));
+        buffers.register().unwrap();
+
+        // The chain doesn't fit under the cap, none of it is submitted and
+        // the buffers come back.
+        let bufs = (0..2).map(|i| buffers.check_out(i).unwrap()).collect();
+        let err = file.readv_fixed_at(bufs, 0).await.unwrap_err();
+        assert!(matches!(
+            tokio_uring::SubmitError::from_io_error(&err.0),
+            Some(tokio_uring::SubmitError::InFlightLimit)
+        ));
+        assert_eq!(err.1.len(), 2);
+        drop(err);
+
+        // The runtime is still usable.
+        let bufs = vec![buffers.check_out(0).unwrap()];
+        let (n, bufs) = file.readv_fixed_at(bufs, 0).await.unwrap();
+        assert_eq!(n, HELLO.len());
+        assert_eq!(&bufs[0][..], HELLO);
+    });
+}
+
+#[test]
+fn shared_fixed_bufs_across_runtimes() {
+    let bufs = SharedFixedBufs::new(4, 4096);
+    let tempfile = tempfile();
+
+    // One thread writes through a buffer of the shared region.
+    let writer = {
+        let (bufs, path) = (bufs.clone(), tempfile.path().to_owned());
+        std::thread::spawn(move || {
+            tokio_uring::start(async move {
+                bufs.register().unwrap();
+
+                let mut buf = bufs.check_out(0).unwrap();
+                buf.put_slice(HELLO);
+
+                let file = File::create(&path).await.unwrap();
+                let (n, _) = file.write_fixed_at(buf, 0).await.unwrap();
+                assert_eq!(n, HELLO.len());
+
+                // The buffer is back in the region.
+                assert!(bufs.check_out(0).is_some());
+                bufs.unregister().unwrap();
+            })
+        })
+    };
+    writer.join().unwrap();
+
+    // Another runtime registers the same region and reads the data back.
+    tokio_uring::start(async {
+        bufs.register().unwrap();
+
+        let held = bufs.check_out(1).unwrap();
+        assert!(bufs.check_out(1).is_none());
+
+        // The written bytes are still in the buffer the writer checked in.
+        let buf = bufs.check_out(0).unwrap();
+        assert_eq!(&buf[..], HELLO);
+        drop(buf);
+
+        let file = File::open(tempfile.path()).await.unwrap();
+        let (n, buf) = file.read_fixed_at(held, 0).await.unwrap();
+        assert_eq!(&buf[..n], HELLO);
+    });
+}
+
+#[test]
+fn locked_region() {
+    let before = memlock_usage().unwrap();
+
+    let bufs = RegionOptions::new()
+        .mlock(true)
+        .numa(NumaPolicy::Local)
+        .allocate(4, 4096)
+        .unwrap();
+
+    // The locked pages are accounted against RLIMIT_MEMLOCK.
+    let after = memlock_usage().unwrap();
+    assert!(after.locked >= before.locked + 4 * 4096);
+
+    tokio_uring::start(async {
+        bufs.register().unwrap();
+
+        let tempfile = tempfile();
+        let mut buf = bufs.check_out(0).unwrap();
+        buf.put_slice(HELLO);
+
+        let file = File::create(tempfile.path()).await.unwrap();
+        let (n, _) = file.write_fixed_at(buf, 0).await.unwrap();
+        assert_eq!(n, HELLO.len());
+
+        bufs.unregister().unwrap();
+    });
+}
+
 fn tempfile() -> NamedTempFile {
     NamedTempFile::new().unwrap()
 }
@@ -4,6 +4,7 @@ mod future;
 
 use tokio_test::assert_ok;
 use tokio_uring::fs;
+use tokio_uring::Submit;
 
 use tempfile::tempdir;
 
@@ -28,3 +29,138 @@ fn basic_remove_dir() {
         assert!(std::fs::metadata(temp_dir.path()).is_err());
     });
 }
+
+#[test]
+fn remove_dir_all() {
+    tokio_uring::start(async {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().join("root");
+        let outside = temp_dir.path().join("outside");
+        std::fs::create_dir_all(root.join("a/b/c")).unwrap();
+        std::fs::create_dir(&outside).unwrap();
+        for i in 0..10 {
+            std::fs::write(root.join("a").join(i.to_string()), b"hello").unwrap();
+        }
+        std::fs::write(root.join("a/b/c/file"), b"hello").unwrap();
+        std::fs::write(outside.join("kept"), b"hello").unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("a/link")).unwrap();
+
+        assert_ok!(fs::remove_dir_all(&root).await);
+        assert!(!root.exists());
+        // The link was removed rather than followed.
+        assert!(outside.join("kept").exists());
+
+        let err = fs::remove_dir_all(&root).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    });
+}
+
+#[test]
+fn remove_dir_all_wide() {
+    let temp_dir = tempdir().unwrap();
+    let root = temp_dir.path().join("root");
+    for i in 0..600 {
+        let dir = root.join(i.to_string());
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("file"), b"hello").unwrap();
+    }
+
+    // Far fewer descriptors than directories are allowed.
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    assert_eq!(
+        unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) },
+        0
+    );
+    let lowered = libc::rlimit {
+        rlim_cur: 256,
+        ..limit
+    };
+    assert_eq!(unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &lowered) }, 0);
+
+    let res = tokio_uring::start(fs::remove_dir_all(&root));
+    assert_eq!(unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &limit) }, 0);
+
+    assert_ok!(res);
+    assert!(!root.exists());
+}
+
+#[test]
+fn read_dir() {
+    use futures_util::StreamExt;
+    use std::collections::BTreeMap;
+
+    tokio_uring::start(async {
+        let temp_dir = tempdir().unwrap();
+        // Enough entries to take several reads.
+        for i in 0..2000 {
+            std::fs::write(temp_dir.path().join(format!("file-{}", i)), b"").unwrap();
+        }
+        std::fs::create_dir(temp_dir.path().join("dir")).unwrap();
+        std::os::unix::fs::symlink("dir", temp_dir.path().join("link")).unwrap();
+
+        let mut entries = BTreeMap::new();
+        let mut read_dir = fs::read_dir(temp_dir.path()).await.unwrap();
+        while let Some(entry) = read_dir.next().await {
+            let entry = entry.unwrap();
+            assert_eq!(entry.path(), temp_dir.path().join(entry.file_name()));
+            let file_type = entry.file_type().await.unwrap();
+            entries.insert(entry.file_name(), (file_type, entry.ino()));
+        }
+        assert!(read_dir.next_entry().await.unwrap().is_none());
+
+        assert_eq!(entries.len(), 2002);
+        let (file_type, ino) = entries[std::ffi::OsStr::new("file-0")];
+        assert!(file_type.is_file());
+        let metadata = std::fs::metadata(temp_dir.path().join("file-0")).unwrap();
+        assert_eq!(ino, std::os::unix::fs::MetadataExt::ino(&metadata));
+        assert!(entries[std::ffi::OsStr::new("dir")].0.is_dir());
+        assert!(entries[std::ffi::OsStr::new("link")].0.is_symlink());
+
+        let err = fs::read_dir(temp_dir.path().join("file-0"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::ENOTDIR));
+    });
+}
+
+#[test]
+fn dir_relative_operations() {
+    tokio_uring::start(async {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("dir");
+        std::fs::create_dir(&path).unwrap();
+        let dir = fs::Dir::open(&path).await.unwrap();
+
+        // The directory is followed across renames.
+        let moved = temp_dir.path().join("moved");
+        std::fs::rename(&path, &moved).unwrap();
+
+        let file = dir
+            .open_at("file", fs::OpenOptions::new().write(true).create_new(true))
+            .await
+            .unwrap();
+        file.write_at(&b"hello"[..], 0).submit().await.unwrap();
+        file.close().await.unwrap();
+        assert_eq!(std::fs::read(moved.join("file")).unwrap(), b"hello");
+
+        assert_eq!(dir.statx_at("file").await.unwrap().stx_size, 5);
+
+        dir.mkdir_at("sub").await.unwrap();
+        let sub = fs::Dir::open(moved.join("sub")).await.unwrap();
+        dir.rename_at("file", &sub, "renamed").await.unwrap();
+        assert!(moved.join("sub/renamed").exists());
+
+        sub.unlink_at("renamed").await.unwrap();
+        dir.remove_dir_at("sub").await.unwrap();
+        assert_eq!(std::fs::read_dir(&moved).unwrap().count(), 0);
+
+        let err = dir.unlink_at("missing").await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+
+        sub.close().await.unwrap();
+        dir.close().await.unwrap();
+    });
+}
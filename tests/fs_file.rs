@@ -1,14 +1,14 @@
 use std::{
     io::prelude::*,
-    os::unix::io::{AsRawFd, FromRawFd, RawFd},
+    os::unix::io::{AsRawFd, RawFd},
+    path::Path,
+    time::{Duration, UNIX_EPOCH},
 };
 
-use libc;
-
 use tempfile::NamedTempFile;
 
 use tokio_uring::buf::{BoundedBuf, BoundedBufMut};
-use tokio_uring::fs::File;
+use tokio_uring::fs::{self, File, FileTimes, LockType, ReadAhead, StatxBuilder, SyncCoalescer};
 use tokio_uring::{buf::fixed::FixedBufRegistry, Submit};
 
 #[path = "../src/future.rs"]
@@ -89,7 +89,7 @@ fn vectored_write() {
         let buf2 = " world...".to_owned().into_bytes();
         let bufs = vec![buf1, buf2];
 
-        file.writev_at(bufs, 0).submit().await.0.unwrap();
+        file.writev_at(bufs, 0).submit().await.unwrap();
 
         let file = std::fs::read(tempfile.path()).unwrap();
         assert_eq!(file, HELLO);
@@ -145,7 +145,7 @@ fn explicit_close() {
 fn drop_open() {
     tokio_uring::start(async {
         let tempfile = tempfile();
-        let _ = File::create(tempfile.path());
+        drop(File::create(tempfile.path()));
 
         // Do something else
         let file = File::create(tempfile.path()).await.unwrap();
@@ -306,6 +306,901 @@ fn basic_fallocate() {
     });
 }
 
+#[test]
+fn open_with_flags_and_mode() {
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::io::AsRawFd;
+
+    tokio_uring::start(async {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("synced");
+
+        let file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .custom_flags(libc::O_SYNC | libc::O_RDONLY)
+            .open(&path)
+            .await
+            .unwrap();
+
+        // The access mode comes from the options, not the custom flags.
+        let flags = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_GETFL) };
+        assert_eq!(flags & libc::O_ACCMODE, libc::O_WRONLY);
+        assert_eq!(flags & libc::O_SYNC, libc::O_SYNC);
+        file.close().await.unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    });
+}
+
+#[test]
+fn open_beneath_a_directory() {
+    tokio_uring::start(async {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("inside"), HELLO).unwrap();
+        std::os::unix::fs::symlink("/etc", dir.path().join("escape")).unwrap();
+
+        let root = fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_DIRECTORY)
+            .open(dir.path())
+            .await
+            .unwrap();
+        let read = fs::OpenOptions::new().read(true).clone();
+
+        let file = read
+            .open_at2(&root, "inside", libc::RESOLVE_BENEATH)
+            .await
+            .unwrap();
+        read_hello(&file).await;
+
+        let err = read
+            .open_at2(&root, "../inside", libc::RESOLVE_BENEATH)
+            .await
+            .unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EXDEV));
+        let err = read
+            .open_at2(&root, "escape/hostname", libc::RESOLVE_NO_SYMLINKS)
+            .await
+            .unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::ELOOP));
+
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open_at2(&root, "created", libc::RESOLVE_BENEATH)
+            .await
+            .unwrap();
+        assert!(dir.path().join("created").exists());
+    });
+}
+
+#[test]
+fn create_anonymous_and_persist() {
+    tokio_uring::start(async {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("published");
+
+        let file = match File::create_anonymous(dir.path()).await {
+            Ok(file) => file,
+            // The filesystem of the temporary directory lacks O_TMPFILE.
+            Err(e) if e.raw_os_error() == Some(libc::EOPNOTSUPP) => return,
+            Err(e) => panic!("{}", e),
+        };
+        file.write_all_at(HELLO, 0).await.unwrap();
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        file.persist(&path).await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), HELLO);
+
+        let err = file.persist(&path).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn statx_relative_to_a_directory() {
+    tokio_uring::start(async {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("target"), HELLO).unwrap();
+
+        let dir_file = fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_DIRECTORY)
+            .open(dir.path())
+            .await
+            .unwrap();
+
+        let statx = fs::statx_at(&dir_file, "target").await.unwrap();
+        assert_eq!(statx.stx_size, HELLO.len() as u64);
+
+        let err = fs::statx_at(&dir_file, "missing").await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        dir_file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn direct_io_alignment() {
+    tokio_uring::start(async {
+        let tempfile = tempfile();
+        let file = File::open(tempfile.path()).await.unwrap();
+
+        // Filesystems which don't report the alignment return `None`.
+        if let Some((mem_align, offset_align)) = file.dio_alignment().await.unwrap() {
+            assert!(mem_align.is_power_of_two());
+            assert!(offset_align.is_power_of_two());
+        }
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn symlink() {
+    tokio_uring::start(async {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+        let link = dir.path().join("link");
+        std::fs::write(&target, HELLO).unwrap();
+
+        fs::symlink("target", &link).await.unwrap();
+        assert_eq!(std::fs::read_link(&link).unwrap(), Path::new("target"));
+        assert_eq!(std::fs::read(&link).unwrap(), HELLO);
+
+        let err = fs::symlink(&target, &link).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+    });
+}
+
+#[test]
+fn hard_link() {
+    use std::os::unix::fs::MetadataExt;
+
+    tokio_uring::start(async {
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("original");
+        let link = dir.path().join("link");
+        std::fs::write(&original, HELLO).unwrap();
+
+        fs::hard_link(&original, &link).await.unwrap();
+        let metadata = std::fs::metadata(&link).unwrap();
+        assert_eq!(metadata.ino(), std::fs::metadata(&original).unwrap().ino());
+        assert_eq!(metadata.nlink(), 2);
+
+        let err = fs::hard_link(&original, &link).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+    });
+}
+
+#[test]
+fn rename_with_flags() {
+    tokio_uring::start(async {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a");
+        let b = dir.path().join("b");
+        std::fs::write(&a, b"a").unwrap();
+        std::fs::write(&b, b"b").unwrap();
+
+        let err = fs::rename_with(&a, &b, libc::RENAME_NOREPLACE)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read(&b).unwrap(), b"b");
+
+        fs::rename_with(&a, &b, libc::RENAME_EXCHANGE)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&a).unwrap(), b"b");
+        assert_eq!(std::fs::read(&b).unwrap(), b"a");
+
+        let c = dir.path().join("c");
+        fs::rename_with(&a, &c, libc::RENAME_NOREPLACE)
+            .await
+            .unwrap();
+        assert!(!a.exists());
+        assert_eq!(std::fs::read(&c).unwrap(), b"b");
+    });
+}
+
+#[test]
+fn truncate() {
+    tokio_uring::start(async {
+        let mut tempfile = tempfile();
+        tempfile.write_all(HELLO).unwrap();
+
+        let file = fs::OpenOptions::new()
+            .write(true)
+            .open(tempfile.path())
+            .await
+            .unwrap();
+        file.truncate(5).await.unwrap();
+        assert_eq!(std::fs::read(tempfile.path()).unwrap(), &HELLO[..5]);
+
+        file.truncate(8).await.unwrap();
+        assert_eq!(std::fs::read(tempfile.path()).unwrap(), b"hello\0\0\0");
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn sync_range() {
+    tokio_uring::start(async {
+        let tempfile = tempfile();
+        let file = File::create(tempfile.path()).await.unwrap();
+        file.write_at(HELLO, 0).submit().await.unwrap();
+
+        let flags = libc::SYNC_FILE_RANGE_WAIT_BEFORE
+            | libc::SYNC_FILE_RANGE_WRITE
+            | libc::SYNC_FILE_RANGE_WAIT_AFTER;
+        file.sync_range(0, HELLO.len() as u32, flags).await.unwrap();
+        file.sync_range(0, 0, libc::SYNC_FILE_RANGE_WRITE)
+            .await
+            .unwrap();
+
+        let err = file.sync_range(0, 0, !0).await.unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn fadvise() {
+    tokio_uring::start(async {
+        let mut tempfile = tempfile();
+        tempfile.write_all(HELLO).unwrap();
+
+        let file = File::open(tempfile.path()).await.unwrap();
+        file.fadvise(0, 0, libc::POSIX_FADV_SEQUENTIAL)
+            .await
+            .unwrap();
+        file.fadvise(0, HELLO.len() as u32, libc::POSIX_FADV_WILLNEED)
+            .await
+            .unwrap();
+        read_hello(&file).await;
+
+        let err = file.fadvise(0, 0, -1).await.unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn punch_hole_and_zero_range() {
+    use std::os::unix::fs::MetadataExt;
+
+    tokio_uring::start(async {
+        let tempfile = tempfile();
+        let len = 64 * 1024;
+        std::fs::write(tempfile.path(), vec![1; len]).unwrap();
+
+        let file = fs::OpenOptions::new()
+            .write(true)
+            .open(tempfile.path())
+            .await
+            .unwrap();
+        let blocks = std::fs::metadata(tempfile.path()).unwrap().blocks();
+
+        file.punch_hole(0, 32 * 1024).await.unwrap();
+        let metadata = std::fs::metadata(tempfile.path()).unwrap();
+        assert_eq!(metadata.len(), len as u64);
+        assert!(metadata.blocks() < blocks);
+
+        file.zero_range(48 * 1024, 32 * 1024).await.unwrap();
+        let contents = std::fs::read(tempfile.path()).unwrap();
+        assert_eq!(contents.len(), 80 * 1024);
+        assert!(contents[..32 * 1024].iter().all(|&b| b == 0));
+        assert!(contents[32 * 1024..48 * 1024].iter().all(|&b| b == 1));
+        assert!(contents[48 * 1024..].iter().all(|&b| b == 0));
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn extended_attributes() {
+    tokio_uring::start(async {
+        let tempfile = tempfile();
+        let file = File::open(tempfile.path()).await.unwrap();
+
+        match file.set_xattr("user.mime_type", b"text/plain").await {
+            Ok(()) => {}
+            // The filesystem of the temporary file lacks user attributes.
+            Err(e) if e.raw_os_error() == Some(libc::EOPNOTSUPP) => return,
+            Err(e) => panic!("{}", e),
+        }
+        assert_eq!(
+            file.get_xattr("user.mime_type").await.unwrap().unwrap(),
+            b"text/plain"
+        );
+        assert!(file.get_xattr("user.missing").await.unwrap().is_none());
+
+        fs::setxattr(tempfile.path(), "user.empty", b"")
+            .await
+            .unwrap();
+        let big = vec![7; 3000];
+        fs::setxattr(tempfile.path(), "user.big", &big)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::getxattr(tempfile.path(), "user.empty").await.unwrap(),
+            Some(Vec::new())
+        );
+        assert_eq!(
+            fs::getxattr(tempfile.path(), "user.big").await.unwrap(),
+            Some(big)
+        );
+
+        let mut names = fs::listxattr(tempfile.path()).await.unwrap();
+        names.sort();
+        assert_eq!(names, ["user.big", "user.empty", "user.mime_type"]);
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn permissions_and_owner() {
+    use std::fs::Permissions;
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    tokio_uring::start(async {
+        let tempfile = tempfile();
+
+        fs::set_permissions(tempfile.path(), Permissions::from_mode(0o640))
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::metadata(tempfile.path()).unwrap().mode() & 0o777,
+            0o640
+        );
+
+        let file = File::open(tempfile.path()).await.unwrap();
+        file.set_permissions(Permissions::from_mode(0o600))
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::metadata(tempfile.path()).unwrap().mode() & 0o777,
+            0o600
+        );
+
+        // Setting the current owner and group is allowed without privileges.
+        let metadata = std::fs::metadata(tempfile.path()).unwrap();
+        file.set_owner(Some(metadata.uid()), Some(metadata.gid()))
+            .await
+            .unwrap();
+        file.set_owner(None, None).await.unwrap();
+        let after = std::fs::metadata(tempfile.path()).unwrap();
+        assert_eq!((after.uid(), after.gid()), (metadata.uid(), metadata.gid()));
+
+        let err = fs::set_permissions("/does/not/exist", Permissions::from_mode(0o600))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn try_clone() {
+    tokio_uring::start(async {
+        let mut tempfile = tempfile();
+        tempfile.write_all(HELLO).unwrap();
+
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .append(true)
+            .open(tempfile.path())
+            .await
+            .unwrap();
+        let clone = file.try_clone().unwrap();
+        assert_ne!(file.as_raw_fd(), clone.as_raw_fd());
+        file.close().await.unwrap();
+
+        // The clone outlives the original, and shares its O_APPEND flag.
+        clone.write_at(&b"!"[..], 0).submit().await.unwrap();
+        let (n, buf) = clone.read_at(vec![0; 1024], 0).submit().await.unwrap();
+        assert_eq!(&buf[..n - 1], HELLO);
+        assert_eq!(&buf[n - 1..n], b"!");
+        clone.close().await.unwrap();
+    });
+}
+
+#[test]
+fn read_to_end_at() {
+    tokio_uring::start(async {
+        let mut tempfile = tempfile();
+        let data: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+        tempfile.write_all(&data).unwrap();
+
+        let file = File::open(tempfile.path()).await.unwrap();
+        assert_eq!(file.read_to_end_at(0).await.unwrap(), data);
+        assert_eq!(file.read_to_end_at(9_000).await.unwrap(), &data[9_000..]);
+        assert!(file.read_to_end_at(20_000).await.unwrap().is_empty());
+        file.close().await.unwrap();
+
+        // Files in /proc report no size.
+        let file = File::open("/proc/self/status").await.unwrap();
+        let status = file.read_to_end_at(0).await.unwrap();
+        assert!(status.starts_with(b"Name:"));
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn read_and_write_whole_files() {
+    tokio_uring::start(async {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        fs::write(&path, "name = \"tokio-uring\"\n").await.unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).await.unwrap(),
+            "name = \"tokio-uring\"\n"
+        );
+
+        // Writing truncates the previous contents.
+        fs::write(&path, vec![0xff, 0xfe]).await.unwrap();
+        assert_eq!(fs::read(&path).await.unwrap(), [0xff, 0xfe]);
+        let err = fs::read_to_string(&path).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        let err = fs::read(dir.path().join("missing")).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    });
+}
+
+#[test]
+fn copy() {
+    use std::os::unix::fs::PermissionsExt;
+
+    tokio_uring::start(async {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("from");
+        let to = dir.path().join("to");
+        // More than the pipe holds at once, and not a multiple of its size.
+        let data: Vec<u8> = (0..3_000_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&from, &data).unwrap();
+        std::fs::set_permissions(&from, std::fs::Permissions::from_mode(0o640)).unwrap();
+        std::fs::write(&to, vec![1; 4_000_000]).unwrap();
+
+        assert_eq!(fs::copy(&from, &to).await.unwrap(), data.len() as u64);
+        assert_eq!(std::fs::read(&to).unwrap(), data);
+        let mode = std::fs::metadata(&to).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+
+        let empty = dir.path().join("empty");
+        std::fs::write(&empty, b"").unwrap();
+        assert_eq!(fs::copy(&empty, &to).await.unwrap(), 0);
+        assert!(std::fs::read(&to).unwrap().is_empty());
+
+        let err = fs::copy(dir.path(), &to).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    });
+}
+
+#[test]
+fn async_read_and_write() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    tokio_uring::start(async {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("from");
+        let to = dir.path().join("to");
+        // Spans several staging buffers.
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&from, &data).unwrap();
+
+        let mut reader = File::open(&from).await.unwrap().into_io();
+        let mut writer = File::create(&to).await.unwrap().into_io();
+        let n = tokio::io::copy(&mut reader, &mut writer).await.unwrap();
+        assert_eq!(n, data.len() as u64);
+        writer.flush().await.unwrap();
+        assert_eq!(writer.position(), data.len() as u64);
+        assert_eq!(std::fs::read(&to).unwrap(), data);
+        reader.into_inner().close().await.unwrap();
+        writer.into_inner().close().await.unwrap();
+
+        // Writing after a read writes at the cursor, not after the staged data.
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&to)
+            .await
+            .unwrap();
+        let mut io = file.into_io();
+        let mut head = [0; 10];
+        io.read_exact(&mut head).await.unwrap();
+        assert_eq!(&head[..], &data[..10]);
+        io.write_all(b"hello").await.unwrap();
+        io.flush().await.unwrap();
+        assert_eq!(io.position(), 15);
+        let mut rest = Vec::new();
+        io.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(rest, &data[15..]);
+        io.into_inner().close().await.unwrap();
+
+        let contents = std::fs::read(&to).unwrap();
+        assert_eq!(&contents[10..15], b"hello");
+    });
+}
+
+#[test]
+fn buffered_reads_and_writes() {
+    use tokio_uring::fs::{BufReader, BufWriter};
+
+    tokio_uring::start(async {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lines.txt");
+
+        // A small buffer, so that lines straddle writes and reads.
+        let mut writer = BufWriter::with_capacity(16, File::create(&path).await.unwrap());
+        for i in 0..100 {
+            writer
+                .write_all(format!("line {}\n", i).as_bytes())
+                .await
+                .unwrap();
+        }
+        writer.write_all(b"no newline").await.unwrap();
+        assert!(writer.buffer().len() <= 16);
+        let expected = std::fs::metadata(&path).unwrap().len() + writer.buffer().len() as u64;
+        assert_eq!(writer.position(), expected);
+        let file = writer.into_inner().await.unwrap();
+        file.close().await.unwrap();
+
+        let mut reader = BufReader::with_capacity(16, File::open(&path).await.unwrap());
+        let mut line = String::new();
+        for i in 0..100 {
+            line.clear();
+            reader.read_line(&mut line).await.unwrap();
+            assert_eq!(line, format!("line {}\n", i));
+        }
+        let mut head = [0; 3];
+        reader.read_exact(&mut head).await.unwrap();
+        assert_eq!(&head, b"no ");
+        line.clear();
+        assert_eq!(reader.read_line(&mut line).await.unwrap(), 7);
+        assert_eq!(line, "newline");
+        assert_eq!(reader.read_line(&mut line).await.unwrap(), 0);
+        assert_eq!(reader.position(), std::fs::metadata(&path).unwrap().len());
+
+        let err = reader.read_exact(&mut head).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+        reader.into_inner().close().await.unwrap();
+    });
+}
+
+#[test]
+fn read_stream() {
+    use futures_util::StreamExt;
+
+    tokio_uring::start(async {
+        let mut tempfile = tempfile();
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        tempfile.write_all(&data).unwrap();
+
+        let file = File::open(tempfile.path()).await.unwrap();
+        let chunks: Vec<Vec<u8>> = file
+            .read_stream(1024)
+            .depth(3)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), 10);
+        assert!(chunks[..9].iter().all(|chunk| chunk.len() == 1024));
+        assert_eq!(chunks.concat(), data);
+
+        // A file of whole chunks ends with an empty read.
+        let chunks: Vec<_> = file.read_stream(5_000).collect().await;
+        assert_eq!(chunks.len(), 2);
+        file.close().await.unwrap();
+
+        let empty = NamedTempFile::new().unwrap();
+        let empty = File::open(empty.path()).await.unwrap();
+        assert!(empty.read_stream(1024).next().await.is_none());
+        empty.close().await.unwrap();
+    });
+}
+
+#[test]
+fn append_and_write_at_current() {
+    tokio_uring::start(async {
+        let mut tempfile = tempfile();
+        tempfile.write_all(HELLO).unwrap();
+
+        let log = fs::OpenOptions::new()
+            .append(true)
+            .open(tempfile.path())
+            .await
+            .unwrap();
+        log.append(&b"one\n"[..]).await.unwrap();
+        log.append(vec![b't', b'w', b'o']).await.unwrap();
+        log.close().await.unwrap();
+        let mut expected = HELLO.to_vec();
+        expected.extend_from_slice(b"one\ntwo");
+        assert_eq!(std::fs::read(tempfile.path()).unwrap(), expected);
+
+        // Without O_APPEND, writes start at the file position and advance it.
+        let file = fs::OpenOptions::new()
+            .write(true)
+            .open(tempfile.path())
+            .await
+            .unwrap();
+        file.write_at_current(&b"HE"[..]).submit().await.unwrap();
+        file.write_at_current(&b"LLO"[..]).submit().await.unwrap();
+        file.close().await.unwrap();
+        expected[..5].copy_from_slice(b"HELLO");
+        assert_eq!(std::fs::read(tempfile.path()).unwrap(), expected);
+    });
+}
+
+#[test]
+fn rw_flags() {
+    tokio_uring::start(async {
+        let tempfile = tempfile();
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(tempfile.path())
+            .await
+            .unwrap();
+
+        file.write_at(HELLO, 0).dsync().submit().await.unwrap();
+        file.write_at(&b"!"[..], 0).append().submit().await.unwrap();
+
+        // The data just written is in the page cache.
+        let (n, buf) = file
+            .read_at(vec![0; 64], 0)
+            .nowait()
+            .submit()
+            .await
+            .unwrap();
+        assert_eq!(&buf[..n - 1], HELLO);
+        assert_eq!(&buf[n - 1..n], b"!");
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn direct_file_alignment() {
+    use tokio_uring::fs::DirectFile;
+
+    tokio_uring::start(async {
+        let tempfile = tempfile();
+        let mut options = fs::OpenOptions::new();
+        options.read(true).write(true);
+        let file = match DirectFile::open(tempfile.path(), &options).await {
+            Ok(file) => file,
+            // The filesystem of the temporary file lacks direct I/O.
+            Err(e) if e.raw_os_error() == Some(libc::EINVAL) => return,
+            Err(e) => panic!("{}", e),
+        };
+        let align = file.offset_alignment();
+
+        let mut buf = file.alloc_buf(100);
+        assert_eq!(buf.capacity(), align);
+        assert_eq!(buf.as_ptr() as usize % file.memory_alignment(), 0);
+        buf.extend_from_slice(HELLO);
+
+        // Unpadded data, and unaligned offsets, are rejected up front.
+        let tokio_uring::Error(e, mut buf) = file.write_at(buf, 0).await.unwrap_err();
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(e.to_string().contains("length"), "{}", e);
+        buf.zero_pad();
+        let tokio_uring::Error(e, buf) = file.write_at(buf, 1).await.unwrap_err();
+        assert!(e.to_string().contains("offset"), "{}", e);
+
+        let (n, _) = file.write_at(buf, 0).await.unwrap();
+        assert_eq!(n, align);
+
+        let (n, buf) = file.read_at(file.alloc_buf(align), 0).await.unwrap();
+        assert_eq!(n, align);
+        assert_eq!(&buf[..HELLO.len()], HELLO);
+
+        // A buffer which isn't aligned, even if its length is.
+        let unaligned = vec![0u8; align + 1];
+        let unaligned = unaligned.slice(1..);
+        match file.read_at(unaligned, 0).await {
+            Err(tokio_uring::Error(e, _)) => assert!(e.to_string().contains("address"), "{}", e),
+            Ok(_) => panic!("an unaligned buffer was read into"),
+        }
+
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn nvme_device_rejects_other_files() {
+    tokio_uring::builder().sqe128().cqe32().start(async {
+        let tempfile = tempfile();
+        let options = fs::OpenOptions::new().read(true).clone();
+
+        let err = fs::NvmeDevice::open(tempfile.path(), &options)
+            .await
+            .unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::ENOTTY));
+
+        let file = File::open(tempfile.path()).await.unwrap();
+        let (err, file) = fs::NvmeDevice::from_file(file).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::ENOTTY));
+        file.close().await.unwrap();
+
+        let cmd = fs::NvmeCommand::read(1, 1 << 32, 8);
+        assert!(!cmd.is_admin());
+        assert_eq!(cmd, fs::NvmeCommand::io(0x02).nsid(1).cdw11(1).cdw12(7));
+        assert!(fs::NvmeCommand::identify_controller().is_admin());
+    });
+}
+
+#[test]
+fn statx_builder_flags() {
+    tokio_uring::start(async {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+        let link = dir.path().join("link");
+        std::fs::write(&target, HELLO).unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let statx = StatxBuilder::new()
+            .mask(libc::STATX_SIZE | libc::STATX_TYPE)
+            .dont_sync(true)
+            .pathname(&link)
+            .unwrap()
+            .statx()
+            .await
+            .unwrap();
+        assert_ne!(statx.stx_mask & libc::STATX_SIZE, 0);
+        assert_eq!(statx.stx_size, HELLO.len() as u64);
+
+        let statx = StatxBuilder::new()
+            .mask(libc::STATX_TYPE)
+            .symlink_nofollow(true)
+            .pathname(&link)
+            .unwrap()
+            .statx()
+            .await
+            .unwrap();
+        assert_eq!(u32::from(statx.stx_mode) & libc::S_IFMT, libc::S_IFLNK);
+    });
+}
+
+#[test]
+fn metadata_and_times() {
+    use std::os::unix::fs::MetadataExt;
+
+    tokio_uring::start(async {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+        let link = dir.path().join("link");
+        std::fs::write(&target, HELLO).unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let metadata = fs::metadata(&link).await.unwrap();
+        assert!(metadata.is_file());
+        assert_eq!(metadata.len(), HELLO.len() as u64);
+
+        let std_metadata = std::fs::metadata(&target).unwrap();
+        assert_eq!(metadata.mode(), std_metadata.mode());
+        assert_eq!(metadata.uid(), std_metadata.uid());
+        assert_eq!(metadata.gid(), std_metadata.gid());
+
+        let metadata = fs::symlink_metadata(&link).await.unwrap();
+        assert!(metadata.is_symlink());
+
+        let metadata = fs::metadata(dir.path()).await.unwrap();
+        assert!(metadata.is_dir());
+
+        let file = File::open(&target).await.unwrap();
+        let accessed = UNIX_EPOCH + Duration::new(1_000_000_000, 500);
+        let modified = UNIX_EPOCH - Duration::new(100, 250);
+        file.set_times(
+            FileTimes::new()
+                .set_accessed(accessed)
+                .set_modified(modified),
+        )
+        .unwrap();
+
+        let metadata = file.metadata().await.unwrap();
+        assert_eq!(metadata.accessed().unwrap(), accessed);
+        assert_eq!(metadata.modified().unwrap(), modified);
+
+        // Setting only one timestamp leaves the other one alone.
+        file.set_modified(accessed).unwrap();
+        let metadata = file.metadata().await.unwrap();
+        assert_eq!(metadata.accessed().unwrap(), accessed);
+        assert_eq!(metadata.modified().unwrap(), accessed);
+
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn range_locks() {
+    tokio_uring::start(async {
+        let tempfile = tempfile();
+        let mut options = fs::OpenOptions::new();
+        options.read(true).write(true);
+        let a = options.open(tempfile.path()).await.unwrap();
+        let b = options.open(tempfile.path()).await.unwrap();
+
+        a.lock_range(0, 10, LockType::Exclusive).await.unwrap();
+        assert!(!b.try_lock_range(5, 10, LockType::Shared).unwrap());
+        assert!(b.try_lock_range(10, 10, LockType::Exclusive).unwrap());
+
+        // Wait for the lock held through `a` to be released.
+        let waiter = tokio_uring::spawn(async move {
+            b.lock_range(0, 10, LockType::Shared).await.unwrap();
+            b
+        });
+        tokio_uring::no_op().await.unwrap();
+        a.unlock_range(0, 0).unwrap();
+
+        let b = waiter.await.unwrap();
+        assert!(!a.try_lock_range(0, 1, LockType::Exclusive).unwrap());
+        assert!(a.try_lock_range(0, 1, LockType::Shared).unwrap());
+
+        a.close().await.unwrap();
+        b.close().await.unwrap();
+    });
+}
+
+#[test]
+fn whole_file_locks() {
+    tokio_uring::start(async {
+        let tempfile = tempfile();
+        let mut options = fs::OpenOptions::new();
+        options.read(true).write(true);
+        let a = options.open(tempfile.path()).await.unwrap();
+        let b = options.open(tempfile.path()).await.unwrap();
+
+        a.lock_shared().await.unwrap();
+        assert!(b.try_lock_shared().unwrap());
+        assert!(!b.try_lock().unwrap());
+        b.unlock().unwrap();
+
+        a.lock_exclusive().await.unwrap();
+        assert!(!b.try_lock_shared().unwrap());
+
+        // Wait for the lock held through `a` to be released.
+        let waiter = tokio_uring::spawn(async move {
+            b.lock_exclusive().await.unwrap();
+            b
+        });
+        tokio_uring::no_op().await.unwrap();
+        a.unlock().unwrap();
+
+        let b = waiter.await.unwrap();
+        assert!(!a.try_lock_shared().unwrap());
+        b.unlock().unwrap();
+        assert!(a.try_lock().unwrap());
+
+        a.close().await.unwrap();
+        b.close().await.unwrap();
+    });
+}
+
+#[test]
+fn close_cancels_in_flight_ops() {
+    tokio_uring::start(async {
+        use std::os::unix::io::FromRawFd;
+
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let rx = unsafe { File::from_raw_fd(fds[0]) };
+
+        // Nothing is ever written to the pipe.
+        let read = rx.read_at(vec![0; 16], 0).submit();
+        let reader = tokio_uring::spawn(read);
+        tokio_uring::no_op().await.unwrap();
+
+        rx.close().await.unwrap();
+        let err = reader.await.unwrap().unwrap_err();
+        assert_eq!(err.0.raw_os_error(), Some(libc::ECANCELED));
+
+        unsafe { libc::close(fds[1]) };
+    });
+}
+
 #[test]
 fn read_linked() {
     tokio_uring::start(async {
@@ -325,10 +1220,10 @@ fn read_linked() {
         let (res1, future2) = future1.await;
         let res2 = future2.await;
 
-        res1.0.unwrap();
-        res2.0.unwrap();
+        let (_, buf1) = res1.unwrap();
+        let (_, buf2) = res2.unwrap();
 
-        assert_eq!([HELLO, HELLO].concat(), [res1.1, res2.1].concat());
+        assert_eq!([HELLO, HELLO].concat(), [buf1, buf2].concat());
     });
 }
 
@@ -346,14 +1241,116 @@ fn write_linked() {
         let (res1, future2) = future1.await;
         let res2 = future2.await;
 
-        res1.0.unwrap();
-        res2.0.unwrap();
+        res1.unwrap();
+        res2.unwrap();
+
+        let file = std::fs::read(tempfile.path()).unwrap();
+        assert_eq!(file, [HELLO, HELLO].concat());
+    });
+}
+
+#[test]
+fn write_linked_skip_success() {
+    tokio_uring::start(async {
+        let tempfile = tempfile();
+        let file = File::create(tempfile.path()).await.unwrap();
+
+        let write1 = file.write_at(HELLO, 0).skip_success();
+        let write2 = file.write_at(HELLO, HELLO.len() as u64);
+
+        let (res1, future2) = write1.link(write2).submit().await;
+        let (n, _) = res1.unwrap();
+        assert_eq!(n, HELLO.len());
+        let (n, _) = future2.await.unwrap();
+        assert_eq!(n, HELLO.len());
+
+        // Failures are still reported, cancelling the rest of the chain.
+        let read = file.read_at(vec![0; 8], 0).skip_success();
+        let write = file.write_at(HELLO, 0);
+        let (res, future) = read.link(write).submit().await;
+        assert_eq!(res.unwrap_err().0.raw_os_error(), Some(libc::EBADF));
+        let err = future.await.unwrap_err();
+        assert_eq!(err.0.raw_os_error(), Some(libc::ECANCELED));
+
+        // Ending its chain, the operation completes as usual.
+        let (n, _) = file
+            .write_at(HELLO, 0)
+            .skip_success()
+            .submit()
+            .await
+            .unwrap();
+        assert_eq!(n, HELLO.len());
 
         let file = std::fs::read(tempfile.path()).unwrap();
         assert_eq!(file, [HELLO, HELLO].concat());
     });
 }
 
+#[test]
+fn read_ahead_sequential() {
+    tokio_uring::start(async {
+        let data = HELLO.repeat(1000);
+
+        let mut tempfile = tempfile();
+        tempfile.write_all(&data).unwrap();
+
+        let file = File::open(tempfile.path()).await.unwrap();
+        let mut reader = ReadAhead::new(file, 1024, 4);
+
+        let mut out = Vec::new();
+        let mut pos = 0;
+        loop {
+            let (n, buf) = reader.read_at(vec![0; 100], pos).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            if pos > 200 && pos < data.len() as u64 - 4096 {
+                assert!(reader.prefetched() > 0);
+            }
+            out.extend_from_slice(&buf[..n]);
+            pos += n as u64;
+        }
+        assert_eq!(out, data);
+
+        // A random read drops the prefetched chunks.
+        let (n, buf) = reader.read_at(vec![0; 5], 14).await.unwrap();
+        assert_eq!(&buf[..n], &HELLO[..5]);
+        assert_eq!(reader.prefetched(), 0);
+    });
+}
+
+#[test]
+fn coalesced_syncs() {
+    tokio_uring::start(async {
+        let tempfile = tempfile();
+        let file = File::create(tempfile.path()).await.unwrap();
+        let syncer = SyncCoalescer::new(&file);
+
+        // The first caller starts a sync, the others share the next one.
+        let syncs = (0..10).map(|i| {
+            let syncer = syncer.clone();
+            tokio_uring::spawn(async move {
+                if i % 2 == 0 {
+                    syncer.sync_all().await
+                } else {
+                    syncer.sync_data().await
+                }
+            })
+        });
+        for sync in syncs.collect::<Vec<_>>() {
+            sync.await.unwrap().unwrap();
+        }
+        assert_eq!(syncer.syncs(), 2);
+
+        syncer.sync_data().await.unwrap();
+        assert_eq!(syncer.syncs(), 3);
+
+        // Closing waits for the coalescer's reference to the file.
+        drop(syncer);
+        file.close().await.unwrap();
+    });
+}
+
 fn tempfile() -> NamedTempFile {
     NamedTempFile::new().unwrap()
 }
@@ -374,13 +1371,13 @@ async fn poll_once(future: impl std::future::Future) {
 }
 
 fn assert_invalid_fd(fd: RawFd) {
-    use std::fs::File;
-
-    let mut f = unsafe { File::from_raw_fd(fd) };
-    let mut buf = vec![];
-
-    match f.read_to_end(&mut buf) {
-        Err(ref e) if e.raw_os_error() == Some(libc::EBADF) => {}
-        res => panic!("assert_invalid_fd finds for fd {:?}, res = {:?}", fd, res),
+    // Probe the descriptor without taking ownership of it; dropping an owned
+    // `std::fs::File` over a closed descriptor aborts the process.
+    let res = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+    let err = std::io::Error::last_os_error();
+
+    match res {
+        -1 if err.raw_os_error() == Some(libc::EBADF) => {}
+        _ => panic!("assert_invalid_fd finds for fd {:?}, res = {:?}", fd, res),
     }
 }
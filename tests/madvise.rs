@@ -0,0 +1,45 @@
+use std::ptr;
+
+#[test]
+fn discard_anonymous_memory() {
+    tokio_uring::start(async {
+        let page = 4096;
+        let len = 4 * page;
+        let addr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        assert_ne!(addr, libc::MAP_FAILED);
+        let addr = addr.cast::<u8>();
+        unsafe { ptr::write_bytes(addr, 0xaa, len) };
+
+        unsafe { tokio_uring::io::madvise(addr, len as u32, libc::MADV_WILLNEED) }
+            .await
+            .unwrap();
+        assert_eq!(unsafe { *addr }, 0xaa);
+
+        // The pages of the second half are freed, and read as zeroes.
+        unsafe {
+            tokio_uring::io::madvise(addr.add(2 * page), 2 * page as u32, libc::MADV_DONTNEED)
+        }
+        .await
+        .unwrap();
+        let memory = unsafe { std::slice::from_raw_parts(addr, len) };
+        assert!(memory[..2 * page].iter().all(|&b| b == 0xaa));
+        assert!(memory[2 * page..].iter().all(|&b| b == 0));
+
+        // Not at a page boundary.
+        let err = unsafe { tokio_uring::io::madvise(addr.add(1), 1, libc::MADV_DONTNEED) }
+            .await
+            .unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+
+        unsafe { libc::munmap(addr.cast(), len) };
+    });
+}
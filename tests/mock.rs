@@ -0,0 +1,146 @@
+#![cfg(feature = "mock")]
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio_uring::fs::File;
+use tokio_uring::runtime::{DriverError, MockDriver};
+use tokio_uring::Submit;
+
+fn dev_null() -> File {
+    File::from_std(std::fs::File::open("/dev/null").unwrap())
+}
+
+// Lets the runtime park, which flushes the submissions.
+async fn flush() {
+    tokio::time::sleep(Duration::from_millis(1)).await;
+}
+
+#[test]
+fn partial_read() {
+    tokio_uring::builder().mock(true).start(async {
+        let mock = MockDriver::current();
+        let file = dev_null();
+
+        let read = tokio_uring::spawn(async move { file.read_at(vec![0; 16], 4).submit().await });
+        flush().await;
+
+        let ops = mock.submitted();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].opcode, io_uring::opcode::Read::CODE);
+        assert_eq!((ops[0].offset, ops[0].len), (4, 16));
+        assert_eq!(mock.pending().len(), 1);
+
+        mock.complete_read(ops[0].user_data, b"hello");
+        let (n, buf) = read.await.unwrap().unwrap();
+        assert_eq!(&buf[..n], b"hello");
+        assert!(mock.pending().is_empty());
+    });
+}
+
+#[test]
+fn error_results() {
+    tokio_uring::builder().mock(true).start(async {
+        let mock = MockDriver::current();
+        let file = dev_null();
+
+        let read = tokio_uring::spawn(async move { file.read_at(vec![0; 16], 0).submit().await });
+        flush().await;
+
+        let op = mock.submitted().pop().unwrap();
+        mock.complete(op.user_data, -libc::EAGAIN);
+        let err = read.await.unwrap().unwrap_err();
+        assert_eq!(err.0.raw_os_error(), Some(libc::EAGAIN));
+    });
+}
+
+#[test]
+fn overflow_is_reported() {
+    let errors = Arc::new(Mutex::new(Vec::new()));
+    let reported = errors.clone();
+
+    tokio_uring::builder()
+        .mock(true)
+        .on_driver_error(move |e| {
+            let undersized = matches!(e, DriverError::Undersized(_));
+            reported.lock().unwrap().push(undersized);
+        })
+        .start(async {
+            let mock = MockDriver::current();
+            mock.overflow();
+
+            let stats = tokio_uring::runtime::ring_stats();
+            assert_eq!(stats.cq_overflow, 1);
+        });
+
+    assert_eq!(*errors.lock().unwrap(), vec![true]);
+}
+
+#[test]
+fn dropped_ops_are_cancelled() {
+    let (_, report) = tokio_uring::builder().mock(true).start_with_report(async {
+        let mock = MockDriver::current();
+        let file = dev_null();
+
+        let read = tokio_uring::spawn(async move { file.read_at(vec![0; 16], 0).submit().await });
+        flush().await;
+        let op = mock.submitted().pop().unwrap();
+
+        read.abort();
+        flush().await;
+        assert_eq!(mock.cancelled(), vec![op.user_data]);
+
+        // One is left in flight for the shutdown.
+        let file = dev_null();
+        std::mem::forget(tokio_uring::spawn(async move {
+            file.read_at(vec![0; 16], 0).submit().await
+        }));
+        flush().await;
+        assert_eq!(mock.pending().len(), 2);
+
+        mock.complete(op.user_data, -libc::ECANCELED);
+        assert_eq!(mock.pending().len(), 1);
+    });
+
+    assert_eq!((report.in_flight, report.cancelled), (1, 1));
+}
+
+#[test]
+fn truncate_sqe() {
+    tokio_uring::builder().mock(true).start(async {
+        let mock = MockDriver::current();
+        let file = dev_null();
+        let fd = std::os::unix::io::AsRawFd::as_raw_fd(&file);
+
+        let truncate = tokio_uring::spawn(async move { file.truncate(4096).await });
+        flush().await;
+
+        let op = mock.submitted().pop().unwrap();
+        // IORING_OP_FTRUNCATE, with the length as the offset.
+        assert_eq!((op.opcode, op.fd, op.offset), (55, fd, 4096));
+
+        mock.complete(op.user_data, 0);
+        truncate.await.unwrap().unwrap();
+    });
+}
+
+#[test]
+fn rw_flags_sqe() {
+    tokio_uring::builder().mock(true).start(async {
+        let mock = MockDriver::current();
+        let file = dev_null();
+
+        let read = file.read_at(vec![0; 16], 0).nowait().hipri().submit();
+        let write = file.write_at(vec![0; 16], 0).dsync().submit();
+        flush().await;
+
+        let ops = mock.submitted();
+        assert_eq!(ops[0].op_flags, (libc::RWF_NOWAIT | libc::RWF_HIPRI) as u32);
+        assert_eq!(ops[1].op_flags, libc::RWF_DSYNC as u32);
+
+        mock.complete(ops[0].user_data, 16);
+        mock.complete(ops[1].user_data, 16);
+        read.await.unwrap();
+        write.await.unwrap();
+    });
+}
@@ -0,0 +1,37 @@
+use std::os::unix::process::ExitStatusExt;
+use std::process::Command;
+
+use tokio_uring::process::{Child, PidFd};
+
+#[test]
+fn wait_for_exit() {
+    tokio_uring::start(async {
+        let mut child = Child::spawn(Command::new("sh").args(["-c", "exit 3"])).unwrap();
+        let status = child.wait().await.unwrap();
+        assert_eq!(status.code(), Some(3));
+
+        // Waiting again returns the cached status.
+        assert_eq!(child.wait().await.unwrap().code(), Some(3));
+    });
+}
+
+#[test]
+fn signal_through_pidfd() {
+    tokio_uring::start(async {
+        let mut child = Child::spawn(Command::new("sleep").arg("60")).unwrap();
+
+        // A second pidfd to the same process observes the exit too.
+        let pidfd = PidFd::open(child.id()).unwrap();
+
+        child.pidfd().send_signal(libc::SIGTERM).unwrap();
+        pidfd.exited().await.unwrap();
+
+        let status = child.wait().await.unwrap();
+        assert_eq!(status.signal(), Some(libc::SIGTERM));
+
+        pidfd.close().await.unwrap();
+
+        // Killing a reaped child is a no-op.
+        child.kill().unwrap();
+    });
+}
@@ -1,4 +1,10 @@
+use io_uring::opcode;
+use std::os::unix::io::FromRawFd;
 use tokio::net::{TcpListener, TcpStream};
+use tokio_uring::buf::fixed::SharedFixedBufs;
+use tokio_uring::fs::OpenOptions;
+use tokio_uring::runtime::{Personality, Restrictions};
+use tokio_uring::Submit;
 
 #[test]
 fn use_tokio_types_from_runtime() {
@@ -34,3 +40,1121 @@ fn spawn_a_task() {
         assert_eq!(2, *cell.borrow());
     });
 }
+
+#[test]
+fn run_several_futures_on_a_runtime() {
+    let rt = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+
+    // Spawned before any future runs, it lives across the calls.
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let sum = rt.spawn(async move {
+        let mut sum = 0;
+        while let Some(n) = rx.recv().await {
+            tokio_uring::no_op().await.unwrap();
+            sum += n;
+        }
+        sum
+    });
+
+    for n in 1..=3 {
+        let tx = tx.clone();
+        rt.block_on(async move {
+            tokio_uring::no_op().await.unwrap();
+            tx.send(n).unwrap();
+        });
+    }
+    drop(tx);
+
+    assert_eq!(rt.block_on(sum).unwrap(), 6);
+}
+
+#[test]
+fn spawn_a_named_task() {
+    use tokio_uring::runtime::{current_task_name, TaskBuilder};
+
+    tokio_uring::start(async {
+        let handle = TaskBuilder::new()
+            .name("worker")
+            .spawn(async {
+                assert_eq!(current_task_name().as_deref(), Some("worker"));
+                tokio::task::yield_now().await;
+                tokio_uring::no_op().await.unwrap();
+                7
+            })
+            .unwrap();
+        assert_eq!(handle.name(), Some("worker"));
+        assert!(!handle.is_finished());
+        assert_eq!(current_task_name(), None);
+
+        while !handle.is_finished() {
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        }
+        assert!(handle.polls() >= 3);
+        assert!(handle.busy_duration() > std::time::Duration::ZERO);
+        assert_eq!(handle.await.unwrap(), 7);
+
+        // Tasks without a name, and aborted ones.
+        let handle = TaskBuilder::new()
+            .spawn(async {
+                assert_eq!(current_task_name(), None);
+                std::future::pending::<()>().await
+            })
+            .unwrap();
+        tokio::task::yield_now().await;
+        assert_eq!(handle.polls(), 1);
+        handle.abort();
+        tokio::task::yield_now().await;
+        assert!(handle.is_finished());
+        assert!(handle.await.unwrap_err().is_cancelled());
+    });
+}
+
+#[test]
+fn park_and_unpark_hooks() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let parks = Arc::new(AtomicUsize::new(0));
+    let unparks = Arc::new(AtomicUsize::new(0));
+    let (p, u) = (parks.clone(), unparks.clone());
+
+    tokio_uring::builder()
+        .on_park(move || {
+            p.fetch_add(1, Ordering::SeqCst);
+        })
+        .on_unpark(move || {
+            u.fetch_add(1, Ordering::SeqCst);
+        })
+        .start(async {
+            // The operation is only submitted once the runtime parks.
+            tokio_uring::no_op().await.unwrap();
+            assert!(parks.load(Ordering::SeqCst) >= 1);
+            assert!(unparks.load(Ordering::SeqCst) >= 1);
+        });
+}
+
+#[test]
+fn execute_from_another_thread() {
+    use std::cell::Cell;
+
+    thread_local! {
+        static RAN_ON_RING: Cell<bool> = const { Cell::new(false) };
+    }
+
+    let rt = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    let handle = rt.handle();
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    let thread = std::thread::spawn(move || {
+        handle
+            .execute(move || {
+                RAN_ON_RING.with(|r| r.set(true));
+                // The closure runs within the runtime context.
+                tokio_uring::spawn(async move {
+                    tokio_uring::no_op().await.unwrap();
+                    tx.send(()).unwrap();
+                });
+            })
+            .unwrap();
+        handle
+    });
+
+    rt.block_on(async {
+        rx.await.unwrap();
+    });
+    assert!(RAN_ON_RING.with(|r| r.get()));
+
+    let handle = thread.join().unwrap();
+    drop(rt);
+    assert!(handle.is_closed());
+    assert!(handle.execute(|| {}).is_err());
+}
+
+#[test]
+fn spawn_from_another_thread() {
+    use tokio_uring::fs::File;
+
+    let rt = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    let handle = rt.handle();
+    let ring_thread = std::thread::current().id();
+
+    let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+
+    let thread = std::thread::spawn(move || {
+        let on_ring = handle.spawn(async { std::thread::current().id() });
+
+        // The file stays on the ring thread, only the length crosses back.
+        let file_len = handle.spawn_with(|| async {
+            let file = File::open("Cargo.toml").await.unwrap();
+            let statx = file.statx().await.unwrap();
+            file.close().await.unwrap();
+            statx.stx_size
+        });
+
+        let panicked = handle.spawn(async { panic!("boom") });
+
+        let tokio_rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let res = tokio_rt.block_on(async {
+            (
+                on_ring.await.unwrap(),
+                file_len.await.unwrap(),
+                panicked.await.unwrap_err(),
+            )
+        });
+        done_tx.send(()).unwrap();
+        res
+    });
+
+    rt.block_on(async {
+        done_rx.await.unwrap();
+    });
+
+    let (id, len, err) = thread.join().unwrap();
+    assert_eq!(id, ring_thread);
+    assert_eq!(len, std::fs::metadata("Cargo.toml").unwrap().len());
+    assert!(err.is_panic());
+
+    // Tasks spawned after shutdown are reported as cancelled.
+    let handle = rt.handle();
+    drop(rt);
+    let tokio_rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let err = tokio_rt.block_on(handle.spawn(async {})).unwrap_err();
+    assert!(err.is_cancelled());
+}
+
+#[test]
+fn blocking_join_from_another_thread() {
+    let rt = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    let handle = rt.handle();
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    let thread = std::thread::spawn(move || {
+        let n = handle
+            .spawn_with(|| async {
+                let file = tokio_uring::fs::File::open("/dev/zero").await.unwrap();
+                let (n, _) = file.read_at(vec![1; 8], 0).submit().await.unwrap();
+                file.close().await.unwrap();
+                n
+            })
+            .blocking_join()
+            .unwrap();
+        tx.send(n).unwrap();
+    });
+
+    let n = rt.block_on(async { rx.await.unwrap() });
+    assert_eq!(n, 8);
+    thread.join().unwrap();
+}
+
+#[test]
+fn restricted_ring() {
+    let mut restrictions = Restrictions::new();
+    restrictions
+        .allow_op(opcode::Nop::CODE)
+        .allow_op(opcode::Read::CODE)
+        .allow_runtime_ops();
+
+    tokio_uring::builder()
+        .restrictions(&restrictions)
+        .op_timeout(std::time::Duration::from_millis(20))
+        .start(async {
+            tokio_uring::no_op().await.unwrap();
+
+            // Operations get their deadline.
+            let mut fds = [0; 2];
+            assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+            let rx = unsafe { tokio_uring::fs::File::from_raw_fd(fds[0]) };
+            let err = rx.read_at(vec![0; 16], 0).submit().await.unwrap_err();
+            assert_eq!(err.0.raw_os_error(), Some(libc::ETIMEDOUT));
+            rx.close().await.unwrap();
+            unsafe { libc::close(fds[1]) };
+
+            let file = std::fs::File::open("/dev/null").unwrap();
+            tokio_uring::fs::File::from_std(file).close().await.unwrap();
+
+            // Opening a file is not on the allow-list.
+            let err = tokio_uring::fs::File::open("/dev/null").await.unwrap_err();
+            assert_eq!(err.raw_os_error(), Some(libc::EACCES));
+        });
+}
+
+#[test]
+fn fall_back_from_unsupported_ops() {
+    let mut restrictions = Restrictions::new();
+    restrictions
+        .allow_op(opcode::Connect::CODE)
+        .allow_op(opcode::SendMsg::CODE)
+        .allow_op(opcode::Read::CODE)
+        .allow_op(opcode::AsyncCancel::CODE);
+
+    tokio_uring::builder()
+        .restrictions(&restrictions)
+        .start(async {
+            use tokio_uring::net::UdpSocket;
+
+            let rx = UdpSocket::bind("127.0.0.1:0".parse().unwrap())
+                .await
+                .unwrap();
+            let tx = UdpSocket::bind("127.0.0.1:0".parse().unwrap())
+                .await
+                .unwrap();
+            tx.connect(rx.local_addr().unwrap()).await.unwrap();
+
+            // Zero-copy sends are not on the allow-list.
+            let (n, _) = tx.send_zc(b"zc".to_vec()).await.unwrap();
+            assert_eq!(n, 2);
+            let (res, _, _) = tx
+                .sendmsg_zc(vec![b"msg".to_vec()], None, None::<Vec<u8>>)
+                .await;
+            assert_eq!(res.unwrap(), 3);
+
+            let (n, buf) = rx.read(vec![0; 8]).await.unwrap();
+            assert_eq!(&buf[..n], b"zc");
+            let (n, buf) = rx.read(vec![0; 8]).await.unwrap();
+            assert_eq!(&buf[..n], b"msg");
+        });
+}
+
+#[test]
+fn open_with_personality() {
+    if unsafe { libc::geteuid() } != 0 {
+        return;
+    }
+
+    let tempfile = tempfile::NamedTempFile::new().unwrap();
+    let mut options = OpenOptions::new();
+    options.read(true);
+
+    tokio_uring::start(async {
+        // Switch the credentials of this thread only, the raw syscall does not
+        // propagate them to the other threads of the process.
+        let setresuid = |uid: libc::uid_t| unsafe {
+            assert_eq!(libc::syscall(libc::SYS_setresuid, -1, uid, -1), 0);
+        };
+        setresuid(65534);
+        let nobody = Personality::register().unwrap();
+        setresuid(0);
+
+        // The temporary file is only readable by its owner.
+        options.open(tempfile.path()).await.unwrap();
+
+        let err = options
+            .clone()
+            .personality(nobody)
+            .open(tempfile.path())
+            .await
+            .unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EACCES));
+
+        nobody.unregister().unwrap();
+    });
+}
+
+#[test]
+fn attach_to_worker_pool() {
+    let primary = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    let handle = primary.handle();
+
+    std::thread::spawn(move || {
+        tokio_uring::builder().attach_wq(&handle).start(async {
+            let file = tokio_uring::fs::File::open("/dev/null").await.unwrap();
+            file.close().await.unwrap();
+        })
+    })
+    .join()
+    .unwrap();
+
+    // The pool of a runtime which shut down can't be shared, even if its fd
+    // was reused since.
+    let primary = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    let handle = primary.handle();
+    drop(primary);
+    let _reused = std::fs::File::open("/dev/null").unwrap();
+    assert!(tokio_uring::Runtime::new(tokio_uring::builder().attach_wq(&handle)).is_err());
+}
+
+#[test]
+fn pin_worker_pool() {
+    tokio_uring::builder().iowq_cpus(&[0]).start(async {
+        tokio_uring::no_op().await.unwrap();
+    });
+
+    tokio_uring::builder().iowq_thread_affinity().start(async {
+        tokio_uring::no_op().await.unwrap();
+    });
+
+    assert!(tokio_uring::Runtime::new(tokio_uring::builder().iowq_cpus(&[usize::MAX])).is_err());
+}
+
+#[test]
+fn shutdown_report() {
+    let mut fds = [0; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+
+    let (_, report) = tokio_uring::builder().start_with_report(async move {
+        // Nothing is ever written to the pipe, the read stays in flight.
+        tokio_uring::spawn(async move {
+            let rx = unsafe { tokio_uring::fs::File::from_raw_fd(fds[0]) };
+            let _ = rx.read_at(vec![0; 16], 0).submit().await;
+        });
+        tokio_uring::no_op().await.unwrap();
+    });
+
+    assert_eq!(report.in_flight, 1);
+    assert_eq!(report.cancelled, 1);
+    assert_eq!(report.completed, 0);
+    assert!(report.close_errors.is_empty());
+    assert!(!report.is_clean());
+
+    unsafe { libc::close(fds[1]) };
+
+    let (_, report) = tokio_uring::builder().start_with_report(async {});
+    assert!(report.is_clean());
+}
+
+#[test]
+fn cancel_on_shutdown() {
+    let mut restricted = Restrictions::new();
+    restricted
+        .allow_op(opcode::Nop::CODE)
+        .allow_op(opcode::Read::CODE)
+        .allow_op(opcode::AsyncCancel::CODE);
+
+    let mut builders = vec![tokio_uring::builder(); 3];
+    builders[1].defer_taskrun(true);
+    // Rings restricted from cancelling synchronously cancel op by op.
+    builders[2].restrictions(&restricted);
+
+    for builder in &builders {
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+
+        let (_, report) = builder.start_with_report(async move {
+            let rx = std::rc::Rc::new(unsafe { tokio_uring::fs::File::from_raw_fd(fds[0]) });
+            for _ in 0..32 {
+                let rx = rx.clone();
+                tokio_uring::spawn(async move {
+                    let _ = rx.read_at(vec![0; 16], 0).submit().await;
+                });
+            }
+            tokio_uring::no_op().await.unwrap();
+        });
+
+        assert_eq!(report.in_flight, 32);
+        assert_eq!(report.cancelled, 32);
+        unsafe { libc::close(fds[1]) };
+    }
+}
+
+#[test]
+fn dump_inflight() {
+    tokio_uring::start(async {
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let rx = unsafe { tokio_uring::fs::File::from_raw_fd(fds[0]) };
+
+        let before = std::time::Instant::now();
+        let read = rx.read_at(vec![0; 16], 0).submit();
+        tokio_uring::no_op().await.unwrap();
+
+        let ops = tokio_uring::runtime::dump_inflight();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].opcode, opcode::Read::CODE);
+        assert_eq!(ops[0].fd, fds[0]);
+        assert!(!ops[0].fixed_file);
+        assert!(!ops[0].abandoned);
+        assert!(ops[0].submitted >= before);
+
+        drop(read);
+        let ops = tokio_uring::runtime::dump_inflight();
+        assert!(ops[0].abandoned);
+
+        // The dropped read is cancelled.
+        tokio_uring::no_op().await.unwrap();
+        tokio_uring::no_op().await.unwrap();
+        assert!(tokio_uring::runtime::dump_inflight().is_empty());
+        unsafe { libc::close(fds[1]) };
+    });
+}
+
+#[test]
+fn cap_operations_in_flight() {
+    use tokio_uring::SubmitError;
+
+    tokio_uring::builder()
+        .preallocate_ops(4)
+        .max_in_flight(2)
+        .start(async {
+            let mut fds = [0; 2];
+            assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+            let rx = unsafe { tokio_uring::fs::File::from_raw_fd(fds[0]) };
+            let read = rx.read_at(vec![0; 16], 0).submit();
+
+            // The permit holds the last slot aside.
+            let permit = tokio_uring::runtime::op_permit().await;
+            let err = rx.read_at(vec![0; 16], 0).try_submit().err().unwrap();
+            assert!(matches!(err, SubmitError::InFlightLimit));
+            let err = tokio_uring::no_op().await.unwrap_err();
+            assert!(matches!(
+                SubmitError::from_io_error(&err),
+                Some(SubmitError::InFlightLimit)
+            ));
+
+            let waiter = tokio_uring::spawn(async {
+                let permit = tokio_uring::runtime::op_permit().await;
+                permit.run(tokio_uring::no_op()).await
+            });
+            let second = permit.submit(rx.read_at(vec![0; 16], 0));
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            assert!(!waiter.is_finished());
+
+            // A read completing makes room for the waiter.
+            assert_eq!(unsafe { libc::write(fds[1], b"x".as_ptr().cast(), 1) }, 1);
+            waiter.await.unwrap().unwrap();
+            unsafe { libc::close(fds[1]) };
+            let (a, _) = read.await.unwrap();
+            let (b, _) = second.await.unwrap();
+            assert_eq!(a + b, 1);
+        });
+}
+
+#[test]
+fn op_deadlines() {
+    use std::time::Duration;
+
+    tokio_uring::builder()
+        .op_timeout(Duration::from_millis(20))
+        .start(async {
+            let mut fds = [0; 2];
+            assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+            let rx = unsafe { tokio_uring::fs::File::from_raw_fd(fds[0]) };
+
+            let err = rx.read_at(vec![0; 16], 0).submit().await.unwrap_err();
+            assert_eq!(err.0.raw_os_error(), Some(libc::ETIMEDOUT));
+            tokio_uring::no_op().await.unwrap();
+
+            // Overridden, the read outlives the default deadline.
+            let read = rx.read_at(vec![0; 16], 0).no_timeout().submit();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert_eq!(unsafe { libc::write(fds[1], b"x".as_ptr().cast(), 1) }, 1);
+            let (n, _) = read.await.unwrap();
+            assert_eq!(n, 1);
+            unsafe { libc::close(fds[1]) };
+        });
+
+    tokio_uring::start(async {
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let rx = unsafe { tokio_uring::fs::File::from_raw_fd(fds[0]) };
+
+        let err = rx
+            .read_at(vec![0; 16], 0)
+            .timeout(Duration::from_millis(10))
+            .submit()
+            .await
+            .unwrap_err();
+        assert_eq!(err.0.raw_os_error(), Some(libc::ETIMEDOUT));
+        unsafe { libc::close(fds[1]) };
+    });
+}
+
+#[test]
+fn access_the_ring() {
+    tokio_uring::builder().entries(64).start(async {
+        let sq_entries = tokio_uring::with_ring(|ring| ring.params().sq_entries());
+        assert_eq!(sq_entries, 64);
+
+        let supported = tokio_uring::with_ring(|ring| {
+            let mut probe = io_uring::Probe::new();
+            ring.submitter().register_probe(&mut probe).unwrap();
+            probe.is_supported(opcode::Nop::CODE)
+        });
+        assert!(supported);
+    });
+}
+
+#[test]
+fn submission_polling() {
+    tokio_uring::builder().entries(4).sqpoll(10).start(async {
+        assert!(tokio_uring::with_ring(|ring| ring
+            .params()
+            .is_setup_sqpoll()));
+
+        let file = tokio_uring::fs::File::open("/dev/zero").await.unwrap();
+
+        // Overrun both queues, the SQ thread makes room in the submission
+        // queue, and overflowed completions are flushed by the runtime.
+        let reads: Vec<_> = (0..16)
+            .map(|_| file.read_at(vec![1; 8], 0).submit())
+            .collect();
+        for read in reads {
+            let (n, buf) = read.await.unwrap();
+            assert_eq!(&buf[..n], &[0; 8]);
+        }
+
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn wake_sleeping_sq_thread() {
+    tokio_uring::builder()
+        .sq_thread_idle(std::time::Duration::from_millis(1))
+        .sq_thread_cpu(0)
+        .start(async {
+            let file = tokio_uring::fs::File::open("/dev/zero").await.unwrap();
+
+            for _ in 0..3 {
+                // Let the SQ thread go idle, the next submission wakes it.
+                std::thread::sleep(std::time::Duration::from_millis(20));
+                let (n, _) = file.read_at(vec![1; 8], 0).submit().await.unwrap();
+                assert_eq!(n, 8);
+            }
+
+            file.close().await.unwrap();
+        });
+}
+
+#[test]
+fn polled_io() {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mut tempfile = tempfile::NamedTempFile::new_in(env!("CARGO_TARGET_TMPDIR")).unwrap();
+    tempfile.write_all(&[7; 4096]).unwrap();
+    tempfile.as_file().sync_all().unwrap();
+
+    let std_file = std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_DIRECT)
+        .open(tempfile.path())
+        .unwrap();
+
+    tokio_uring::builder().iopoll(true).start(async {
+        let file = tokio_uring::fs::File::from_std(std_file);
+
+        // O_DIRECT needs an aligned buffer.
+        let bufs = SharedFixedBufs::new(1, 4096);
+        bufs.register().unwrap();
+
+        // The completion is reaped by polling, whether the read succeeds or
+        // the device lacks poll queues.
+        match file.read_fixed_at(bufs.check_out(0).unwrap(), 0).await {
+            Ok((n, buf)) => {
+                assert_eq!(n, 4096);
+                assert!(buf.iter().all(|&b| b == 7));
+            }
+            Err(e) => assert_eq!(e.0.raw_os_error(), Some(libc::EOPNOTSUPP)),
+        }
+    });
+}
+
+#[test]
+fn deferred_task_work() {
+    tokio_uring::builder().defer_taskrun(true).start(async {
+        assert!(tokio_uring::with_ring(|ring| ring
+            .params()
+            .is_feature_nodrop()));
+
+        let file = tokio_uring::fs::File::open("/dev/zero").await.unwrap();
+        for _ in 0..3 {
+            let (n, _) = file.read_at(vec![1; 8], 0).submit().await.unwrap();
+            assert_eq!(n, 8);
+        }
+
+        // Completions of another task are collected as well.
+        let task = tokio_uring::spawn(async { tokio_uring::no_op().await });
+        task.await.unwrap().unwrap();
+
+        file.close().await.unwrap();
+    });
+
+    tokio_uring::builder().single_issuer(true).start(async {
+        tokio_uring::no_op().await.unwrap();
+    });
+}
+
+#[test]
+fn cooperative_task_work() {
+    tokio_uring::builder().coop_taskrun(true).start(async {
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let rx = unsafe { tokio_uring::fs::File::from_raw_fd(fds[0]) };
+        let tx = fds[1];
+
+        // The completion arrives while the runtime is parked.
+        let writer = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            assert_eq!(unsafe { libc::write(tx, b"hi".as_ptr().cast(), 2) }, 2);
+            unsafe { libc::close(tx) };
+        });
+
+        let (n, buf) = rx.read_at(vec![0; 8], 0).submit().await.unwrap();
+        assert_eq!(&buf[..n], b"hi");
+        writer.join().unwrap();
+
+        let file = tokio_uring::fs::File::open("/dev/zero").await.unwrap();
+        let (n, _) = file.read_at(vec![1; 8], 0).submit().await.unwrap();
+        assert_eq!(n, 8);
+    });
+}
+
+#[test]
+fn napi_busy_poll() {
+    tokio_uring::builder()
+        .napi_busy_poll(std::time::Duration::from_micros(50), true)
+        .start(async {
+            let listener =
+                tokio_uring::net::TcpListener::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+            let addr = listener.local_addr().unwrap();
+
+            let client = tokio_uring::net::TcpStream::connect(addr).await.unwrap();
+            let (server, _) = listener.accept().await.unwrap();
+
+            let (n, _) = client.write(b"ping".to_vec()).submit().await.unwrap();
+            assert_eq!(n, 4);
+            let (n, buf) = server.read(vec![0; 4]).await.unwrap();
+            assert_eq!(&buf[..n], b"ping");
+        });
+}
+
+#[test]
+fn busy_poll() {
+    use std::time::Duration;
+    use tokio_uring::runtime::BusyPoll;
+
+    for mode in [
+        BusyPoll::Always,
+        BusyPoll::Timeout(Duration::from_millis(1)),
+    ] {
+        tokio_uring::builder().busy_poll(mode, true).start(async {
+            let mut fds = [0; 2];
+            assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+            let rx = unsafe { tokio_uring::fs::File::from_raw_fd(fds[0]) };
+            let tx = fds[1];
+
+            // With a timeout, the runtime stops spinning before this arrives.
+            let writer = std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(50));
+                assert_eq!(unsafe { libc::write(tx, b"hi".as_ptr().cast(), 2) }, 2);
+                unsafe { libc::close(tx) };
+            });
+
+            let (n, buf) = rx.read_at(vec![0; 8], 0).submit().await.unwrap();
+            assert_eq!(&buf[..n], b"hi");
+            writer.join().unwrap();
+
+            for _ in 0..8 {
+                tokio_uring::no_op().await.unwrap();
+            }
+        });
+    }
+}
+
+#[test]
+fn graceful_shutdown() {
+    let mut fds = [0; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+    let tx = fds[1];
+
+    let (_, report) = tokio_uring::builder()
+        .shutdown_timeout(std::time::Duration::from_secs(10))
+        .start_with_report(async move {
+            tokio_uring::spawn(async move {
+                let rx = unsafe { tokio_uring::fs::File::from_raw_fd(fds[0]) };
+                let _ = rx.read_at(vec![0; 16], 0).submit().await;
+            });
+            tokio_uring::no_op().await.unwrap();
+
+            // The read completes while the runtime shuts down.
+            std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                assert_eq!(unsafe { libc::write(tx, b"hi".as_ptr().cast(), 2) }, 2);
+            });
+        });
+
+    assert_eq!(report.in_flight, 1);
+    assert_eq!(report.completed, 1);
+    assert_eq!(report.cancelled, 0);
+
+    // Ops still in flight at the deadline are cancelled.
+    let (_, report) = tokio_uring::builder()
+        .shutdown_timeout(std::time::Duration::from_millis(10))
+        .start_with_report(async move {
+            let mut fds = [0; 2];
+            assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+            tokio_uring::spawn(async move {
+                let rx = unsafe { tokio_uring::fs::File::from_raw_fd(fds[0]) };
+                let _ = rx.read_at(vec![0; 16], 0).submit().await;
+            });
+            tokio_uring::no_op().await.unwrap();
+        });
+
+    assert_eq!(report.in_flight, 1);
+    assert_eq!(report.cancelled, 1);
+    unsafe { libc::close(tx) };
+}
+
+#[test]
+fn message_another_ring() {
+    let rt = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    let handle = rt.handle();
+
+    let sender = std::thread::spawn(move || {
+        tokio_uring::start(async move {
+            for i in 0..3 {
+                handle.send_msg(i, 100 + i as u64).await.unwrap();
+            }
+            // The largest data a message can carry.
+            handle.send_msg(3, u64::MAX >> 1).await.unwrap();
+
+            let err = handle.send_msg(0, u64::MAX).await.unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        });
+    });
+
+    rt.block_on(async {
+        for i in 0..3 {
+            let msg = tokio_uring::runtime::recv_msg().await;
+            assert_eq!((msg.value(), msg.data()), (i, 100 + i as u64));
+        }
+        let msg = tokio_uring::runtime::recv_msg().await;
+        assert_eq!((msg.value(), msg.data()), (3, u64::MAX >> 1));
+    });
+
+    sender.join().unwrap();
+}
+
+#[test]
+fn pin_to_cpu() {
+    std::thread::spawn(|| {
+        tokio_uring::builder().pin_to_cpu(0).start(async {
+            let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+            let size = std::mem::size_of::<libc::cpu_set_t>();
+            assert_eq!(unsafe { libc::sched_getaffinity(0, size, &mut set) }, 0);
+            assert_eq!(unsafe { libc::CPU_COUNT(&set) }, 1);
+            assert!(unsafe { libc::CPU_ISSET(0, &set) });
+        });
+
+        let err = tokio_uring::Runtime::new(tokio_uring::builder().pin_to_cpu(usize::MAX))
+            .err()
+            .unwrap();
+        assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn register_eventfd() {
+    use std::os::unix::io::AsRawFd;
+
+    let rt = tokio_uring::Runtime::new(&tokio_uring::builder()).unwrap();
+    assert!(rt.as_raw_fd() >= 0);
+
+    rt.block_on(async {
+        let efd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+        assert!(efd >= 0);
+        tokio_uring::runtime::register_eventfd(efd, false).unwrap();
+
+        let err = tokio_uring::runtime::register_eventfd(efd, true).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EBUSY));
+
+        tokio_uring::no_op().await.unwrap();
+
+        let mut count = 0u64;
+        let n = unsafe { libc::read(efd, (&mut count as *mut u64).cast(), 8) };
+        assert_eq!(n, 8);
+        assert!(count >= 1);
+
+        tokio_uring::runtime::unregister_eventfd().unwrap();
+        let err = tokio_uring::runtime::unregister_eventfd().unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::ENXIO));
+        unsafe { libc::close(efd) };
+    });
+}
+
+#[test]
+fn resize_the_ring() {
+    use std::os::unix::io::AsRawFd;
+    use std::time::Duration;
+    use tokio_uring::buf::fixed::FixedBufRegistry;
+    use tokio_uring::runtime::{resize, ring_stats};
+
+    let mut builder = tokio_uring::builder();
+    builder.entries(4);
+    let rt = tokio_uring::Runtime::new(&builder).unwrap();
+    let fd = rt.as_raw_fd();
+    let handle = rt.handle();
+
+    rt.block_on(async {
+        let efd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+        tokio_uring::runtime::register_eventfd(efd, false).unwrap();
+        let buffers = FixedBufRegistry::new([Vec::with_capacity(16)]);
+        buffers.register().unwrap();
+
+        // Operations in flight belong to the ring.
+        let (rx, mut tx) = std::os::unix::net::UnixStream::pair().unwrap();
+        let rx = tokio_uring::fs::File::from_std(std::os::fd::OwnedFd::from(rx).into());
+        let read = tokio_uring::spawn(async move {
+            let (n, _) = rx.read_at(vec![0; 4], 0).submit().await.unwrap();
+            n
+        });
+        tokio::task::yield_now().await;
+        let err = resize(64, 128).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EBUSY));
+        std::io::Write::write_all(&mut tx, b"ping").unwrap();
+        assert_eq!(read.await.unwrap(), 4);
+
+        let personality = tokio_uring::runtime::Personality::register().unwrap();
+        let err = resize(64, 128).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EBUSY));
+        personality.unregister().unwrap();
+
+        resize(64, 128).unwrap();
+        let stats = ring_stats();
+        assert_eq!((stats.sq_entries, stats.cq_entries), (64, 128));
+
+        // Completions of the new ring wake the runtime, and what was
+        // registered with the old ring carries over.
+        tokio::time::timeout(Duration::from_secs(5), tokio_uring::no_op())
+            .await
+            .unwrap()
+            .unwrap();
+        let mut count = 0u64;
+        let n = unsafe { libc::read(efd, (&mut count as *mut u64).cast(), 8) };
+        assert_eq!(n, 8);
+
+        let file = tokio_uring::fs::File::open("/dev/zero").await.unwrap();
+        let buf = buffers.check_out(0).unwrap();
+        let (n, _) = file.read_fixed_at(buf, 0).await.unwrap();
+        assert_eq!(n, 16);
+        file.close().await.unwrap();
+
+        tokio_uring::runtime::unregister_eventfd().unwrap();
+        unsafe { libc::close(efd) };
+    });
+
+    // The fd of the runtime refers to the new ring, so other threads still
+    // reach it.
+    assert_eq!(rt.as_raw_fd(), fd);
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let thread = std::thread::spawn(move || {
+        handle
+            .execute(move || {
+                tx.send(ring_stats().sq_entries).unwrap();
+            })
+            .unwrap();
+    });
+    assert_eq!(rt.block_on(async { rx.await.unwrap() }), 64);
+    thread.join().unwrap();
+}
+
+#[test]
+fn multiple_rings() {
+    use tokio_uring::runtime::{add_ring, on_ring, ring_stats, route, RingId};
+
+    tokio_uring::start(async {
+        let mut restrictions = Restrictions::new();
+        restrictions
+            .allow_op(opcode::Read::CODE)
+            .allow_runtime_ops();
+        let second = add_ring(
+            tokio_uring::builder()
+                .entries(8)
+                .restrictions(&restrictions),
+        )
+        .unwrap();
+        assert_ne!(second, RingId::PRIMARY);
+
+        assert_eq!(on_ring(second, async { ring_stats().sq_entries }).await, 8);
+        assert_eq!(ring_stats().sq_entries, 256);
+
+        // Selected explicitly.
+        let err = on_ring(second, tokio_uring::no_op()).await.unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EACCES));
+        tokio_uring::no_op().await.unwrap();
+
+        let file = tokio_uring::fs::File::open("/dev/zero").await.unwrap();
+        let (n, _) = on_ring(second, file.read_at(vec![1; 8], 0).submit())
+            .await
+            .unwrap();
+        assert_eq!(n, 8);
+
+        // Routed by opcode.
+        route(opcode::Nop::CODE, second);
+        let err = tokio_uring::no_op().await.unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EACCES));
+        route(opcode::Nop::CODE, RingId::PRIMARY);
+        tokio_uring::no_op().await.unwrap();
+
+        // Linked chains stay on a single ring.
+        let tempfile = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(tempfile.path(), [7; 8]).unwrap();
+        let rw = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(tempfile.path())
+            .await
+            .unwrap();
+        route(opcode::Write::CODE, second);
+        let read = rw.read_at(vec![0; 8], 0);
+        let write = rw.write_at(&b"hello"[..], 0);
+        let (res, write) = read.link(write).submit().await;
+        assert_eq!(res.unwrap().0, 8);
+        assert_eq!(write.await.unwrap().0, 5);
+        route(opcode::Write::CODE, RingId::PRIMARY);
+        rw.close().await.unwrap();
+
+        file.close().await.unwrap();
+    });
+}
+
+#[test]
+fn big_entries() {
+    use io_uring::{cqueue, squeue};
+    use tokio_uring::{BigOutputTransform, UnsubmittedBig};
+
+    struct Extra;
+
+    impl BigOutputTransform for Extra {
+        type Output = (i32, [u64; 2]);
+        type StoredData = ();
+
+        fn transform_big_output(self, _: (), cqe: cqueue::Entry32) -> Self::Output {
+            (cqe.result(), *cqe.big_cqe())
+        }
+    }
+
+    let nop = || {
+        UnsubmittedBig::new(
+            (),
+            Extra,
+            squeue::Entry128::from(opcode::Nop::new().build()),
+        )
+    };
+
+    tokio_uring::builder().sqe128().cqe32().start(async {
+        let (res, extra) = nop().submit().await;
+        assert_eq!((res, extra), (0, [0, 0]));
+
+        // Regular operations run on rings with big entries too.
+        let file = tokio_uring::fs::File::open("/dev/zero").await.unwrap();
+        let (n, buf) = file.read_at(vec![1; 8], 0).submit().await.unwrap();
+        assert_eq!((n, buf), (8, vec![0; 8]));
+        file.close().await.unwrap();
+        tokio_uring::no_op().await.unwrap();
+    });
+
+    tokio_uring::builder().cqe32().start(async {
+        tokio_uring::no_op().await.unwrap();
+        let err = nop().try_submit().err().unwrap();
+        assert_eq!(
+            std::io::Error::from(err).kind(),
+            std::io::ErrorKind::InvalidInput
+        );
+    });
+
+    let err = tokio_uring::Runtime::new(
+        tokio_uring::builder()
+            .sqe128()
+            .uring_builder(&tokio_uring::uring_builder()),
+    )
+    .err()
+    .unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn multi_thread() {
+    let rt = tokio_uring::Runtime::new_multi_thread(2).unwrap();
+    assert_eq!(rt.workers(), 2);
+
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            rt.spawn(|| async {
+                let file = tokio_uring::fs::File::open("/dev/zero").await.unwrap();
+                let (n, _) = file.read_at(vec![1; 8], 0).submit().await.unwrap();
+                file.close().await.unwrap();
+                (n, std::thread::current().id())
+            })
+        })
+        .collect();
+
+    let ids: Vec<_> = rt.block_on(|| async move {
+        let mut ids = Vec::new();
+        for task in tasks {
+            let (n, id) = task.await.unwrap();
+            assert_eq!(n, 8);
+            ids.push(id);
+        }
+        ids
+    });
+
+    // Spawned on the workers in turn.
+    assert_ne!(ids[0], ids[1]);
+    assert_eq!(ids[0], ids[2]);
+    assert_eq!(ids[1], ids[3]);
+    assert!(!ids.contains(&std::thread::current().id()));
+
+    let pinned = rt.spawn_on(1, || async { std::thread::current().id() });
+    let pinned = rt.block_on(|| async move { pinned.await.unwrap() });
+    assert_eq!(pinned, ids[1]);
+}
+
+#[test]
+fn attach_to_tokio_runtime() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    tokio::task::LocalSet::new().block_on(&rt, async {
+        let driver = tokio_uring::Driver::attach().unwrap();
+        let err = tokio_uring::Driver::attach().err().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+
+        let file = tokio_uring::fs::File::open("/dev/zero").await.unwrap();
+        let (n, _) = file.read_at(vec![1; 8], 0).submit().await.unwrap();
+        assert_eq!(n, 8);
+
+        // Local tasks and Tokio types work alongside.
+        let task = tokio::task::spawn_local(async move {
+            let (n, _) = file.read_at(vec![1; 4], 0).submit().await.unwrap();
+            file.close().await.unwrap();
+            n
+        });
+        tokio::task::yield_now().await;
+        assert_eq!(task.await.unwrap(), 4);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accept = tokio::task::spawn_local(async move { listener.accept().await });
+        tokio_uring::net::TcpStream::connect(addr).await.unwrap();
+        accept.await.unwrap().unwrap();
+
+        drop(driver);
+        let _driver = tokio_uring::Driver::attach().unwrap();
+        tokio_uring::no_op().await.unwrap();
+    });
+}
+
+#[test]
+fn probe_kernel() {
+    let probe = tokio_uring::probe().unwrap();
+    assert!(probe.is_supported(opcode::Nop::CODE));
+    assert!(probe.is_supported(opcode::Read::CODE));
+    assert!(!probe.is_supported(u8::MAX));
+    assert!(probe.params().is_feature_nodrop());
+
+    // Works from within a runtime as well.
+    tokio_uring::start(async {
+        assert!(tokio_uring::probe()
+            .unwrap()
+            .is_supported(opcode::Nop::CODE));
+    });
+}
@@ -0,0 +1,49 @@
+use tokio_uring::net::{TcpListener, TcpStream};
+
+#[test]
+fn readiness() {
+    tokio_uring::start(async {
+        let listener = TcpListener::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = TcpStream::connect(addr).await.unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+
+        // A fresh connection has room in its send buffer.
+        client.writable().await.unwrap();
+
+        let readable = tokio_uring::spawn(async move {
+            server.readable().await.unwrap();
+            let (n, buf) = server.read(vec![0; 16]).await.unwrap();
+            buf[..n].to_vec()
+        });
+
+        client.write_all(b"ping".to_vec()).await.unwrap();
+
+        assert_eq!(readable.await.unwrap(), b"ping");
+    });
+}
+
+#[test]
+fn cancel_ops() {
+    tokio_uring::start(async {
+        let listener = TcpListener::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let _client = TcpStream::connect(addr).await.unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+        let server = std::rc::Rc::new(server);
+
+        // The client never sends anything.
+        let reader = tokio_uring::spawn({
+            let server = server.clone();
+            async move { server.read(vec![0; 16]).await.map(|(n, _)| n) }
+        });
+        tokio_uring::no_op().await.unwrap();
+
+        assert_eq!(server.cancel_ops().await.unwrap(), 1);
+        let err = reader.await.unwrap().unwrap_err();
+        assert_eq!(err.0.raw_os_error(), Some(libc::ECANCELED));
+        assert_eq!(server.cancel_ops().await.unwrap(), 0);
+    });
+}
@@ -0,0 +1,110 @@
+use std::time::{Duration, Instant};
+
+use tokio_uring::time::{interval, sleep, sleep_until};
+
+#[test]
+fn sleep_for_a_while() {
+    tokio_uring::start(async {
+        let start = Instant::now();
+        sleep(Duration::from_millis(20)).await;
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        // A deadline in the past completes without a timeout.
+        let mut past = sleep_until(start);
+        (&mut past).await;
+        assert!(past.is_elapsed());
+    });
+}
+
+#[test]
+fn reset_a_sleep() {
+    tokio_uring::start(async {
+        let start = Instant::now();
+        let mut sleep = sleep(Duration::from_secs(60));
+
+        // Polled once, the timeout is in flight.
+        poll_once(&mut sleep).await;
+        assert_eq!(tokio_uring::runtime::dump_inflight().len(), 1);
+
+        sleep.reset(start + Duration::from_millis(10));
+        (&mut sleep).await;
+        assert!(start.elapsed() < Duration::from_secs(60));
+
+        // Dropping a sleep cancels its timeout.
+        let mut sleep = tokio_uring::time::sleep(Duration::from_secs(60));
+        poll_once(&mut sleep).await;
+        drop(sleep);
+        tokio_uring::no_op().await.unwrap();
+        tokio_uring::no_op().await.unwrap();
+        assert!(tokio_uring::runtime::dump_inflight().is_empty());
+    });
+}
+
+#[test]
+fn update_and_remove_timeouts() {
+    use io_uring::opcode;
+
+    tokio_uring::start(async {
+        let start = Instant::now();
+        let mut sleep = sleep(Duration::from_millis(10));
+        poll_once(&mut sleep).await;
+
+        // The timeout in flight is moved rather than replaced.
+        sleep.reset(start + Duration::from_millis(50));
+        tokio_uring::no_op().await.unwrap();
+        let ops = tokio_uring::runtime::dump_inflight();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].opcode, opcode::Timeout::CODE);
+        (&mut sleep).await;
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        let mut idle = tokio_uring::time::sleep(Duration::from_secs(60));
+        poll_once(&mut idle).await;
+        assert!(idle.cancel().await);
+        assert!(tokio_uring::runtime::dump_inflight().is_empty());
+
+        let mut elapsed = tokio_uring::time::sleep(Duration::from_millis(1));
+        poll_once(&mut elapsed).await;
+        tokio_uring::time::sleep(Duration::from_millis(10)).await;
+        assert!(!elapsed.cancel().await);
+    });
+}
+
+#[test]
+fn sleep_under_in_flight_cap() {
+    tokio_uring::builder().max_in_flight(1).start(async {
+        let start = Instant::now();
+        let mut first = sleep(Duration::from_millis(20));
+        poll_once(&mut first).await;
+
+        // Waits for the slot of the first timeout rather than panicking.
+        sleep(Duration::from_millis(1)).await;
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        first.await;
+    });
+}
+
+#[test]
+fn tick_at_intervals() {
+    tokio_uring::start(async {
+        let start = Instant::now();
+        let mut interval = interval(Duration::from_millis(10));
+
+        let first = interval.tick().await;
+        assert!(first.duration_since(start) < Duration::from_millis(10));
+        for n in 1..=3 {
+            let tick = interval.tick().await;
+            assert_eq!(tick, first + Duration::from_millis(10) * n);
+            assert!(Instant::now() >= tick);
+        }
+    });
+}
+
+async fn poll_once<F: std::future::Future + Unpin>(fut: &mut F) {
+    let mut fut = std::pin::Pin::new(fut);
+    std::future::poll_fn(|cx| {
+        assert!(fut.as_mut().poll(cx).is_pending());
+        std::task::Poll::Ready(())
+    })
+    .await
+}
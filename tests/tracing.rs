@@ -0,0 +1,88 @@
+#![cfg(feature = "tracing")]
+
+use std::fmt::Debug;
+
+use std::sync::{Arc, Mutex};
+use tokio_uring::Submit;
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+// Collects the events of the driver, with their fields.
+#[derive(Clone, Default)]
+struct Collector {
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push_str(&format!("{}={:?} ", field.name(), value));
+    }
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target().starts_with("tokio_uring")
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(String::new());
+        event.record(&mut fields);
+        self.events.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn trace_ops() {
+    let collector = Collector::default();
+    let events = collector.events.clone();
+
+    tracing::subscriber::with_default(collector, || {
+        tokio_uring::start(async {
+            let file = std::fs::File::open("/dev/null").unwrap();
+            let file = tokio_uring::fs::File::from_std(file);
+            let (n, _) = file.read_at(vec![0; 4], 0).submit().await.unwrap();
+            assert_eq!(n, 0);
+            file.close().await.unwrap();
+        });
+    });
+
+    let events = events.lock().unwrap();
+    let find = |what: &str| {
+        events
+            .iter()
+            .find(|event| {
+                event.starts_with(&format!("message={} ", what)) && event.contains("opcode=22")
+            })
+            .unwrap_or_else(|| panic!("no {} event in {:#?}", what, events))
+            .clone()
+    };
+
+    // A read (`IORING_OP_READ`) of nothing.
+    let submit = find("submit op");
+    assert!(submit.contains("fd="));
+    let complete = find("complete op");
+    assert!(complete.contains("result=0 "));
+    assert!(complete.contains("latency_us="));
+    assert!(events
+        .iter()
+        .any(|e| e.starts_with("message=enter kernel ")));
+    assert!(events
+        .iter()
+        .any(|e| e.starts_with("message=dispatch completions ")));
+}